@@ -3,12 +3,14 @@ use clap::{CommandFactory, Parser, Subcommand};
 use clap_complete::{self, Shell};
 use huak_home::huak_home_dir;
 use huak_package_manager::ops::{
-    self, install as install_op, AddOptions, BuildOptions, CleanOptions, FormatOptions,
-    LintOptions, PublishOptions, RemoveOptions, TestOptions, UpdateOptions,
+    self, install as install_op, AddOptions, AuditOptions, BuildOptions, CleanOptions,
+    ExportFormat, ExportOptions, FormatOptions, LicensesFormat, LicensesOptions, LintOptions,
+    LockOptions, OutdatedOptions, PublishOptions, RemoveOptions, SyncOptions, TestOptions,
+    TreeOptions, UpdateOptions, UpdateStrategy,
 };
 use huak_package_manager::{
-    Config, Error as HuakError, HuakResult, InstallOptions, TerminalOptions, Verbosity,
-    WorkspaceOptions,
+    Config, Error as HuakError, HuakResult, ImportSource, InstallOptions, ManifestGroup,
+    OutputFormat, TerminalOptions, Verbosity, VersionBound, WorkspaceOptions,
 };
 use huak_python_manager::RequestedVersion;
 use huak_toolchain::{Channel, LocalTool};
@@ -22,6 +24,7 @@ use url::Url;
 /// A Python package manager written in Rust inspired by Cargo.
 #[derive(Parser)]
 #[command(version, author, about, arg_required_else_help = true)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Cli {
     #[command(subcommand)]
     command: Commands,
@@ -29,6 +32,15 @@ pub struct Cli {
     quiet: bool,
     #[arg(long, global = true)]
     no_color: bool,
+    /// Require huak.lock to be up to date and don't modify pyproject.toml.
+    #[arg(long, global = true)]
+    locked: bool,
+    /// Use huak.lock as-is and don't modify pyproject.toml.
+    #[arg(long, global = true)]
+    frozen: bool,
+    /// Disable network access. Packages are resolved from find-links directories and Huak's cache.
+    #[arg(long, global = true)]
+    offline: bool,
 }
 
 // List of commands.
@@ -44,16 +56,52 @@ enum Commands {
         /// Adds an optional dependency group.
         #[arg(long)]
         group: Option<String>,
+        /// Adds a dependency group from `[dependency-groups]` (not published with the project).
+        #[arg(long, conflicts_with = "group")]
+        dependency_group: Option<String>,
+        /// Version bound for dependencies added without a specifier (exact, compatible,
+        /// lower or major).
+        #[arg(long)]
+        bound: Option<VersionBound>,
+        /// Install local path dependencies in editable mode.
+        #[arg(short, long)]
+        editable: bool,
+        /// Use the system Python environment instead of a virtual environment.
+        #[arg(long)]
+        system: bool,
+        /// Allow modifying an externally managed system Python environment.
+        #[arg(long)]
+        break_system_packages: bool,
         /// Pass trailing arguments with `--`.
         #[arg(last = true)]
         trailing: Option<Vec<String>>,
     },
+    /// Check the project's packages for known vulnerabilities.
+    Audit {
+        /// Output format (table or json).
+        #[arg(long, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+        /// The OSV advisory database to use (a directory, a zip file or a URL).
+        #[arg(long)]
+        database: Option<String>,
+        /// Ignore an advisory by its ID or alias.
+        #[arg(long)]
+        ignore: Vec<String>,
+        /// Audit the packages in huak.lock instead of the installed packages.
+        #[arg(long)]
+        lock_file: bool,
+    },
     /// Build tarball and wheel for the project.
     Build {
         /// Pass trailing arguments with `--`.
         #[arg(last = true)]
         trailing: Option<Vec<String>>,
     },
+    /// Manage the package cache.
+    Cache {
+        #[command(subcommand)]
+        command: Cache,
+    },
     /// Remove tarball and wheel from the built project.
     Clean {
         #[arg(long, required = false)]
@@ -68,6 +116,21 @@ enum Commands {
         #[arg(short, long, value_name = "shell")]
         shell: Option<Shell>,
     },
+    /// Export the project's dependencies to a requirements, constraints or pylock.toml file.
+    Export {
+        /// Output format (requirements-txt, constraints or pylock).
+        #[arg(long, default_value_t = ExportFormat::RequirementsTxt)]
+        format: ExportFormat,
+        /// Groups to export with the required dependencies (defaults to all groups).
+        #[arg(long, num_args = 1..)]
+        groups: Option<Vec<String>>,
+        /// Include package hashes from huak.lock.
+        #[arg(long)]
+        hashes: bool,
+        /// Write to a file instead of stdout.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
     /// Auto-fix fixable lint conflicts
     Fix {
         /// Pass trailing arguments with `--`.
@@ -97,15 +160,27 @@ enum Commands {
         /// Initialize with a project manifest.
         #[arg(long)]
         manifest: Option<PathBuf>,
+        /// Import dependencies from a requirements file, Pipfile or Poetry project (`poetry`).
+        #[arg(long, value_name = "SOURCE")]
+        from: Option<ImportSource>,
         /// Initialize without setting up a Python environment.
         #[arg(long)]
         no_env: bool,
         /// Optional dependency groups to install.
         #[arg(long)]
         optional_dependencies: Option<Vec<String>>,
+        /// Dependency groups from `[dependency-groups]` to install.
+        #[arg(long)]
+        dependency_groups: Option<Vec<String>>,
         /// Force the initialization.
         #[arg(short, long)]
         force: bool,
+        /// Use the system Python environment instead of a virtual environment.
+        #[arg(long)]
+        system: bool,
+        /// Allow modifying an externally managed system Python environment.
+        #[arg(long)]
+        break_system_packages: bool,
         /// Pass trailing arguments with `--`.
         #[arg(last = true)]
         trailing: Option<Vec<String>>,
@@ -120,14 +195,21 @@ enum Commands {
         #[arg(long, alias = "py", required = false)]
         python_version: Option<RequestedVersion>,
         // TODO(cnpryer): Deps (document this)
-        /// The package index to use.
-        #[arg(
-            long,
-            alias = "index-url",
-            default_value = "https://pypi.python.org/simple",
-            required = false
-        )] // TODO(cnpryer): Names
-        package_index_url: Url,
+        /// The package index to use instead of the default index.
+        #[arg(long, alias = "index-url", required = false)] // TODO(cnpryer): Names
+        package_index_url: Option<Url>,
+        /// Use the system Python environment instead of a virtual environment.
+        #[arg(long)]
+        system: bool,
+        /// Allow modifying an externally managed system Python environment.
+        #[arg(long)]
+        break_system_packages: bool,
+    },
+    /// Display the licenses of the installed packages.
+    Licenses {
+        /// Output format (table, csv or json).
+        #[arg(long, default_value_t = LicensesFormat::Table)]
+        format: LicensesFormat,
     },
     /// Lint the project's Python code.
     Lint {
@@ -141,6 +223,12 @@ enum Commands {
         #[arg(last = true)]
         trailing: Option<Vec<String>>,
     },
+    /// Resolve the project's dependencies and write them to huak.lock.
+    Lock {
+        /// Pass trailing arguments with `--`.
+        #[arg(last = true)]
+        trailing: Option<Vec<String>>,
+    },
     /// Create a new project at <path>.
     New {
         /// Use an application template.
@@ -155,6 +243,15 @@ enum Commands {
         #[arg(long)]
         no_vcs: bool,
     },
+    /// Display the project's dependencies that have newer versions available.
+    Outdated {
+        /// Output format (table or json).
+        #[arg(long, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+        /// The package index to check (defaults to pypi.org).
+        #[arg(long)]
+        index_url: Option<String>,
+    },
     /// Builds and uploads current project to a registry.
     Publish {
         /// Pass trailing arguments with `--`.
@@ -171,6 +268,18 @@ enum Commands {
     Remove {
         #[arg(num_args = 1.., required = true)]
         dependencies: Vec<String>,
+        /// Only remove the dependencies from an optional dependency group.
+        #[arg(long)]
+        group: Option<String>,
+        /// Only remove the dependencies from a dependency group in `[dependency-groups]`.
+        #[arg(long, conflicts_with = "group")]
+        dependency_group: Option<String>,
+        /// Use the system Python environment instead of a virtual environment.
+        #[arg(long)]
+        system: bool,
+        /// Allow modifying an externally managed system Python environment.
+        #[arg(long)]
+        break_system_packages: bool,
         /// Pass trailing arguments with `--`.
         #[arg(last = true)]
         trailing: Option<Vec<String>>,
@@ -180,6 +289,21 @@ enum Commands {
         #[arg(trailing_var_arg = true)]
         command: Vec<String>,
     },
+    /// Sync the Python environment with the project's locked dependencies.
+    Sync {
+        /// Optional dependency groups to sync (defaults to all groups).
+        #[arg(long, num_args = 1..)]
+        groups: Option<Vec<String>>,
+        /// Use the system Python environment instead of a virtual environment.
+        #[arg(long)]
+        system: bool,
+        /// Allow modifying an externally managed system Python environment.
+        #[arg(long)]
+        break_system_packages: bool,
+        /// Pass trailing arguments with `--`.
+        #[arg(last = true)]
+        trailing: Option<Vec<String>>,
+    },
     /// Test the project's Python code.
     Test {
         /// Pass trailing arguments with `--`.
@@ -192,16 +316,66 @@ enum Commands {
         #[command(subcommand)]
         command: Toolchain,
     },
+    /// Display the tree of the project's installed dependencies.
+    Tree {
+        /// Maximum depth of the tree (direct dependencies have a depth of 1).
+        #[arg(long)]
+        depth: Option<usize>,
+        /// Invert the tree for a package to display what depends on it.
+        #[arg(long, short, value_name = "PACKAGE")]
+        invert: Option<String>,
+        /// Only display packages that are depended on more than once.
+        #[arg(long, short, conflicts_with = "invert")]
+        duplicates: bool,
+    },
     /// Update the project's dependencies.
     Update {
         #[arg(num_args = 0..)]
         dependencies: Option<Vec<String>>,
+        /// Rewrite version specifiers for the newest release.
+        #[arg(long, conflicts_with = "compatible")]
+        latest: bool,
+        /// Raise lower bounds to the newest version satisfying the version specifiers.
+        #[arg(long, conflicts_with = "latest")]
+        compatible: bool,
+        /// Only update the dependencies in an optional dependency group.
+        #[arg(long)]
+        group: Option<String>,
+        /// Only update the dependencies in a dependency group in `[dependency-groups]`.
+        #[arg(long, conflicts_with = "group")]
+        dependency_group: Option<String>,
+        /// Display the changes to pyproject.toml without updating anything.
+        #[arg(long)]
+        dry_run: bool,
+        /// Use the system Python environment instead of a virtual environment.
+        #[arg(long)]
+        system: bool,
+        /// Allow modifying an externally managed system Python environment.
+        #[arg(long)]
+        break_system_packages: bool,
         /// Pass trailing arguments with `--`.
         #[arg(last = true)]
         trailing: Option<Vec<String>>,
     },
     /// Display the version of the project.
     Version,
+    /// Display how an installed package is required by the project.
+    Why {
+        /// The name of the installed package.
+        package: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum Cache {
+    /// Remove everything from the cache.
+    Clean,
+    /// Display the path to the cache directory.
+    Dir,
+    /// Display information about the cache.
+    Info,
+    /// Remove cached packages that aren't installed to any environment.
+    Prune,
 }
 
 #[derive(Subcommand)]
@@ -313,12 +487,40 @@ fn exec_command(cmd: Commands, config: &mut Config) -> HuakResult<()> {
         Commands::Add {
             dependencies,
             group,
+            dependency_group,
+            bound,
+            editable,
+            system,
+            break_system_packages,
             trailing,
         } => {
+            config.system = system;
+            config.break_system_packages = break_system_packages;
             let options = AddOptions {
+                bound,
+                editable,
                 install_options: InstallOptions { values: trailing },
             };
-            add(&dependencies, group.as_ref(), &options, config)
+            add(
+                &dependencies,
+                manifest_group(group, dependency_group).as_ref(),
+                &options,
+                config,
+            )
+        }
+        Commands::Audit {
+            format,
+            database,
+            ignore,
+            lock_file,
+        } => {
+            let options = AuditOptions {
+                format,
+                database,
+                ignore,
+                lock_file,
+            };
+            audit(&options, config)
         }
         Commands::Build { trailing } => {
             let options = BuildOptions {
@@ -327,6 +529,7 @@ fn exec_command(cmd: Commands, config: &mut Config) -> HuakResult<()> {
             };
             build(&options, config)
         }
+        Commands::Cache { command } => cache(&command, config),
         Commands::Clean {
             include_pyc,
             include_pycache,
@@ -342,6 +545,20 @@ fn exec_command(cmd: Commands, config: &mut Config) -> HuakResult<()> {
             completion(&options);
             Ok(())
         }
+        Commands::Export {
+            format,
+            groups,
+            hashes,
+            output,
+        } => {
+            let options = ExportOptions {
+                format,
+                groups,
+                hashes,
+                output,
+            };
+            export(&options, config)
+        }
         Commands::Fix { trailing } => {
             let options = LintOptions {
                 values: trailing,
@@ -370,11 +587,17 @@ fn exec_command(cmd: Commands, config: &mut Config) -> HuakResult<()> {
             lib,
             no_vcs,
             manifest,
+            from,
             no_env,
             optional_dependencies,
+            dependency_groups,
             trailing,
             force,
+            system,
+            break_system_packages,
         } => {
+            config.system = system;
+            config.break_system_packages = break_system_packages;
             config.workspace_root.clone_from(&config.cwd);
             let workspace_options = WorkspaceOptions {
                 uses_git: !no_vcs,
@@ -386,11 +609,13 @@ fn exec_command(cmd: Commands, config: &mut Config) -> HuakResult<()> {
             // TODO(cnpryer): Use `WorkspaceOptions` where possible.
             init(
                 manifest,
+                from,
                 optional_dependencies,
+                dependency_groups,
                 app,
                 force,
-                lib,
                 no_env,
+                lib,
                 &install_options,
                 &workspace_options,
                 config,
@@ -400,7 +625,17 @@ fn exec_command(cmd: Commands, config: &mut Config) -> HuakResult<()> {
             package,
             python_version,
             package_index_url,
-        } => install(&package, python_version, &package_index_url, config),
+            system,
+            break_system_packages,
+        } => {
+            config.system = system;
+            config.break_system_packages = break_system_packages;
+            install(&package, python_version, package_index_url.as_ref(), config)
+        }
+        Commands::Licenses { format } => {
+            let options = LicensesOptions { format };
+            licenses(&options, config)
+        }
         Commands::Lint {
             fix,
             no_types,
@@ -421,6 +656,12 @@ fn exec_command(cmd: Commands, config: &mut Config) -> HuakResult<()> {
             };
             lint(config, &options)
         }
+        Commands::Lock { trailing } => {
+            let options = LockOptions {
+                install_options: InstallOptions { values: trailing },
+            };
+            lock(&options, config)
+        }
         Commands::New {
             path,
             app,
@@ -434,6 +675,10 @@ fn exec_command(cmd: Commands, config: &mut Config) -> HuakResult<()> {
             };
             new(&options, app, lib, config)
         }
+        Commands::Outdated { format, index_url } => {
+            let options = OutdatedOptions { format, index_url };
+            outdated(&options, config)
+        }
         Commands::Publish { trailing } => {
             let options = PublishOptions {
                 values: trailing,
@@ -444,14 +689,35 @@ fn exec_command(cmd: Commands, config: &mut Config) -> HuakResult<()> {
         Commands::Python { command } => python(command, config),
         Commands::Remove {
             dependencies,
+            group,
+            dependency_group,
+            system,
+            break_system_packages,
             trailing,
         } => {
+            config.system = system;
+            config.break_system_packages = break_system_packages;
             let options = RemoveOptions {
+                group: manifest_group(group, dependency_group),
                 install_options: InstallOptions { values: trailing },
             };
             remove(&dependencies, &options, config)
         }
         Commands::Run { command } => run(&command, config),
+        Commands::Sync {
+            groups,
+            system,
+            break_system_packages,
+            trailing,
+        } => {
+            config.system = system;
+            config.break_system_packages = break_system_packages;
+            let options = SyncOptions {
+                groups,
+                install_options: InstallOptions { values: trailing },
+            };
+            sync(&options, config)
+        }
         Commands::Test { trailing } => {
             let options = TestOptions {
                 values: trailing,
@@ -460,16 +726,48 @@ fn exec_command(cmd: Commands, config: &mut Config) -> HuakResult<()> {
             test(&options, config)
         }
         Commands::Toolchain { command } => toolchain(command, config),
+        Commands::Tree {
+            depth,
+            invert,
+            duplicates,
+        } => {
+            let options = TreeOptions {
+                depth,
+                invert,
+                duplicates,
+            };
+            tree(&options, config)
+        }
         Commands::Update {
             dependencies,
+            latest,
+            compatible,
+            group,
+            dependency_group,
+            dry_run,
+            system,
+            break_system_packages,
             trailing,
         } => {
+            config.system = system;
+            config.break_system_packages = break_system_packages;
+            let strategy = if latest {
+                UpdateStrategy::Latest
+            } else if compatible {
+                UpdateStrategy::Compatible
+            } else {
+                UpdateStrategy::Keep
+            };
             let options = UpdateOptions {
+                strategy,
+                group: manifest_group(group, dependency_group),
+                dry_run,
                 install_options: InstallOptions { values: trailing },
             };
             update(dependencies, &options, config)
         }
         Commands::Version => version(config),
+        Commands::Why { package } => why(&package, config),
     }
 }
 
@@ -490,6 +788,11 @@ fn get_config(cwd: PathBuf, cli: &Cli) -> Config {
         cwd,
         terminal_options,
         home: huak_home_dir(),
+        locked: cli.locked,
+        frozen: cli.frozen,
+        offline: cli.offline,
+        system: false,
+        break_system_packages: false,
     };
     if cli.no_color {
         config.terminal_options = TerminalOptions {
@@ -506,7 +809,7 @@ fn activate(config: &Config) -> HuakResult<()> {
 
 fn add(
     dependencies: &[Dependency],
-    group: Option<&String>,
+    group: Option<&ManifestGroup>,
     options: &AddOptions,
     config: &Config,
 ) -> HuakResult<()> {
@@ -514,20 +817,42 @@ fn add(
         .iter()
         .map(std::string::ToString::to_string)
         .collect::<Vec<String>>();
-    match group.as_ref() {
-        Some(it) => ops::add_project_optional_dependencies(&deps, it, config, options),
+    match group {
+        Some(ManifestGroup::Optional(it)) => {
+            ops::add_project_optional_dependencies(&deps, it, config, options)
+        }
+        Some(ManifestGroup::Dependency(it)) => {
+            ops::add_dependency_group_dependencies(&deps, it, config, options)
+        }
         None => ops::add_project_dependencies(&deps, config, options),
     }
 }
 
+fn audit(options: &AuditOptions, config: &Config) -> HuakResult<()> {
+    ops::audit_project(config, options)
+}
+
 fn build(options: &BuildOptions, config: &Config) -> HuakResult<()> {
     ops::build_project(config, options)
 }
 
+fn cache(command: &Cache, config: &Config) -> HuakResult<()> {
+    match command {
+        Cache::Clean => ops::clean_cache(config),
+        Cache::Dir => ops::display_cache_dir(config),
+        Cache::Info => ops::display_cache_info(config),
+        Cache::Prune => ops::prune_cache(config),
+    }
+}
+
 fn clean(options: &CleanOptions, config: &Config) -> HuakResult<()> {
     ops::clean_project(config, options)
 }
 
+fn export(options: &ExportOptions, config: &Config) -> HuakResult<()> {
+    ops::export_project(config, options)
+}
+
 fn fix(options: &LintOptions, config: &Config) -> HuakResult<()> {
     ops::lint_project(config, options)
 }
@@ -540,7 +865,9 @@ fn fmt(options: &FormatOptions, config: &Config) -> HuakResult<()> {
 #[allow(clippy::fn_params_excessive_bools)]
 fn init(
     manifest: Option<PathBuf>,
+    from: Option<ImportSource>,
     optional_dependencies: Option<Vec<String>>,
+    dependency_groups: Option<Vec<String>>,
     app: bool,
     force: bool,
     no_env: bool,
@@ -555,6 +882,14 @@ fn init(
         ops::init_lib_project(config, workspace_options)
     };
 
+    // Import dependencies into the new manifest file or the existing one.
+    let res = match (res, from) {
+        (Ok(()) | Err(HuakError::ManifestFileFound), Some(source)) => {
+            ops::import_dependencies(config, &source)
+        }
+        (res, _) => res,
+    };
+
     // If initialization failed because a manifest file already exists and the project
     // initialization option 'no-env' is 'false' then we attempt to inititialize the
     // project's Python environment.
@@ -567,6 +902,7 @@ fn init(
         ops::init_python_env(
             manifest,
             optional_dependencies,
+            dependency_groups,
             force,
             install_options,
             config,
@@ -579,16 +915,29 @@ fn init(
 fn install(
     package: &Requirement,
     python_version: Option<RequestedVersion>,
-    package_index_url: &Url,
+    package_index_url: Option<&Url>,
     config: &Config,
 ) -> HuakResult<()> {
-    install_op(package, python_version, package_index_url.as_str(), config)
+    install_op(
+        package,
+        python_version,
+        package_index_url.map(Url::as_str),
+        config,
+    )
+}
+
+fn licenses(options: &LicensesOptions, config: &Config) -> HuakResult<()> {
+    ops::display_licenses(config, options)
 }
 
 fn lint(config: &Config, options: &LintOptions) -> HuakResult<()> {
     ops::lint_project(config, options)
 }
 
+fn lock(options: &LockOptions, config: &Config) -> HuakResult<()> {
+    ops::lock_project(config, options)
+}
+
 fn new(options: &WorkspaceOptions, app: bool, _lib: bool, config: &Config) -> HuakResult<()> {
     if app {
         ops::new_app_project(config, options)
@@ -597,6 +946,10 @@ fn new(options: &WorkspaceOptions, app: bool, _lib: bool, config: &Config) -> Hu
     }
 }
 
+fn outdated(options: &OutdatedOptions, config: &Config) -> HuakResult<()> {
+    ops::display_outdated_dependencies(config, options)
+}
+
 fn publish(options: &PublishOptions, config: &Config) -> HuakResult<()> {
     ops::publish_project(config, options)
 }
@@ -609,6 +962,16 @@ fn python(command: Python, config: &Config) -> HuakResult<()> {
     }
 }
 
+/// Get the `ManifestGroup` selected with `--group` or `--dependency-group`.
+fn manifest_group(
+    group: Option<String>,
+    dependency_group: Option<String>,
+) -> Option<ManifestGroup> {
+    group
+        .map(ManifestGroup::Optional)
+        .or(dependency_group.map(ManifestGroup::Dependency))
+}
+
 fn remove(dependencies: &[String], options: &RemoveOptions, config: &Config) -> HuakResult<()> {
     ops::remove_project_dependencies(dependencies, config, options)
 }
@@ -617,6 +980,10 @@ fn run(command: &[String], config: &Config) -> HuakResult<()> {
     ops::run_command_str(&command.join(" "), config)
 }
 
+fn sync(options: &SyncOptions, config: &Config) -> HuakResult<()> {
+    ops::sync_project(config, options)
+}
+
 fn test(options: &TestOptions, config: &Config) -> HuakResult<()> {
     ops::test_project(config, options)
 }
@@ -641,6 +1008,10 @@ fn toolchain(command: Toolchain, config: &Config) -> HuakResult<()> {
     }
 }
 
+fn tree(options: &TreeOptions, config: &Config) -> HuakResult<()> {
+    ops::display_dependency_tree(config, options)
+}
+
 fn update(
     dependencies: Option<Vec<String>>,
     options: &UpdateOptions,
@@ -653,6 +1024,10 @@ fn version(config: &Config) -> HuakResult<()> {
     ops::display_project_version(config)
 }
 
+fn why(package: &str, config: &Config) -> HuakResult<()> {
+    ops::display_dependency_paths(package, config)
+}
+
 fn completion(options: &CompletionOptions) {
     generate_shell_completion_script(options.shell);
 }
@@ -678,7 +1053,10 @@ impl FromStr for Dependency {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self(s.replace('@', "==")))
+        let requirement = huak_package_manager::requirement_from_arg(s, current_dir()?)
+            .map_err(|e| Error::new(e, ExitCode::FAILURE))?;
+
+        Ok(Self(requirement))
     }
 }
 
@@ -7,6 +7,7 @@ use clap::Parser;
 use cli::Cli;
 use colored::Colorize;
 use huak_home::huak_home_dir;
+use huak_package_manager::redact;
 use human_panic::setup_panic;
 use std::{
     env,
@@ -54,7 +55,12 @@ pub fn main() -> ExitCode {
             if e.error.to_string().is_empty() {
                 eprintln!("{}", e.error);
             } else {
-                eprintln!("{}{} {}", "error".red(), ":".bold(), e.error);
+                eprintln!(
+                    "{}{} {}",
+                    "error".red(),
+                    ":".bold(),
+                    redact(&e.error.to_string())
+                );
             }
             e.exit_code
         }
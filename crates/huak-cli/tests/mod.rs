@@ -13,11 +13,21 @@ mod tests {
         assert_cmd_snapshot!(Command::new(bin()).arg("add").arg("--help"));
     }
 
+    #[test]
+    fn test_audit_help() {
+        assert_cmd_snapshot!(Command::new(bin()).arg("audit").arg("--help"));
+    }
+
     #[test]
     fn test_build_help() {
         assert_cmd_snapshot!(Command::new(bin()).arg("build").arg("--help"));
     }
 
+    #[test]
+    fn test_cache_help() {
+        assert_cmd_snapshot!(Command::new(bin()).arg("cache").arg("--help"));
+    }
+
     #[test]
     fn test_clean_help() {
         assert_cmd_snapshot!(Command::new(bin()).arg("clean").arg("--help"));
@@ -28,6 +38,11 @@ mod tests {
         assert_cmd_snapshot!(Command::new(bin()).arg("completion").arg("--help"));
     }
 
+    #[test]
+    fn test_export_help() {
+        assert_cmd_snapshot!(Command::new(bin()).arg("export").arg("--help"));
+    }
+
     #[test]
     fn test_fix_help() {
         assert_cmd_snapshot!(Command::new(bin()).arg("fix").arg("--help"));
@@ -49,21 +64,53 @@ mod tests {
         assert_cmd_snapshot!(Command::new(bin()).arg("init").arg("--help"));
     }
 
+    /// `--no-env` skips the Python environment even when the project has dependencies to
+    /// install.
+    #[test]
+    fn test_init_no_env() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("requirements.txt"), "click==8.1.7\n").unwrap();
+        let status = Command::new(bin())
+            .args(["init", "--no-env", "--no-vcs", "--from", "requirements.txt"])
+            .current_dir(dir.path())
+            .status()
+            .unwrap();
+
+        assert!(status.success());
+        assert!(dir.path().join("pyproject.toml").exists());
+        assert!(!dir.path().join(".venv").exists());
+    }
+
     #[test]
     fn test_install_help() {
         assert_cmd_snapshot!(Command::new(bin()).arg("install").arg("--help"));
     }
 
+    #[test]
+    fn test_licenses_help() {
+        assert_cmd_snapshot!(Command::new(bin()).arg("licenses").arg("--help"));
+    }
+
     #[test]
     fn test_lint_help() {
         assert_cmd_snapshot!(Command::new(bin()).arg("lint").arg("--help"));
     }
 
+    #[test]
+    fn test_lock_help() {
+        assert_cmd_snapshot!(Command::new(bin()).arg("lock").arg("--help"));
+    }
+
     #[test]
     fn test_new_help() {
         assert_cmd_snapshot!(Command::new(bin()).arg("new").arg("--help"));
     }
 
+    #[test]
+    fn test_outdated_help() {
+        assert_cmd_snapshot!(Command::new(bin()).arg("outdated").arg("--help"));
+    }
+
     #[test]
     fn test_publish_help() {
         assert_cmd_snapshot!(Command::new(bin()).arg("publish").arg("--help"));
@@ -84,11 +131,21 @@ mod tests {
         assert_cmd_snapshot!(Command::new(bin()).arg("run").arg("--help"));
     }
 
+    #[test]
+    fn test_sync_help() {
+        assert_cmd_snapshot!(Command::new(bin()).arg("sync").arg("--help"));
+    }
+
     #[test]
     fn test_test_help() {
         assert_cmd_snapshot!(Command::new(bin()).arg("test").arg("--help"));
     }
 
+    #[test]
+    fn test_tree_help() {
+        assert_cmd_snapshot!(Command::new(bin()).arg("tree").arg("--help"));
+    }
+
     #[test]
     fn test_update_help() {
         assert_cmd_snapshot!(Command::new(bin()).arg("update").arg("--help"));
@@ -108,6 +165,11 @@ mod tests {
             .current_dir(from));
     }
 
+    #[test]
+    fn test_why_help() {
+        assert_cmd_snapshot!(Command::new(bin()).arg("why").arg("--help"));
+    }
+
     /// The resource directory found in the Huak repo used for testing purposes.
     fn dev_resources_dir() -> PathBuf {
         PathBuf::from(env!("CARGO_MANIFEST_DIR"))
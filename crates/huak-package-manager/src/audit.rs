@@ -0,0 +1,531 @@
+use crate::{lock::normalized_name, Error, HuakResult};
+use pep440_rs::Version;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    io::{Read, Seek},
+    path::Path,
+    str::FromStr,
+};
+
+/// The OSV ecosystem of Python packages.
+const PYPI_ECOSYSTEM: &str = "PyPI";
+
+/// A database of OSV (<https://ossf.github.io/osv-schema/>) advisories for Python packages.
+///
+/// Databases are loaded from a directory of advisory JSON files or a zip export of one
+/// (like <https://osv-vulnerabilities.storage.googleapis.com/PyPI/all.zip>). Withdrawn
+/// advisories and advisories for other ecosystems are ignored.
+///
+/// ```no_run
+/// use huak_package_manager::AdvisoryDatabase;
+///
+/// let db = AdvisoryDatabase::from_path("advisories.zip").unwrap();
+/// let vulnerabilities = db
+///     .vulnerabilities("requests", &"2.25.0".parse().unwrap())
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AdvisoryDatabase {
+    /// The advisories affecting each package by normalized name.
+    advisories: BTreeMap<String, Vec<Advisory>>,
+}
+
+impl AdvisoryDatabase {
+    /// Load an `AdvisoryDatabase` from a directory of advisories, a zip export or a single
+    /// advisory file.
+    pub fn from_path<T: AsRef<Path>>(path: T) -> HuakResult<AdvisoryDatabase> {
+        let path = path.as_ref();
+        let mut db = AdvisoryDatabase::default();
+
+        if path.is_dir() {
+            for entry in glob::glob(&path.join("**").join("*.json").display().to_string())? {
+                let entry = entry?;
+                db.add(&entry.display().to_string(), &std::fs::read(&entry)?)?;
+            }
+        } else if path.extension().is_some_and(|it| it == "zip") {
+            db = AdvisoryDatabase::from_zip(std::fs::File::open(path)?)?;
+        } else {
+            db.add(&path.display().to_string(), &std::fs::read(path)?)?;
+        }
+
+        Ok(db)
+    }
+
+    /// Load an `AdvisoryDatabase` from a zip export of advisories.
+    pub fn from_zip<R: Read + Seek>(reader: R) -> HuakResult<AdvisoryDatabase> {
+        let mut archive = zip::ZipArchive::new(reader)
+            .map_err(|e| Error::InvalidAdvisory(format!("invalid zip archive: {e}")))?;
+        let mut db = AdvisoryDatabase::default();
+
+        for i in 0..archive.len() {
+            let mut file = archive
+                .by_index(i)
+                .map_err(|e| Error::InvalidAdvisory(format!("invalid zip archive: {e}")))?;
+            if !file.is_file()
+                || !Path::new(file.name())
+                    .extension()
+                    .is_some_and(|it| it == "json")
+            {
+                continue;
+            }
+
+            let name = file.name().to_string();
+            let mut bytes = Vec::new();
+            file.read_to_end(&mut bytes)?;
+            db.add(&name, &bytes)?;
+        }
+
+        Ok(db)
+    }
+
+    /// Get the number of advisories in the `AdvisoryDatabase`.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        let mut ids = self
+            .advisories
+            .values()
+            .flatten()
+            .map(|it| it.id.as_str())
+            .collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids.dedup();
+        ids.len()
+    }
+
+    /// Check if the `AdvisoryDatabase` has no advisories.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.advisories.is_empty()
+    }
+
+    /// Get the `Vulnerability`s affecting a version of a package.
+    ///
+    /// Returns `Error::InvalidAdvisory` if an advisory for the package has a range with a
+    /// version that can't be parsed, since the affected versions can't be known.
+    pub fn vulnerabilities(&self, name: &str, version: &Version) -> HuakResult<Vec<Vulnerability>> {
+        let name = normalized_name(name);
+        let Some(advisories) = self.advisories.get(&name) else {
+            return Ok(Vec::new());
+        };
+
+        let mut vulnerabilities = Vec::new();
+        for advisory in advisories {
+            let affected = advisory
+                .affected
+                .iter()
+                .filter(|it| it.is_package(&name))
+                .collect::<Vec<_>>();
+            let mut range = None;
+            for it in &affected {
+                range = it.affected_range(&advisory.id, version)?;
+                if range.is_some() {
+                    break;
+                }
+            }
+            let Some(range) = range else {
+                continue;
+            };
+            let mut fixed = affected
+                .iter()
+                .flat_map(|it| it.fixed_versions())
+                .filter(|it| it > version)
+                .collect::<Vec<_>>();
+            fixed.sort();
+            fixed.dedup();
+
+            vulnerabilities.push(Vulnerability {
+                name: name.clone(),
+                version: version.to_string(),
+                id: advisory.id.clone(),
+                aliases: advisory.aliases.clone(),
+                summary: advisory.summary.clone(),
+                affected: range,
+                fixed: fixed.iter().map(ToString::to_string).collect(),
+            });
+        }
+
+        Ok(vulnerabilities)
+    }
+
+    /// Add an advisory from the contents of its JSON file.
+    fn add(&mut self, filename: &str, bytes: &[u8]) -> HuakResult<()> {
+        let advisory = serde_json::from_slice::<Advisory>(bytes)
+            .map_err(|e| Error::InvalidAdvisory(format!("{filename}: {e}")))?;
+        if advisory.withdrawn.is_some() {
+            return Ok(());
+        }
+
+        let mut names = advisory
+            .affected
+            .iter()
+            .filter(|it| it.package.ecosystem == PYPI_ECOSYSTEM)
+            .map(|it| normalized_name(&it.package.name))
+            .collect::<Vec<_>>();
+        names.sort();
+        names.dedup();
+
+        for name in names {
+            self.advisories
+                .entry(name)
+                .or_default()
+                .push(advisory.clone());
+        }
+
+        Ok(())
+    }
+}
+
+/// A package version affected by an advisory.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Vulnerability {
+    /// The normalized name of the package.
+    pub name: String,
+    /// The affected version of the package.
+    pub version: String,
+    /// The ID of the advisory.
+    pub id: String,
+    /// Other IDs of the advisory (like its CVE).
+    pub aliases: Vec<String>,
+    /// A summary of the advisory.
+    pub summary: Option<String>,
+    /// The range of versions the version is affected in (for example ">=1.0, <1.2").
+    pub affected: String,
+    /// The versions newer than the affected version with a fix.
+    pub fixed: Vec<String>,
+}
+
+impl Vulnerability {
+    /// Check if the `Vulnerability`'s advisory has an ID or alias.
+    #[must_use]
+    pub fn has_id(&self, id: &str) -> bool {
+        self.id.eq_ignore_ascii_case(id)
+            || self.aliases.iter().any(|it| it.eq_ignore_ascii_case(id))
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct Advisory {
+    id: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    summary: Option<String>,
+    withdrawn: Option<String>,
+    #[serde(default)]
+    affected: Vec<AffectedPackage>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct AffectedPackage {
+    package: OsvPackage,
+    #[serde(default)]
+    ranges: Vec<AffectedRange>,
+    #[serde(default)]
+    versions: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct OsvPackage {
+    ecosystem: String,
+    name: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct AffectedRange {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    events: Vec<RangeEvent>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct RangeEvent {
+    introduced: Option<String>,
+    fixed: Option<String>,
+    last_affected: Option<String>,
+    limit: Option<String>,
+}
+
+/// An interval of affected versions. Missing bounds are unbounded.
+struct Interval {
+    /// The first affected version.
+    lower: Option<Version>,
+    /// The upper bound and whether it's affected itself.
+    upper: Option<(Version, bool)>,
+}
+
+impl Interval {
+    fn contains(&self, version: &Version) -> bool {
+        self.lower.as_ref().map_or(true, |it| version >= it)
+            && self.upper.as_ref().map_or(true, |(it, inclusive)| {
+                if *inclusive {
+                    version <= it
+                } else {
+                    version < it
+                }
+            })
+    }
+}
+
+impl std::fmt::Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lower = self.lower.as_ref().map(|it| format!(">={it}"));
+        let upper = self.upper.as_ref().map(|(it, inclusive)| {
+            if *inclusive {
+                format!("<={it}")
+            } else {
+                format!("<{it}")
+            }
+        });
+
+        match (lower, upper) {
+            (Some(lower), Some(upper)) => write!(f, "{lower}, {upper}"),
+            (Some(it), None) | (None, Some(it)) => write!(f, "{it}"),
+            (None, None) => write!(f, "*"),
+        }
+    }
+}
+
+impl AffectedPackage {
+    fn is_package(&self, name: &str) -> bool {
+        self.package.ecosystem == PYPI_ECOSYSTEM && normalized_name(&self.package.name) == name
+    }
+
+    /// Get the description of the range a version is affected in, if it's affected.
+    fn affected_range(&self, id: &str, version: &Version) -> HuakResult<Option<String>> {
+        if self
+            .versions
+            .iter()
+            .any(|it| Version::from_str(it).is_ok_and(|it| &it == version))
+        {
+            return Ok(Some(format!("=={version}")));
+        }
+
+        Ok(self
+            .intervals(id)?
+            .into_iter()
+            .find(|it| it.contains(version))
+            .map(|it| it.to_string()))
+    }
+
+    /// Get the affected `Interval`s of the package's ECOSYSTEM ranges. Events are sorted by
+    /// version and each introduced version starts an interval until the next fixed,
+    /// last affected or limit version.
+    ///
+    /// Returns `Error::InvalidAdvisory` if an event's version can't be parsed.
+    fn intervals(&self, id: &str) -> HuakResult<Vec<Interval>> {
+        let mut intervals = Vec::new();
+
+        for range in self.ranges.iter().filter(|it| it.kind == "ECOSYSTEM") {
+            let mut events = Vec::new();
+            for it in &range.events {
+                let (value, kind) = match (&it.introduced, &it.fixed, &it.last_affected, &it.limit)
+                {
+                    (Some(it), ..) if it == "0" => {
+                        events.push((None, Event::Introduced));
+                        continue;
+                    }
+                    (Some(it), ..) => (it, Event::Introduced),
+                    (_, Some(it), ..) => (it, Event::Fixed),
+                    (_, _, Some(it), _) => (it, Event::LastAffected),
+                    (_, _, _, Some(it)) => (it, Event::Fixed),
+                    _ => continue,
+                };
+                let version = Version::from_str(value).map_err(|_| {
+                    Error::InvalidAdvisory(format!(
+                        "{id}: {value} in the affected ranges of {} isn't a valid version",
+                        self.package.name
+                    ))
+                })?;
+                events.push((Some(version), kind));
+            }
+            events.sort_by(|a, b| a.0.cmp(&b.0));
+
+            let mut lower = None;
+            let mut open = false;
+            for (version, kind) in events {
+                match kind {
+                    Event::Introduced if !open => {
+                        lower = version;
+                        open = true;
+                    }
+                    Event::Fixed | Event::LastAffected if open => {
+                        intervals.push(Interval {
+                            lower: lower.take(),
+                            upper: version.map(|it| (it, kind == Event::LastAffected)),
+                        });
+                        open = false;
+                    }
+                    _ => (),
+                }
+            }
+            if open {
+                intervals.push(Interval { lower, upper: None });
+            }
+        }
+
+        Ok(intervals)
+    }
+
+    fn fixed_versions(&self) -> Vec<Version> {
+        self.ranges
+            .iter()
+            .filter(|it| it.kind == "ECOSYSTEM")
+            .flat_map(|it| &it.events)
+            .filter_map(|it| it.fixed.as_ref())
+            .filter_map(|it| Version::from_str(it).ok())
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Event {
+    Introduced,
+    Fixed,
+    LastAffected,
+}
+
+/// A mock advisory's ID, alias, package and range events. Events are (kind, version) with
+/// kinds like `introduced`, `fixed` or `last_affected`.
+#[cfg(test)]
+pub(crate) type MockAdvisory<'a> = (&'a str, &'a str, &'a str, &'a [(&'a str, &'a str)]);
+
+/// Write a JSON file for each `MockAdvisory` to a directory.
+#[cfg(test)]
+pub(crate) fn write_mock_advisories(dir: &Path, advisories: &[MockAdvisory]) {
+    std::fs::create_dir_all(dir).unwrap();
+
+    for (id, alias, package, events) in advisories {
+        let events = events
+            .iter()
+            .map(|(kind, version)| serde_json::json!({ *kind: version }))
+            .collect::<Vec<_>>();
+        let advisory = serde_json::json!({
+            "id": id,
+            "aliases": [alias],
+            "summary": format!("A mock vulnerability in {package}"),
+            "affected": [{
+                "package": { "ecosystem": "PyPI", "name": package },
+                "ranges": [{ "type": "ECOSYSTEM", "events": events }],
+            }],
+        });
+        std::fs::write(dir.join(format!("{id}.json")), advisory.to_string()).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    fn versions(db: &AdvisoryDatabase, name: &str, version: &str) -> Vec<(String, String)> {
+        db.vulnerabilities(name, &Version::from_str(version).unwrap())
+            .unwrap()
+            .into_iter()
+            .map(|it| (it.id, it.affected))
+            .collect()
+    }
+
+    #[test]
+    fn test_advisory_database_ranges() {
+        let dir = tempdir().unwrap();
+        write_mock_advisories(
+            dir.path(),
+            &[
+                (
+                    "PYSEC-0000-1",
+                    "CVE-0000-1",
+                    "Mock_Package",
+                    &[
+                        ("introduced", "0"),
+                        ("fixed", "1.2.0"),
+                        ("introduced", "2.0.0"),
+                        ("fixed", "2.1.0"),
+                    ],
+                ),
+                (
+                    "PYSEC-0000-2",
+                    "CVE-0000-2",
+                    "mock-package",
+                    &[("introduced", "2.0.0"), ("last_affected", "2.0.5")],
+                ),
+            ],
+        );
+        let db = AdvisoryDatabase::from_path(dir.path()).unwrap();
+
+        assert_eq!(db.len(), 2);
+        assert_eq!(
+            versions(&db, "mock-package", "1.0.0"),
+            [("PYSEC-0000-1".to_string(), "<1.2.0".to_string())]
+        );
+        assert!(versions(&db, "mock-package", "1.2.0").is_empty());
+        assert_eq!(
+            versions(&db, "mock_package", "2.0.5"),
+            [
+                ("PYSEC-0000-1".to_string(), ">=2.0.0, <2.1.0".to_string()),
+                ("PYSEC-0000-2".to_string(), ">=2.0.0, <=2.0.5".to_string())
+            ]
+        );
+        assert!(versions(&db, "mock-package", "2.1.0").is_empty());
+
+        let vulnerability = &db
+            .vulnerabilities("mock-package", &Version::from_str("1.0").unwrap())
+            .unwrap()[0];
+        assert_eq!(vulnerability.fixed, ["1.2.0", "2.1.0"]);
+        assert!(vulnerability.has_id("cve-0000-1"));
+    }
+
+    #[test]
+    fn test_advisory_database_invalid_version() {
+        let dir = tempdir().unwrap();
+        write_mock_advisories(
+            dir.path(),
+            &[(
+                "PYSEC-0000-4",
+                "CVE-0000-4",
+                "mock-package",
+                &[("introduced", "1.0"), ("fixed", "not a version")],
+            )],
+        );
+        let db = AdvisoryDatabase::from_path(dir.path()).unwrap();
+
+        assert!(matches!(
+            db.vulnerabilities("mock-package", &Version::from_str("2.0").unwrap()),
+            Err(Error::InvalidAdvisory(it)) if it.starts_with("PYSEC-0000-4")
+        ));
+        assert!(db
+            .vulnerabilities("other-package", &Version::from_str("2.0").unwrap())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_advisory_database_from_zip() {
+        let dir = tempdir().unwrap();
+        let advisories = dir.path().join("advisories");
+        write_mock_advisories(
+            &advisories,
+            &[(
+                "GHSA-mock",
+                "CVE-0000-3",
+                "mock-package",
+                &[("introduced", "1.0")],
+            )],
+        );
+        let path = dir.path().join("all.zip");
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+        zip.start_file("GHSA-mock.json", zip::write::FileOptions::default())
+            .unwrap();
+        zip.write_all(&std::fs::read(advisories.join("GHSA-mock.json")).unwrap())
+            .unwrap();
+        zip.finish().unwrap();
+
+        let db = AdvisoryDatabase::from_path(path).unwrap();
+
+        assert_eq!(
+            versions(&db, "mock-package", "3.0"),
+            [("GHSA-mock".to_string(), ">=1.0".to_string())]
+        );
+    }
+}
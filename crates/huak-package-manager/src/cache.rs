@@ -0,0 +1,348 @@
+use crate::{Credentials, DistributionFile, HuakResult, UnpackedWheel, Wheel};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// The directory cached wheels are unpacked to.
+const WHEELS_DIR_NAME: &str = "wheels";
+
+/// The directory cached wheel files are kept in. It's used as a `find-links` location in
+/// offline mode.
+const WHEELHOUSE_DIR_NAME: &str = "wheelhouse";
+
+/// The directory downloaded advisory databases are kept in.
+const ADVISORIES_DIR_NAME: &str = "advisories";
+
+/// The directory wheels are unpacked to before they're moved into the cache.
+const TEMP_DIR_NAME: &str = "tmp";
+
+/// A content-addressed cache of unpacked wheels shared by every `PythonEnvironment`.
+///
+/// Wheels are unpacked to {cache}/wheels/{sha256}, where the sha256 is the digest of the
+/// wheel file, and installed by hard linking their files. The wheel files are kept in
+/// {cache}/wheelhouse.
+///
+/// ```no_run
+/// use huak_package_manager::{Cache, DistributionFile, PythonEnvironment};
+///
+/// let python_env = PythonEnvironment::new(".venv").unwrap();
+/// let file = DistributionFile::from_url(
+///     "https://files.pythonhosted.org/packages/py3/c/click/click-8.1.7-py3-none-any.whl",
+/// )
+/// .unwrap();
+/// let cache = Cache::new("~/.huak/cache");
+/// let wheel = cache.wheel(&file, None).unwrap();
+/// wheel.link(&python_env.install_scheme()).unwrap();
+/// ```
+pub struct Cache {
+    root: PathBuf,
+}
+
+impl Cache {
+    /// Initialize a `Cache` at a directory.
+    pub fn new<T: Into<PathBuf>>(root: T) -> Cache {
+        Cache { root: root.into() }
+    }
+
+    /// Get a reference to the path to the `Cache`'s directory.
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Get the path to the directory cached wheel files are kept in.
+    #[must_use]
+    pub fn wheelhouse(&self) -> PathBuf {
+        self.root.join(WHEELHOUSE_DIR_NAME)
+    }
+
+    /// Get the path an advisory database downloaded from a URL is kept at.
+    #[must_use]
+    pub fn advisory_database(&self, url: &str) -> PathBuf {
+        self.root.join(ADVISORIES_DIR_NAME).join(format!(
+            "{}.zip",
+            hex::encode(Sha256::digest(url.as_bytes()))
+        ))
+    }
+
+    /// Check if the `Cache` has a wheel `DistributionFile`. Files without a sha256 hash
+    /// can't be found.
+    #[must_use]
+    pub fn contains(&self, file: &DistributionFile) -> bool {
+        file.hashes
+            .get("sha256")
+            .is_some_and(|it| self.wheel_path(it).exists())
+    }
+
+    /// Get the `UnpackedWheel` for a wheel `DistributionFile`. The file is downloaded (with
+    /// optional `Credentials`) and unpacked if the `Cache` doesn't have it yet.
+    pub fn wheel(
+        &self,
+        file: &DistributionFile,
+        credentials: Option<&Credentials>,
+    ) -> HuakResult<UnpackedWheel> {
+        if let Some(digest) = file.hashes.get("sha256") {
+            let path = self.wheel_path(digest);
+            if path.exists() {
+                return UnpackedWheel::new(path);
+            }
+        }
+
+        self.add(&file.filename, &file.download(credentials)?)
+    }
+
+    /// Add a `Wheel` file to the `Cache`.
+    pub fn add_wheel(&self, wheel: &Wheel) -> HuakResult<UnpackedWheel> {
+        let filename = wheel
+            .path()
+            .file_name()
+            .map(|it| it.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        self.add(&filename, &std::fs::read(wheel.path())?)
+    }
+
+    /// Get `CacheInfo` for the `Cache`.
+    pub fn info(&self) -> HuakResult<CacheInfo> {
+        if !self.root.exists() {
+            return Ok(CacheInfo::default());
+        }
+
+        let (files, size) = dir_size(&self.root)?;
+
+        Ok(CacheInfo {
+            wheels: self.wheel_paths()?.len(),
+            files,
+            size,
+        })
+    }
+
+    /// Remove everything from the `Cache`. Returns `CacheInfo` for what was removed.
+    pub fn clean(&self) -> HuakResult<CacheInfo> {
+        let info = self.info()?;
+
+        if self.root.exists() {
+            std::fs::remove_dir_all(&self.root)?;
+        }
+
+        Ok(info)
+    }
+
+    /// Remove the wheels that aren't installed to any environment and anything left over
+    /// from interrupted installs. Returns `CacheInfo` for what was removed.
+    ///
+    /// Installed wheels are detected by the hard links to their files. On platforms
+    /// without hard link counts cached wheels are kept.
+    pub fn prune(&self) -> HuakResult<CacheInfo> {
+        let mut info = CacheInfo::default();
+
+        for path in self.wheel_paths()? {
+            if is_linked(&path)? {
+                continue;
+            }
+
+            let (files, size) = dir_size(&path)?;
+            info.wheels += 1;
+            info.files += files;
+            info.size += size;
+            std::fs::remove_dir_all(path)?;
+        }
+
+        // Wheel files are removed with their unpacked wheels.
+        let wheelhouse = self.wheelhouse();
+        if wheelhouse.exists() {
+            for entry in std::fs::read_dir(wheelhouse)? {
+                let entry = entry?;
+                let digest = hex::encode(Sha256::digest(std::fs::read(entry.path())?));
+                if !self.wheel_path(&digest).exists() {
+                    info.files += 1;
+                    info.size += entry.metadata()?.len();
+                    std::fs::remove_file(entry.path())?;
+                }
+            }
+        }
+
+        let temp = self.root.join(TEMP_DIR_NAME);
+        if temp.exists() {
+            let (files, size) = dir_size(&temp)?;
+            info.files += files;
+            info.size += size;
+            std::fs::remove_dir_all(temp)?;
+        }
+
+        Ok(info)
+    }
+
+    fn add(&self, filename: &str, bytes: &[u8]) -> HuakResult<UnpackedWheel> {
+        let path = self.wheel_path(&hex::encode(Sha256::digest(bytes)));
+        if path.exists() {
+            return UnpackedWheel::new(path);
+        }
+
+        let temp = self.root.join(TEMP_DIR_NAME);
+        std::fs::create_dir_all(&temp)?;
+        let dir = tempfile::tempdir_in(temp)?;
+        let wheel_path = dir.path().join(filename);
+        std::fs::write(&wheel_path, bytes)?;
+        let unpacked = Wheel::new(&wheel_path)?.unpack(dir.path().join("unpacked"))?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // Another process might cache the same wheel while it's unpacked.
+        if let Err(e) = std::fs::rename(unpacked.path(), &path) {
+            if !path.exists() {
+                return Err(e.into());
+            }
+        }
+
+        let wheelhouse = self.wheelhouse();
+        std::fs::create_dir_all(&wheelhouse)?;
+        if !wheelhouse.join(filename).exists() {
+            std::fs::rename(&wheel_path, wheelhouse.join(filename))?;
+        }
+
+        UnpackedWheel::new(path)
+    }
+
+    fn wheel_path(&self, digest: &str) -> PathBuf {
+        self.root
+            .join(WHEELS_DIR_NAME)
+            .join(digest.to_ascii_lowercase())
+    }
+
+    fn wheel_paths(&self) -> HuakResult<Vec<PathBuf>> {
+        let dir = self.root.join(WHEELS_DIR_NAME);
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut paths = std::fs::read_dir(dir)?
+            .map(|it| it.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        paths.sort();
+
+        Ok(paths)
+    }
+}
+
+/// The number of wheels and files in a `Cache` and their total size in bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheInfo {
+    pub wheels: usize,
+    pub files: usize,
+    pub size: u64,
+}
+
+/// Get the number of files in a directory and their total size in bytes.
+fn dir_size(dir: &Path) -> HuakResult<(usize, u64)> {
+    let mut files = 0;
+    let mut size = 0;
+    let mut dirs = vec![dir.to_path_buf()];
+
+    while let Some(dir) = dirs.pop() {
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if metadata.is_dir() {
+                dirs.push(entry.path());
+            } else {
+                files += 1;
+                size += metadata.len();
+            }
+        }
+    }
+
+    Ok((files, size))
+}
+
+/// Check if any file in a directory is hard linked elsewhere.
+#[cfg(unix)]
+fn is_linked(dir: &Path) -> HuakResult<bool> {
+    use std::os::unix::fs::MetadataExt;
+
+    let mut dirs = vec![dir.to_path_buf()];
+
+    while let Some(dir) = dirs.pop() {
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if metadata.is_dir() {
+                dirs.push(entry.path());
+            } else if metadata.nlink() > 1 {
+                return Ok(true);
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+#[cfg(not(unix))]
+fn is_linked(_dir: &Path) -> HuakResult<bool> {
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{wheel::write_mock_wheel, InstallScheme};
+    use tempfile::tempdir;
+
+    fn mock_scheme(root: &Path) -> InstallScheme {
+        InstallScheme {
+            purelib: root.join("lib").join("site-packages"),
+            platlib: root.join("lib").join("site-packages"),
+            scripts: root.join("bin"),
+            headers: root.join("include"),
+            data: root.to_path_buf(),
+            python_path: root.join("bin").join("python"),
+        }
+    }
+
+    #[test]
+    fn test_cache_wheel() {
+        let dir = tempdir().unwrap();
+        let cache = Cache::new(dir.path().join("cache"));
+        let path = write_mock_wheel(dir.path(), "mock_package", "0.1.0");
+        let file = DistributionFile::from_url(&format!(
+            "{}#sha256={}",
+            url::Url::from_file_path(&path).unwrap(),
+            hex::encode(Sha256::digest(std::fs::read(&path).unwrap()))
+        ))
+        .unwrap();
+
+        let wheel = cache.wheel(&file, None).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // The cached wheel is used without downloading it again.
+        assert_eq!(cache.wheel(&file, None).unwrap().path(), wheel.path());
+        assert_eq!(cache.info().unwrap().wheels, 1);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_cache_prune() {
+        let dir = tempdir().unwrap();
+        let cache = Cache::new(dir.path().join("cache"));
+        let linked = cache
+            .add_wheel(&Wheel::new(write_mock_wheel(dir.path(), "linked", "0.1.0")).unwrap())
+            .unwrap();
+        cache
+            .add_wheel(&Wheel::new(write_mock_wheel(dir.path(), "unused", "0.1.0")).unwrap())
+            .unwrap();
+        std::fs::create_dir_all(cache.root().join(TEMP_DIR_NAME).join("leftover")).unwrap();
+        let distribution = linked.link(&mock_scheme(&dir.path().join("venv"))).unwrap();
+
+        let pruned = cache.prune().unwrap();
+
+        assert_eq!(pruned.wheels, 1);
+        assert!(linked.path().exists());
+        assert!(!cache.root().join(TEMP_DIR_NAME).exists());
+        assert_eq!(cache.info().unwrap().wheels, 1);
+
+        distribution.uninstall().unwrap();
+        assert_eq!(cache.prune().unwrap().wheels, 1);
+        assert_eq!(cache.clean().unwrap(), CacheInfo::default());
+        assert!(!cache.root().exists());
+    }
+}
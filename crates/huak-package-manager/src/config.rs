@@ -1,7 +1,7 @@
 use huak_home::huak_home_dir;
 use std::path::PathBuf;
 
-use crate::{sys::Terminal, workspace::Workspace, TerminalOptions};
+use crate::{sys::Terminal, workspace::Workspace, Cache, TerminalOptions};
 
 /// The main `Config` for Huak.
 ///
@@ -23,6 +23,7 @@ use crate::{sys::Terminal, workspace::Workspace, TerminalOptions};
 /// let workspace = config.workspace();
 /// ```
 #[derive(Clone)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Config {
     /// The configured `Workspace` root path.
     pub workspace_root: PathBuf,
@@ -32,6 +33,19 @@ pub struct Config {
     pub terminal_options: TerminalOptions,
     /// Huak's home directory.
     pub home: Option<PathBuf>,
+    /// Require the lock file to be up to date and refuse to modify the manifest file,
+    /// the lock file, or the Python environment outside of the lock file.
+    pub locked: bool,
+    /// Use the lock file as-is without checking if it's up to date. Implies `locked`.
+    pub frozen: bool,
+    /// Disable network access. Packages are resolved from local `find-links` directories
+    /// and Huak's cache.
+    pub offline: bool,
+    /// Use the Python environment of the first Python interpreter found instead of a
+    /// virtual environment.
+    pub system: bool,
+    /// Allow modifying externally managed Python environments.
+    pub break_system_packages: bool,
 }
 
 impl Config {
@@ -47,6 +61,18 @@ impl Config {
         Terminal::from_options(self.terminal_options.clone())
     }
 
+    /// Get the `Cache` in Huak's home directory.
+    #[must_use]
+    pub fn cache(&self) -> Option<Cache> {
+        self.home.as_ref().map(|it| Cache::new(it.join("cache")))
+    }
+
+    /// Check if the `Config` prevents changes outside of the lock file (`locked` or `frozen`).
+    #[must_use]
+    pub fn is_locked(&self) -> bool {
+        self.locked || self.frozen
+    }
+
     /// Get a `Config` with a new `Terminal`.
     #[must_use]
     pub fn with_terminal(self, terminal_options: TerminalOptions) -> Self {
@@ -54,6 +80,11 @@ impl Config {
             workspace_root: self.workspace_root,
             cwd: self.cwd,
             terminal_options,
+            locked: self.locked,
+            frozen: self.frozen,
+            offline: self.offline,
+            system: self.system,
+            break_system_packages: self.break_system_packages,
             ..Default::default()
         }
     }
@@ -66,6 +97,11 @@ impl Default for Config {
             cwd: PathBuf::default(),
             terminal_options: TerminalOptions::default(),
             home: huak_home_dir(),
+            locked: false,
+            frozen: false,
+            offline: false,
+            system: false,
+            break_system_packages: false,
         }
     }
 }
@@ -1,9 +1,23 @@
-use std::{ffi::OsStr, fmt::Display, str::FromStr};
+use std::{
+    ffi::OsStr,
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
-use pep440_rs::VersionSpecifiers;
+use lazy_static::lazy_static;
+use pep440_rs::{Version, VersionSpecifiers};
 use pep508_rs::{Requirement, VersionOrUrl};
+use regex::Regex;
+use url::Url;
 
-use crate::Error;
+use crate::{lock::normalized_name, Error, HuakResult};
+
+lazy_static! {
+    static ref NAME_REGEX: Regex =
+        Regex::new(r"^\s*[A-Za-z0-9](?:[A-Za-z0-9._-]*[A-Za-z0-9])?\s*(?:\[[^\]]*\])?\s*$")
+            .expect("name regex");
+}
 
 /// The `Dependency` is an abstraction for `Package` data used as a cheap alternative
 /// for operations on lots of `Package` data.
@@ -37,6 +51,24 @@ impl Dependency {
         &self.requirement().name
     }
 
+    /// Get the URL of the `Dependency` if it's a direct reference (`name @ url`).
+    #[must_use]
+    pub fn url(&self) -> Option<&Url> {
+        match self.0.version_or_url.as_ref() {
+            Some(VersionOrUrl::Url(it)) => Some(it),
+            _ => None,
+        }
+    }
+
+    /// Get the path of the `Dependency` if it's a direct reference to a local directory.
+    #[must_use]
+    pub fn local_path(&self) -> Option<PathBuf> {
+        self.url()
+            .filter(|it| it.scheme() == "file")
+            .and_then(|it| it.to_file_path().ok())
+            .filter(|it| it.is_dir())
+    }
+
     /// Get a reference to the `Dependency`'s `VersionSpecifiers`.
     #[allow(dead_code)]
     fn version_specifiers(&self) -> Option<&VersionSpecifiers> {
@@ -91,7 +123,7 @@ impl AsRef<OsStr> for Dependency {
 
 impl PartialEq for Dependency {
     fn eq(&self, other: &Self) -> bool {
-        self.name() == other.name()
+        normalized_name(self.name()) == normalized_name(other.name())
     }
 }
 
@@ -112,6 +144,228 @@ where
         .filter_map(|item| Dependency::from_str(item.as_ref()).ok())
 }
 
+/// Parse a PEP 508 requirement string from a command line argument.
+///
+/// The argument is returned as-is unless it uses one of the following shorthands:
+///
+/// - `name@1.0.0` is written as `name==1.0.0`.
+/// - `name @ ./path/to/project` is written as a `file://` URL to the absolute path. Manifests
+///   record the path relative to the project root instead (see `root_relative_requirement`).
+///
+/// Direct references like `name @ git+https://github.com/owner/repo@v1.0.0` are kept verbatim.
+pub fn requirement_from_arg<T: AsRef<Path>>(arg: &str, cwd: T) -> HuakResult<String> {
+    let arg = arg.trim();
+
+    let requirement = match arg.split_once('@') {
+        Some((name, rest)) if NAME_REGEX.is_match(name) => {
+            let (reference, marker) = match rest.split_once(';') {
+                Some((reference, marker)) => (reference.trim(), Some(marker.trim())),
+                None => (rest.trim(), None),
+            };
+            let marker = marker.map(|it| format!(" ; {it}")).unwrap_or_default();
+
+            if Version::from_str(reference).is_ok() {
+                format!("{}=={reference}{marker}", name.trim())
+            } else if Url::parse(reference).is_ok() {
+                arg.to_string()
+            } else {
+                let path = cwd.as_ref().join(reference);
+                let path = path.canonicalize().map_err(|_| {
+                    Error::InvalidDirectReference(format!("{} doesn't exist", path.display()))
+                })?;
+                let url = Url::from_file_path(&path).map_err(|()| {
+                    Error::InvalidDirectReference(format!("{} isn't a valid path", path.display()))
+                })?;
+                format!("{} @ {url}{marker}", name.trim())
+            }
+        }
+        _ => arg.to_string(),
+    };
+
+    // Validate the requirement.
+    Requirement::from_str(&requirement)?;
+
+    Ok(requirement)
+}
+
+/// The placeholder for the project root's `file://` URL in direct references to local paths
+/// (see <https://hatch.pypa.io/latest/config/context/>).
+pub(crate) const ROOT_URI: &str = "{root:uri}";
+
+/// Write a direct reference to a local path relative to the project `root`
+/// (`name @ {root:uri}/libs/my-lib`) so the manifest isn't tied to one machine.
+///
+/// Only paths in the project or a sibling of it are rewritten. Anything else is returned
+/// as-is.
+pub(crate) fn root_relative_requirement<T: AsRef<Path>>(requirement: &str, root: T) -> String {
+    let Ok(req) = Requirement::from_str(requirement) else {
+        return requirement.to_string();
+    };
+    let Some(VersionOrUrl::Url(url)) = req.version_or_url.as_ref() else {
+        return requirement.to_string();
+    };
+    let Some(relative) = root_relative_url(url, root) else {
+        return requirement.to_string();
+    };
+
+    let extras = match req.extras.as_ref() {
+        Some(it) if !it.is_empty() => format!("[{}]", it.join(",")),
+        _ => String::new(),
+    };
+    let marker = req
+        .marker
+        .as_ref()
+        .map(|it| format!(" ; {it}"))
+        .unwrap_or_default();
+
+    format!("{}{extras} @ {relative}{marker}", req.name)
+}
+
+/// Get a `file://` URL relative to the project `root` (`{root:uri}/libs/my-lib`). `None` is
+/// returned if the URL isn't a path in the project or a sibling of it.
+pub(crate) fn root_relative_url<T: AsRef<Path>>(url: &Url, root: T) -> Option<String> {
+    if url.scheme() != "file" {
+        return None;
+    }
+
+    let root = root.as_ref();
+    let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+
+    let relative = [Some(root.as_path()), root.parent()]
+        .into_iter()
+        .enumerate()
+        .find_map(|(depth, dir)| {
+            let prefix = Url::from_directory_path(dir?).ok()?;
+            let rest = url.as_str().strip_prefix(prefix.as_str())?;
+            Some(format!("{ROOT_URI}/{}{rest}", "../".repeat(depth)))
+        });
+
+    relative
+}
+
+/// Expand `{root:uri}` in a requirement to the `file://` URL of the project `root`.
+pub(crate) fn expand_root_uri<T: AsRef<Path>>(requirement: &str, root: T) -> String {
+    if !requirement.contains(ROOT_URI) {
+        return requirement.to_string();
+    }
+
+    match Url::from_directory_path(root.as_ref()) {
+        Ok(url) => requirement.replace(ROOT_URI, url.as_str().trim_end_matches('/')),
+        Err(()) => requirement.to_string(),
+    }
+}
+
+/// The style of version bound written for a `Dependency` added without a specifier.
+///
+/// Configured with `add-bound` in `[tool.huak]`.
+///
+/// ```toml
+/// [tool.huak]
+/// add-bound = "compatible"
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionBound {
+    /// Pin the version (==1.2.3).
+    #[default]
+    Exact,
+    /// Allow compatible releases (~=1.2).
+    Compatible,
+    /// Only use a lower bound (>=1.2.3).
+    Lower,
+    /// Allow any version until the next major release (>=1.2.3,<2). Versions below 1.0
+    /// are bound by their first non-zero component (>=0.4.1,<0.5).
+    Major,
+}
+
+impl VersionBound {
+    /// Build the `VersionSpecifiers` bounding a `Version`.
+    ///
+    /// Local version labels (+cu118) are only allowed with `==`, so they're dropped from
+    /// every other bound.
+    pub fn version_specifiers(&self, version: &Version) -> HuakResult<VersionSpecifiers> {
+        let public = Version {
+            local: None,
+            ..version.clone()
+        };
+        let epoch = if version.epoch == 0 {
+            String::new()
+        } else {
+            format!("{}!", version.epoch)
+        };
+        let release = |parts: &[u64]| {
+            parts
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(".")
+        };
+
+        let specifiers = match self {
+            VersionBound::Exact => format!("=={version}"),
+            VersionBound::Lower => format!(">={public}"),
+            VersionBound::Compatible => {
+                let major = version.release.first().copied().unwrap_or_default();
+                let minor = version.release.get(1).copied().unwrap_or_default();
+                format!("~={epoch}{major}.{minor}")
+            }
+            VersionBound::Major => {
+                let mut upper = version.release.clone();
+                let i = upper
+                    .iter()
+                    .position(|it| *it != 0)
+                    .unwrap_or(upper.len().saturating_sub(1));
+                upper.truncate(i + 1);
+                if let Some(it) = upper.last_mut() {
+                    *it += 1;
+                }
+                format!(">={public},<{epoch}{}", release(&upper))
+            }
+        };
+
+        Ok(VersionSpecifiers::from_str(&specifiers)?)
+    }
+}
+
+impl FromStr for VersionBound {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "exact" => Ok(VersionBound::Exact),
+            "compatible" => Ok(VersionBound::Compatible),
+            "lower" => Ok(VersionBound::Lower),
+            "major" => Ok(VersionBound::Major),
+            _ => Err(Error::InvalidVersionBound(s.to_string())),
+        }
+    }
+}
+
+impl Display for VersionBound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VersionBound::Exact => write!(f, "exact"),
+            VersionBound::Compatible => write!(f, "compatible"),
+            VersionBound::Lower => write!(f, "lower"),
+            VersionBound::Major => write!(f, "major"),
+        }
+    }
+}
+
+/// Format a `Requirement` without its markers.
+pub(crate) fn requirement_string(requirement: &Requirement) -> String {
+    let extras = match requirement.extras.as_ref() {
+        Some(it) if !it.is_empty() => format!("[{}]", it.join(",")),
+        _ => String::new(),
+    };
+    let version_or_url = match requirement.version_or_url.as_ref() {
+        Some(VersionOrUrl::VersionSpecifier(it)) => it.to_string(),
+        Some(VersionOrUrl::Url(url)) => format!(" @ {url}"),
+        None => String::new(),
+    };
+
+    format!("{}{extras}{version_or_url}", requirement.name)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -127,4 +381,142 @@ mod tests {
             pep440_rs::VersionSpecifiers::from_str("==0.0.0").unwrap()
         );
     }
+
+    #[test]
+    fn test_requirement_from_arg() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("libs").join("my-lib");
+        std::fs::create_dir_all(&project).unwrap();
+        let url = Url::from_file_path(project.canonicalize().unwrap()).unwrap();
+
+        let cases = [
+            ("click", "click".to_string()),
+            ("click>=8.0", "click>=8.0".to_string()),
+            ("click@8.1.7", "click==8.1.7".to_string()),
+            (
+                "requests[socks]@2.31.0",
+                "requests[socks]==2.31.0".to_string(),
+            ),
+            (
+                "my-pkg @ git+https://github.com/owner/repo.git@v1.0.0",
+                "my-pkg @ git+https://github.com/owner/repo.git@v1.0.0".to_string(),
+            ),
+            (
+                "my-pkg @ https://example.com/my_pkg-1.0.0.tar.gz ; python_version >= '3.8'",
+                "my-pkg @ https://example.com/my_pkg-1.0.0.tar.gz ; python_version >= '3.8'"
+                    .to_string(),
+            ),
+            ("my-lib @ ./libs/my-lib", format!("my-lib @ {url}")),
+            (
+                "my-lib@libs/my-lib; sys_platform == 'linux'",
+                format!("my-lib @ {url} ; sys_platform == 'linux'"),
+            ),
+        ];
+
+        for (arg, expected) in cases {
+            assert_eq!(requirement_from_arg(arg, dir.path()).unwrap(), expected);
+        }
+
+        assert!(matches!(
+            requirement_from_arg("my-lib @ ./missing", dir.path()),
+            Err(Error::InvalidDirectReference(_))
+        ));
+        assert!(requirement_from_arg("git+https://github.com/owner/repo.git", dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_root_relative_requirement() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("project");
+        std::fs::create_dir_all(root.join("libs").join("my-lib")).unwrap();
+        std::fs::create_dir_all(dir.path().join("sibling")).unwrap();
+        let url = |path: &Path| Url::from_file_path(path.canonicalize().unwrap()).unwrap();
+
+        let cases = [
+            (
+                format!(
+                    "my-lib @ {} ; sys_platform == 'linux'",
+                    url(&root.join("libs").join("my-lib"))
+                ),
+                "my-lib @ {root:uri}/libs/my-lib ; sys_platform == 'linux'".to_string(),
+            ),
+            (
+                format!("sibling[cli] @ {}", url(&dir.path().join("sibling"))),
+                "sibling[cli] @ {root:uri}/../sibling".to_string(),
+            ),
+            (
+                "my-pkg @ https://example.com/my_pkg-1.0.0.tar.gz".to_string(),
+                "my-pkg @ https://example.com/my_pkg-1.0.0.tar.gz".to_string(),
+            ),
+            ("click>=8".to_string(), "click>=8".to_string()),
+        ];
+
+        for (requirement, expected) in cases {
+            let relative = root_relative_requirement(&requirement, &root);
+            assert_eq!(relative, expected);
+
+            let expanded = expand_root_uri(&relative, root.canonicalize().unwrap());
+            assert_eq!(
+                Requirement::from_str(&expanded).unwrap(),
+                Requirement::from_str(&requirement).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_dependency_normalized_names() {
+        let dep = Dependency::from_str("Foo_Bar[socks]>=1.0").unwrap();
+
+        assert_eq!(dep, Dependency::from_str("foo.bar").unwrap());
+        assert_eq!(normalized_name("Zope__Interface"), "zope-interface");
+        assert_eq!(
+            crate::importable_package_name("My.Project").unwrap(),
+            "My_Project"
+        );
+    }
+
+    #[test]
+    fn test_dependency_local_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = Url::from_file_path(dir.path()).unwrap();
+        let dep = Dependency::from_str(&format!("my-lib @ {url}")).unwrap();
+
+        assert_eq!(dep.url(), Some(&url));
+        assert_eq!(dep.local_path(), Some(dir.path().to_path_buf()));
+        assert!(
+            Dependency::from_str("my-lib @ https://example.com/my_lib-1.0.0.tar.gz")
+                .unwrap()
+                .local_path()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_version_bound_specifiers() {
+        let cases = [
+            ("1.2.3", VersionBound::Exact, "==1.2.3"),
+            ("1.2.3", VersionBound::Compatible, "~=1.2"),
+            ("1.2.3", VersionBound::Lower, ">=1.2.3"),
+            ("1.2.3", VersionBound::Major, ">=1.2.3, <2"),
+            ("0.4.1", VersionBound::Major, ">=0.4.1, <0.5"),
+            ("0.0.7", VersionBound::Major, ">=0.0.7, <0.0.8"),
+            ("2023.10", VersionBound::Compatible, "~=2023.10"),
+            ("5", VersionBound::Compatible, "~=5.0"),
+            ("1!2.0", VersionBound::Major, ">=1!2.0, <1!3"),
+            ("2.1.0+cu118", VersionBound::Exact, "==2.1.0+cu118"),
+            ("2.1.0+cu118", VersionBound::Compatible, "~=2.1"),
+            ("2.1.0+cu118", VersionBound::Lower, ">=2.1.0"),
+            ("2.1.0+cu118", VersionBound::Major, ">=2.1.0, <3"),
+        ];
+
+        for (version, bound, expected) in cases {
+            assert_eq!(
+                bound
+                    .version_specifiers(&Version::from_str(version).unwrap())
+                    .unwrap()
+                    .to_string(),
+                expected
+            );
+        }
+    }
 }
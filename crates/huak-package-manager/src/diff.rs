@@ -0,0 +1,134 @@
+/// The number of unchanged lines displayed around each change.
+const CONTEXT_LINES: usize = 3;
+
+/// Get a unified diff of two texts. An empty string is returned if they're equal.
+///
+/// ```diff
+/// --- pyproject.toml
+/// +++ pyproject.toml
+/// @@ -3,3 +3,3 @@
+///  dependencies = [
+/// -    "click ==8.1.3",
+/// +    "click ==8.1.7",
+///  ]
+/// ```
+pub(crate) fn unified_diff(old: &str, new: &str, label: &str) -> String {
+    let old = old.lines().collect::<Vec<_>>();
+    let new = new.lines().collect::<Vec<_>>();
+    let ops = diff_lines(&old, &new);
+
+    let changes = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, (tag, _))| *tag != ' ')
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    // Group changes close enough to share context into hunks.
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for i in changes {
+        let start = i.saturating_sub(CONTEXT_LINES);
+        let end = (i + CONTEXT_LINES + 1).min(ops.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut out = vec![format!("--- {label}"), format!("+++ {label}")];
+    for (start, end) in hunks {
+        let old_start = ops[..start].iter().filter(|(tag, _)| *tag != '+').count();
+        let new_start = ops[..start].iter().filter(|(tag, _)| *tag != '-').count();
+        let old_len = ops[start..end]
+            .iter()
+            .filter(|(tag, _)| *tag != '+')
+            .count();
+        let new_len = ops[start..end]
+            .iter()
+            .filter(|(tag, _)| *tag != '-')
+            .count();
+
+        out.push(format!(
+            "@@ -{},{old_len} +{},{new_len} @@",
+            old_start + usize::from(old_len > 0),
+            new_start + usize::from(new_len > 0),
+        ));
+        out.extend(
+            ops[start..end]
+                .iter()
+                .map(|(tag, line)| format!("{tag}{line}")),
+        );
+    }
+
+    out.join("\n")
+}
+
+/// Get the edits from `old` to `new` using their longest common subsequence of lines.
+/// Each line is tagged with ' ' if it's unchanged, '-' if it's removed and '+' if it's added.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(char, &'a str)> {
+    let (n, m) = (old.len(), new.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut ops = Vec::with_capacity(n.max(m));
+    while i < n && j < m {
+        if old[i] == new[j] {
+            ops.push((' ', old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            ops.push(('-', old[i]));
+            i += 1;
+        } else {
+            ops.push(('+', new[j]));
+            j += 1;
+        }
+    }
+    ops.extend(old[i..].iter().map(|it| ('-', *it)));
+    ops.extend(new[j..].iter().map(|it| ('+', *it)));
+
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unified_diff() {
+        let old = "[project]\nname = \"mock-project\"\nversion = \"0.0.1\"\ndependencies = [\n    \"click ==8.1.3\",\n]\n\n[project.optional-dependencies]\ndev = [\n    \"pytest >=6\",\n]\n";
+        let new = old.replace("==8.1.3", "==8.1.7");
+
+        assert_eq!(unified_diff(old, old, "pyproject.toml"), "");
+        assert_eq!(
+            unified_diff(old, &new, "pyproject.toml"),
+            [
+                "--- pyproject.toml",
+                "+++ pyproject.toml",
+                "@@ -2,7 +2,7 @@",
+                " name = \"mock-project\"",
+                " version = \"0.0.1\"",
+                " dependencies = [",
+                "-    \"click ==8.1.3\",",
+                "+    \"click ==8.1.7\",",
+                " ]",
+                " ",
+                " [project.optional-dependencies]",
+            ]
+            .join("\n")
+        );
+    }
+}
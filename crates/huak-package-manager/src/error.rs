@@ -30,16 +30,52 @@ pub enum Error {
     ToolchainError(#[from] huak_toolchain::Error),
     #[error("a toolchain already exists: {0}")]
     LocalToolchainExists(PathBuf),
+    #[error("a license policy is violated: {0}")]
+    LicensePolicyViolation(String),
+    #[error("a lock file could not be found")]
+    LockFileNotFound,
+    #[error("a lock file is out of date and cannot be updated with --locked or --frozen")]
+    LockFileOutdated,
+    #[error("a lock file is not supported: version {0}")]
+    LockFileVersionNotSupported(u32),
+    #[error("a locked package has no files to export: {0} (run `huak lock` to update huak.lock)")]
+    LockedFilesNotFound(String),
+    #[error("a distribution must be built to read its metadata: {0}")]
+    BuildRequired(String),
     #[error("a problem with huak's internals occurred: {0}")]
     InternalError(String),
+    #[error("a dependency conflict occurred: {0}")]
+    DependencyConflict(String),
+    #[error("an advisory is invalid: {0}")]
+    InvalidAdvisory(String),
     #[error("a checksum is invalid: {0}")]
     InvalidChecksum(String),
+    #[error("a direct reference is invalid: {0}")]
+    InvalidDirectReference(String),
+    #[error("a distribution's metadata is invalid: {0}")]
+    InvalidMetadata(String),
+    #[error("an export format is invalid: {0} (expected requirements-txt, constraints or pylock)")]
+    InvalidExportFormat(String),
+    #[error("an import source is invalid: {0}")]
+    InvalidImportSource(String),
+    #[error("a license expression is invalid: {0}")]
+    InvalidLicenseExpression(String),
+    #[error("a licenses format is invalid: {0} (expected table, csv or json)")]
+    InvalidLicensesFormat(String),
+    #[error("an output format is invalid: {0} (expected table or json)")]
+    InvalidOutputFormat(String),
     #[error("a program is invalid: {0}")]
     InvalidProgram(String),
     #[error("a run command is invalid: {0}")]
     InvalidRunCommand(String),
+    #[error("a wheel is invalid: {0}")]
+    InvalidWheel(String),
+    #[error("a version bound is invalid: {0} (expected exact, compatible, lower or major)")]
+    InvalidVersionBound(String),
     #[error("a version number could not be parsed: {0}")]
     InvalidVersionString(String),
+    #[error("a credentials file can be read by other users: {0} (restrict it with chmod 600)")]
+    InsecureCredentialsFile(PathBuf),
     #[error("a problem occurred with json deserialization: {0}")]
     JSONSerdeError(#[from] serde_json::Error),
     #[error("a problem with io occurred: {0}")]
@@ -50,10 +86,18 @@ pub enum Error {
     PEP508Error(#[from] pep508_rs::Pep508Error),
     #[error("a manifest file already exists")]
     ManifestFileFound,
+    #[error("a manifest file cannot be modified with --locked or --frozen")]
+    ManifestFileLocked,
     #[error("a manifest file could not be found")]
     ManifestFileNotFound,
     #[error("a manifest file is not supported: {0}")]
     ManifestFileNotSupported(PathBuf),
+    #[error("a problem occurred with a package index: {0}")]
+    PackageIndexError(String),
+    #[error("a package doesn't match the index it's pinned to: {0}")]
+    PackageIndexMismatch(String),
+    #[error("a package could not be found: {0}")]
+    PackageNotFound(String),
     #[error("a package version could not be found")]
     PackageVersionNotFound,
     #[error("a project already exists")]
@@ -70,8 +114,18 @@ pub enum Error {
     PythonInstallError(String),
     #[error("a python release could not be found: {0}")]
     PythonReleaseNotFound(String),
+    #[error("a python environment cannot be modified outside the lock file with --locked or --frozen: {0}")]
+    PythonEnvironmentLocked(String),
     #[error("a python environment could not be found")]
     PythonEnvironmentNotFound,
+    #[error("a python environment doesn't have pip installed: {0} (install it with `python -m ensurepip`)")]
+    PipNotFound(PathBuf),
+    #[error("a network request can't be made in offline mode: {0}")]
+    OfflineNetworkRequest(String),
+    #[error("a distribution's RECORD could not be found: {0}")]
+    RecordNotFound(String),
+    #[error("a problem with a request occurred: {0}")]
+    ReqwestError(#[from] reqwest::Error),
     #[error("a regex error occurred: {0}")]
     RegexError(#[from] regex::Error),
     #[error("a subprocess exited with {0}")]
@@ -90,6 +144,12 @@ pub enum Error {
     Unimplemented(String),
     #[error("a python environment is unsupported for this feature")]
     UnsupportedPythonEnvironment(PathBuf),
+    #[error("a python environment is externally managed (use --break-system-packages to modify it anyway):\n{0}")]
+    ExternallyManagedEnvironment(String),
+    #[error("a url could not be parsed: {0}")]
+    UrlParseError(#[from] url::ParseError),
+    #[error("a vulnerable package was found: {0}")]
+    VulnerablePackages(String),
     #[error("a problem with utf-8 parsing occurred: {0}")]
     Utf8Error(#[from] std::str::Utf8Error),
 }
@@ -0,0 +1,373 @@
+use crate::{
+    dependency::requirement_string,
+    lock::{normalized_name, REQUIRED_GROUP_NAME},
+    InstalledDistribution,
+};
+use indexmap::IndexMap;
+use pep508_rs::{MarkerEnvironment, Requirement};
+use std::{
+    collections::{BTreeSet, HashSet, VecDeque},
+    fmt::Display,
+};
+
+/// A `DependencyGraph` of the distributions installed to a Python environment.
+///
+/// Edges are `Requirement`s from each distribution's metadata that apply to the
+/// `MarkerEnvironment`. Extras requested by the roots, or by other distributions, are
+/// used to evaluate markers like `extra == "test"`.
+#[derive(Clone, Debug, Default)]
+pub struct DependencyGraph {
+    /// The installed packages by normalized name, sorted by name.
+    packages: IndexMap<String, GraphPackage>,
+    /// The `Requirement`s the graph is rooted at.
+    roots: Vec<GraphRoot>,
+}
+
+impl DependencyGraph {
+    /// Initialize a `DependencyGraph` from `InstalledDistribution`s and the `GraphRoot`s
+    /// it's rooted at.
+    #[must_use]
+    pub fn new(
+        distributions: &[InstalledDistribution],
+        roots: Vec<GraphRoot>,
+        marker_env: &MarkerEnvironment,
+    ) -> DependencyGraph {
+        let mut packages = distributions
+            .iter()
+            .map(|it| {
+                let pkg = GraphPackage {
+                    name: it.name().to_string(),
+                    version: it.version().to_string(),
+                    extras: BTreeSet::new(),
+                    dependencies: Vec::new(),
+                };
+                (it.normalized_name(), (pkg, it.requires_dist()))
+            })
+            .collect::<IndexMap<_, _>>();
+        packages.sort_keys();
+
+        let roots = roots
+            .into_iter()
+            .filter(|it| {
+                it.requirement
+                    .marker
+                    .as_ref()
+                    .map_or(true, |m| m.evaluate(marker_env, &[]))
+            })
+            .collect::<Vec<_>>();
+
+        // Extras are propagated from the roots until no new extras are requested.
+        let mut queue = roots
+            .iter()
+            .map(|it| &it.requirement)
+            .cloned()
+            .collect::<VecDeque<_>>();
+        let mut visited = HashSet::new();
+        while let Some(req) = queue.pop_front() {
+            let name = normalized_name(&req.name);
+            let Some((pkg, requires)) = packages.get_mut(&name) else {
+                continue;
+            };
+            let len = pkg.extras.len();
+            pkg.extras
+                .extend(req.extras.iter().flatten().map(|it| normalized_name(it)));
+            if !visited.insert(name) && len == pkg.extras.len() {
+                continue;
+            }
+
+            let extras = pkg.extras.iter().map(String::as_str).collect::<Vec<_>>();
+            queue.extend(
+                requires
+                    .iter()
+                    .filter(|it| {
+                        it.marker
+                            .as_ref()
+                            .map_or(true, |m| m.evaluate(marker_env, &extras))
+                    })
+                    .cloned(),
+            );
+        }
+
+        let packages = packages
+            .into_iter()
+            .map(|(name, (mut pkg, requires))| {
+                let extras = pkg.extras.iter().map(String::as_str).collect::<Vec<_>>();
+                for requirement in requires {
+                    if requirement
+                        .marker
+                        .as_ref()
+                        .map_or(true, |m| m.evaluate(marker_env, &extras))
+                    {
+                        pkg.dependencies.push(GraphEdge {
+                            name: normalized_name(&requirement.name),
+                            requirement,
+                        });
+                    }
+                }
+                pkg.dependencies.sort_by(|a, b| a.name.cmp(&b.name));
+                (name, pkg)
+            })
+            .collect();
+
+        DependencyGraph { packages, roots }
+    }
+
+    /// Get the `GraphPackage`s sorted by name.
+    pub fn packages(&self) -> impl Iterator<Item = &GraphPackage> {
+        self.packages.values()
+    }
+
+    /// Get a `GraphPackage` by name.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&GraphPackage> {
+        self.packages.get(&normalized_name(name))
+    }
+
+    /// Get the `GraphRoot`s that apply to the `MarkerEnvironment`.
+    #[must_use]
+    pub fn roots(&self) -> &[GraphRoot] {
+        &self.roots
+    }
+
+    /// Get the packages that depend on a package along with the `GraphEdge` to it,
+    /// sorted by name.
+    #[must_use]
+    pub fn dependents(&self, name: &str) -> Vec<(&GraphPackage, &GraphEdge)> {
+        let name = normalized_name(name);
+
+        self.packages
+            .values()
+            .filter_map(|pkg| {
+                pkg.dependencies
+                    .iter()
+                    .find(|it| it.name == name)
+                    .map(|edge| (pkg, edge))
+            })
+            .collect()
+    }
+
+    /// Get the `GraphRoot`s that require a package.
+    #[must_use]
+    pub fn root_requirements(&self, name: &str) -> Vec<&GraphRoot> {
+        let name = normalized_name(name);
+
+        self.roots
+            .iter()
+            .filter(|it| normalized_name(&it.requirement.name) == name)
+            .collect()
+    }
+
+    /// Get every `DependencyPath` from a root to a package. Paths don't visit a package
+    /// more than once.
+    #[must_use]
+    pub fn paths(&self, name: &str) -> Vec<DependencyPath> {
+        let mut paths = Vec::new();
+        let mut stack = vec![normalized_name(name)];
+        self.collect_paths(&mut stack, &mut Vec::new(), &mut paths);
+
+        paths.sort_by_key(|it| {
+            (
+                it.root.group != REQUIRED_GROUP_NAME,
+                it.root.group.clone(),
+                it.to_string(),
+            )
+        });
+
+        paths
+    }
+
+    /// Walk from the last package on the `stack` back to the roots. `edges` holds the
+    /// edges walked so far with the nearest to the target last.
+    fn collect_paths(
+        &self,
+        stack: &mut Vec<String>,
+        edges: &mut Vec<GraphEdge>,
+        paths: &mut Vec<DependencyPath>,
+    ) {
+        let name = stack.last().expect("stack should not be empty").clone();
+
+        for root in self.root_requirements(&name) {
+            paths.push(DependencyPath {
+                root: root.clone(),
+                edges: edges.iter().rev().cloned().collect(),
+                versions: stack
+                    .iter()
+                    .rev()
+                    .map(|it| self.get(it).map(|pkg| pkg.version.clone()))
+                    .collect(),
+            });
+        }
+
+        for (pkg, edge) in self.dependents(&name) {
+            let dependent = normalized_name(&pkg.name);
+            if stack.contains(&dependent) {
+                continue;
+            }
+            stack.push(dependent);
+            edges.push(edge.clone());
+            self.collect_paths(stack, edges, paths);
+            edges.pop();
+            stack.pop();
+        }
+    }
+}
+
+/// A path from a `GraphRoot` to a package through the `GraphEdge`s between packages.
+#[derive(Clone, Debug)]
+pub struct DependencyPath {
+    /// The `GraphRoot` the path starts at.
+    pub root: GraphRoot,
+    /// The `GraphEdge`s after the root in order.
+    pub edges: Vec<GraphEdge>,
+    /// The installed version at each hop, starting with the root's package.
+    pub versions: Vec<Option<String>>,
+}
+
+impl DependencyPath {
+    /// Get each `Requirement` along the path, starting with the root's.
+    pub fn requirements(&self) -> impl Iterator<Item = &Requirement> {
+        std::iter::once(&self.root.requirement).chain(self.edges.iter().map(|it| &it.requirement))
+    }
+}
+
+/// Display a `DependencyPath` as each requirement along it with the installed version
+/// that matched it.
+///
+/// ```text
+/// pytest>=7 (v7.4.3) -> pluggy<2.0,>=0.12 (v1.3.0)
+/// ```
+impl Display for DependencyPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (req, version)) in self.requirements().zip(&self.versions).enumerate() {
+            if i > 0 {
+                write!(f, " -> ")?;
+            }
+            write!(f, "{}", requirement_string(req))?;
+            if let Some(version) = version {
+                write!(f, " (v{version})")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// An installed package in a `DependencyGraph`.
+#[derive(Clone, Debug)]
+pub struct GraphPackage {
+    /// The name of the package as it's listed in its metadata.
+    pub name: String,
+    /// The installed version of the package.
+    pub version: String,
+    /// The extras of the package that are requested.
+    pub extras: BTreeSet<String>,
+    /// The edges to the package's dependencies sorted by name.
+    pub dependencies: Vec<GraphEdge>,
+}
+
+/// An edge from a package to one of its dependencies.
+#[derive(Clone, Debug)]
+pub struct GraphEdge {
+    /// The normalized name of the dependency.
+    pub name: String,
+    /// The `Requirement` of the dependency.
+    pub requirement: Requirement,
+}
+
+/// A `Requirement` a `DependencyGraph` is rooted at.
+#[derive(Clone, Debug)]
+pub struct GraphRoot {
+    /// The dependency group the `Requirement` is listed in.
+    pub group: String,
+    /// The `Requirement` listed in the group.
+    pub requirement: Requirement,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::installed::{installed_distributions, write_mock_distributions};
+    use crate::python_environment::mock_marker_environment;
+    use std::str::FromStr;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_dependency_graph() {
+        let dir = tempdir().unwrap();
+        write_mock_distributions(
+            dir.path(),
+            &[
+                (
+                    "app",
+                    "1.0",
+                    &["lib[speed]>=1", "colorama; sys_platform == 'win32'"],
+                ),
+                (
+                    "lib",
+                    "1.2",
+                    &["fast; extra == 'speed'", "slow; extra == 'other'"],
+                ),
+                ("fast", "0.1", &[]),
+                ("slow", "0.1", &[]),
+                ("Other_Tool", "2.0", &["lib"]),
+            ],
+        );
+        let distributions = installed_distributions(dir.path()).unwrap();
+        let roots = ["app", "other-tool; python_version < '3'"]
+            .iter()
+            .map(|it| GraphRoot {
+                group: "required".to_string(),
+                requirement: Requirement::from_str(it).unwrap(),
+            })
+            .collect();
+
+        let graph = DependencyGraph::new(&distributions, roots, &mock_marker_environment());
+
+        assert_eq!(graph.roots().len(), 1);
+        assert_eq!(
+            graph
+                .packages()
+                .map(|it| it.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["app", "fast", "lib", "Other_Tool", "slow"]
+        );
+        assert_eq!(
+            graph
+                .get("app")
+                .unwrap()
+                .dependencies
+                .iter()
+                .map(|it| it.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["lib"]
+        );
+        assert_eq!(
+            graph
+                .get("lib")
+                .unwrap()
+                .dependencies
+                .iter()
+                .map(|it| it.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["fast"]
+        );
+        assert_eq!(
+            graph
+                .dependents("lib")
+                .iter()
+                .map(|(pkg, _)| pkg.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["app", "Other_Tool"]
+        );
+        assert_eq!(graph.root_requirements("APP").len(), 1);
+        assert_eq!(
+            graph
+                .paths("fast")
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["app (v1.0) -> lib[speed]>=1 (v1.2) -> fast (v0.1)"]
+        );
+        assert!(graph.paths("slow").is_empty());
+    }
+}
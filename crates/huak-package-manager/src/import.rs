@@ -0,0 +1,1081 @@
+use crate::{lock::normalized_name, Error, HuakResult};
+use huak_pyproject_toml::PyProjectToml;
+use pep440_rs::VersionSpecifiers;
+use pep508_rs::Requirement;
+use std::{
+    collections::{BTreeMap, HashSet},
+    ffi::OsStr,
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use toml::{Table, Value};
+use url::Url;
+
+/// The Pipfile tables that aren't package categories.
+const PIPFILE_TABLES: [&str; 4] = ["source", "requires", "pipenv", "scripts"];
+
+/// The Pipfile keys that are written as environment markers.
+const PIPFILE_MARKER_KEYS: [&str; 11] = [
+    "os_name",
+    "sys_platform",
+    "platform_machine",
+    "platform_python_implementation",
+    "platform_release",
+    "platform_system",
+    "platform_version",
+    "python_version",
+    "python_full_version",
+    "implementation_name",
+    "implementation_version",
+];
+
+/// A file listing a project's dependencies that can be imported with `huak init --from`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportSource {
+    /// A pip requirements file. `-r` includes and `-c` constraints files are read too.
+    Requirements(PathBuf),
+    /// A Pipfile (see <https://github.com/pypa/pipfile>).
+    Pipfile(PathBuf),
+    /// A pyproject.toml file using `[tool.poetry]` (see <https://python-poetry.org/docs/pyproject/>).
+    Poetry(PathBuf),
+}
+
+impl ImportSource {
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        match self {
+            ImportSource::Requirements(it)
+            | ImportSource::Pipfile(it)
+            | ImportSource::Poetry(it) => it,
+        }
+    }
+
+    /// Read the `ImportedProject` from the source. Relative paths are resolved from `root`.
+    pub fn read<T: AsRef<Path>>(&self, root: T) -> HuakResult<ImportedProject> {
+        let path = root.as_ref().join(self.path());
+
+        match self {
+            ImportSource::Requirements(_) => read_requirements(&path),
+            ImportSource::Pipfile(_) => read_pipfile(&path),
+            ImportSource::Poetry(_) => read_poetry(&path),
+        }
+    }
+}
+
+impl FromStr for ImportSource {
+    type Err = Error;
+
+    /// Parse an `ImportSource` from a path. Pipfiles and pyproject.toml files are recognized
+    /// by name and any other file is read as a requirements file. `poetry` is short for
+    /// `pyproject.toml`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("poetry") {
+            return Ok(ImportSource::Poetry(PathBuf::from("pyproject.toml")));
+        }
+
+        let path = PathBuf::from(s);
+
+        match path.file_name().and_then(OsStr::to_str) {
+            Some("Pipfile") => Ok(ImportSource::Pipfile(path)),
+            Some("pyproject.toml") => Ok(ImportSource::Poetry(path)),
+            Some(_) => Ok(ImportSource::Requirements(path)),
+            None => Err(Error::InvalidImportSource(s.to_string())),
+        }
+    }
+}
+
+impl Display for ImportSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.path().display())
+    }
+}
+
+/// The project metadata and dependencies read from an `ImportSource`.
+#[derive(Debug, Default)]
+pub struct ImportedProject {
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub requires_python: Option<String>,
+    /// The requirements for `[project.dependencies]`.
+    pub dependencies: Vec<Requirement>,
+    /// The requirements for each group in `[project.optional-dependencies]`.
+    pub optional_dependencies: BTreeMap<String, Vec<Requirement>>,
+    /// Messages describing each entry that couldn't be translated.
+    pub skipped: Vec<String>,
+}
+
+impl ImportedProject {
+    fn push(&mut self, group: Option<&str>, requirement: Requirement) {
+        match group {
+            Some(g) => self
+                .optional_dependencies
+                .entry(g.to_string())
+                .or_default()
+                .push(requirement),
+            None => self.dependencies.push(requirement),
+        }
+    }
+
+    fn skip<T: Display>(&mut self, message: T) {
+        let message = message.to_string();
+
+        if !self.skipped.contains(&message) {
+            self.skipped.push(message);
+        }
+    }
+}
+
+/// Read a pip requirements file (see <https://pip.pypa.io/en/stable/reference/requirements-file-format/>).
+///
+/// Files included with `-r` are read as requirements. Files included with `-c` are read as
+/// constraints. A constraint is merged into the requirement for the same package if the
+/// requirement doesn't specify a version. Any other constraint can't be translated.
+fn read_requirements(path: &Path) -> HuakResult<ImportedProject> {
+    let mut project = ImportedProject::default();
+    let mut constraints = Vec::new();
+    let mut visited = HashSet::new();
+
+    read_requirements_file(path, false, &mut project, &mut constraints, &mut visited)?;
+
+    for constraint in constraints {
+        let name = normalized_name(&constraint.name);
+        let mut matched = false;
+
+        for req in project
+            .dependencies
+            .iter_mut()
+            .filter(|it| normalized_name(&it.name) == name)
+        {
+            matched = true;
+
+            if req.version_or_url.is_none() && constraint.marker.is_none() {
+                req.version_or_url.clone_from(&constraint.version_or_url);
+            }
+        }
+
+        if !matched || constraint.marker.is_some() {
+            project.skip(format!(
+                "constraint `{constraint}` wasn't imported: pyproject.toml doesn't support constraints"
+            ));
+        }
+    }
+
+    Ok(project)
+}
+
+fn read_requirements_file(
+    path: &Path,
+    is_constraints: bool,
+    project: &mut ImportedProject,
+    constraints: &mut Vec<Requirement>,
+    visited: &mut HashSet<PathBuf>,
+) -> HuakResult<()> {
+    let path = path.canonicalize()?;
+
+    if !visited.insert(path.clone()) {
+        return Ok(());
+    }
+
+    let contents = std::fs::read_to_string(&path)?;
+    let dir = path
+        .parent()
+        .expect("requirements file should have a parent directory");
+    let file_name = path.file_name().and_then(OsStr::to_str).unwrap_or_default();
+
+    for (line_number, line) in requirements_lines(&contents) {
+        let location = format!("{file_name}:{line_number}");
+
+        if let Some(value) = option_value(&line, &["-r", "--requirement"]) {
+            read_requirements_file(
+                &dir.join(value),
+                is_constraints,
+                project,
+                constraints,
+                visited,
+            )?;
+            continue;
+        }
+
+        if let Some(value) = option_value(&line, &["-c", "--constraint"]) {
+            read_requirements_file(&dir.join(value), true, project, constraints, visited)?;
+            continue;
+        }
+
+        if let Some(value) = option_value(&line, &["-e", "--editable"]) {
+            match direct_reference_requirement(value, dir) {
+                Ok(req) => {
+                    project.skip(format!(
+                        "`{line}` ({location}) was imported as a non-editable requirement"
+                    ));
+                    project.push(None, req);
+                }
+                Err(reason) => {
+                    project.skip(format!("`{line}` ({location}) wasn't imported: {reason}"));
+                }
+            }
+            continue;
+        }
+
+        if line.starts_with('-') {
+            project.skip(format!(
+                "`{line}` ({location}) wasn't imported: pip options aren't supported"
+            ));
+            continue;
+        }
+
+        // Per-requirement options like `--hash` follow the requirement.
+        let (requirement, options) = match line.split_once(" --") {
+            Some((requirement, options)) => (requirement.trim(), Some(options)),
+            None => (line.as_str(), None),
+        };
+
+        if let Some(options) = options {
+            let option = options.split(['=', ' ']).next().unwrap_or_default();
+            project.skip(format!(
+                "`--{option}` options weren't imported: huak.lock records hashes instead"
+            ));
+        }
+
+        let parsed = Requirement::from_str(requirement)
+            .map_err(|e| e.to_string())
+            .or_else(|_| {
+                crate::requirement_from_arg(requirement, dir)
+                    .map_err(|e| e.to_string())
+                    .and_then(|it| Requirement::from_str(&it).map_err(|e| e.to_string()))
+            })
+            .or_else(|_| direct_reference_requirement(requirement, dir));
+
+        match parsed {
+            Ok(req) if is_constraints => constraints.push(req),
+            Ok(req) => project.push(None, req),
+            Err(reason) => {
+                project.skip(format!("`{line}` ({location}) wasn't imported: {reason}"));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Get the logical lines of a requirements file with their line numbers. Comments are
+/// removed and lines ending with `\` are joined.
+fn requirements_lines(contents: &str) -> Vec<(usize, String)> {
+    let mut lines = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+
+    for (i, line) in contents.lines().enumerate() {
+        let (number, mut text) = current.take().unwrap_or((i + 1, String::new()));
+
+        if let Some(continued) = line.strip_suffix('\\') {
+            text.push_str(continued);
+            current = Some((number, text));
+            continue;
+        }
+        text.push_str(line);
+
+        let text = match text.find('#') {
+            Some(0) => "",
+            Some(i) if text[..i].ends_with(char::is_whitespace) => &text[..i],
+            _ => text.as_str(),
+        }
+        .trim();
+
+        if !text.is_empty() {
+            lines.push((number, text.to_string()));
+        }
+    }
+
+    if let Some((number, text)) = current {
+        lines.push((number, text.trim().to_string()));
+    }
+
+    lines
+}
+
+/// Get the value of a requirements file option like `-r file` or `--requirement=file`.
+fn option_value<'a>(line: &'a str, names: &[&str]) -> Option<&'a str> {
+    names.iter().find_map(|name| {
+        let rest = line.strip_prefix(name)?;
+
+        if rest.starts_with(char::is_whitespace) || rest.starts_with('=') {
+            Some(rest[1..].trim())
+        } else if name.len() == 2 && !rest.is_empty() && !name.starts_with("--") {
+            // Short options can be written without a space (-rrequirements.txt).
+            Some(rest.trim())
+        } else {
+            None
+        }
+    })
+}
+
+/// Build a `Requirement` from a URL or path without a package name. The name is read from
+/// an `#egg=` fragment or from the local project's pyproject.toml.
+fn direct_reference_requirement(reference: &str, dir: &Path) -> Result<Requirement, String> {
+    let (reference, egg) = match reference.split_once("#egg=") {
+        Some((reference, egg)) => (reference, Some(egg)),
+        None => (reference, None),
+    };
+
+    let url = match Url::parse(reference) {
+        Ok(it) if it.scheme().len() > 1 => it,
+        _ => {
+            let path = dir
+                .join(reference)
+                .canonicalize()
+                .map_err(|_| format!("{reference} doesn't exist"))?;
+            Url::from_file_path(&path).map_err(|()| format!("{reference} isn't a valid path"))?
+        }
+    };
+
+    let name = match egg {
+        Some(it) => it.to_string(),
+        None => url
+            .to_file_path()
+            .ok()
+            .and_then(|it| PyProjectToml::read_toml(it.join("pyproject.toml")).ok())
+            .and_then(|it| it.project_name())
+            .ok_or_else(|| format!("the package name for {reference} couldn't be determined"))?,
+    };
+
+    Requirement::from_str(&format!("{name} @ {url}")).map_err(|e| e.to_string())
+}
+
+/// Read a Pipfile. `[packages]` are imported as required dependencies, `[dev-packages]` as
+/// the optional `dev` group and any other package category as an optional group of the
+/// same name.
+fn read_pipfile(path: &Path) -> HuakResult<ImportedProject> {
+    let table = toml::from_str::<Table>(&std::fs::read_to_string(path)?)?;
+    let dir = path
+        .parent()
+        .expect("Pipfile should have a parent directory");
+    let mut project = ImportedProject::default();
+
+    if let Some(requires) = table.get("requires").and_then(Value::as_table) {
+        if let Some(version) = requires.get("python_full_version").and_then(Value::as_str) {
+            project.requires_python = Some(format!("=={version}"));
+        } else if let Some(version) = requires.get("python_version").and_then(Value::as_str) {
+            project.requires_python = Some(format!("=={version}.*"));
+        }
+    }
+
+    for source in table
+        .get("source")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|it| it.get("url").and_then(Value::as_str))
+        .filter(|it| !it.starts_with("https://pypi.org/simple"))
+    {
+        project.skip(format!(
+            "package source {source} wasn't imported: indexes aren't configured in pyproject.toml"
+        ));
+    }
+
+    if table.contains_key("scripts") {
+        project.skip("`[scripts]` wasn't imported: scripts aren't supported");
+    }
+
+    for (category, packages) in table
+        .iter()
+        .filter(|(key, _)| !PIPFILE_TABLES.contains(&key.as_str()))
+    {
+        let Some(packages) = packages.as_table() else {
+            continue;
+        };
+        let group = match category.as_str() {
+            "packages" => None,
+            "dev-packages" => Some("dev"),
+            it => Some(it),
+        };
+
+        for (name, value) in packages {
+            match pipfile_requirement(name, value, dir, &mut project) {
+                Ok(req) => project.push(group, req),
+                Err(reason) => {
+                    project.skip(format!(
+                        "`{name}` in [{category}] wasn't imported: {reason}"
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(project)
+}
+
+fn pipfile_requirement(
+    name: &str,
+    value: &Value,
+    dir: &Path,
+    project: &mut ImportedProject,
+) -> Result<Requirement, String> {
+    let table = match value {
+        Value::String(version) => {
+            return requirement(name, &[], &pipfile_version(version), None, &[])
+        }
+        Value::Table(it) => it,
+        _ => return Err("the entry isn't a version or a table".to_string()),
+    };
+
+    if table.get("editable").and_then(Value::as_bool) == Some(true) {
+        project.skip(format!(
+            "`{name}` was imported as a non-editable requirement"
+        ));
+    }
+    if let Some(index) = table.get("index").and_then(Value::as_str) {
+        project.skip(format!(
+            "the index `{index}` for `{name}` wasn't imported: indexes aren't configured in pyproject.toml"
+        ));
+    }
+
+    let extras = string_array(table.get("extras"));
+    let mut markers = table
+        .get("markers")
+        .and_then(Value::as_str)
+        .map(ToString::to_string)
+        .into_iter()
+        .collect::<Vec<_>>();
+    for key in PIPFILE_MARKER_KEYS {
+        if let Some(it) = table.get(key).and_then(Value::as_str) {
+            markers.push(format!("{key} {it}"));
+        }
+    }
+
+    let url = if let Some(git) = table.get("git").and_then(Value::as_str) {
+        let reference = table.get("ref").and_then(Value::as_str);
+        Some(vcs_url("git", git, reference))
+    } else if let Some(path) = table
+        .get("path")
+        .or_else(|| table.get("file"))
+        .and_then(Value::as_str)
+    {
+        Some(path_url(path, dir)?)
+    } else {
+        None
+    };
+
+    let version = table
+        .get("version")
+        .and_then(Value::as_str)
+        .map(pipfile_version)
+        .unwrap_or_default();
+
+    requirement(name, &extras, &version, url.as_deref(), &markers)
+}
+
+/// Pipfile versions are PEP 440 specifiers or `*` for any version.
+fn pipfile_version(version: &str) -> String {
+    let version = version.trim();
+
+    if version == "*" {
+        String::new()
+    } else if version.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("=={version}")
+    } else {
+        version.to_string()
+    }
+}
+
+/// Read a Poetry project. `[tool.poetry.dependencies]` are imported as required dependencies
+/// unless they're optional and listed in `[tool.poetry.extras]`. Each group in
+/// `[tool.poetry.group]` and the legacy `[tool.poetry.dev-dependencies]` are imported as
+/// optional groups.
+fn read_poetry(path: &Path) -> HuakResult<ImportedProject> {
+    let table = toml::from_str::<Table>(&std::fs::read_to_string(path)?)?;
+    let dir = path
+        .parent()
+        .expect("pyproject.toml should have a parent directory");
+    let Some(poetry) = table
+        .get("tool")
+        .and_then(|it| it.get("poetry"))
+        .and_then(Value::as_table)
+    else {
+        return Err(Error::InvalidImportSource(format!(
+            "{} has no [tool.poetry] table",
+            path.display()
+        )));
+    };
+    let mut project = ImportedProject {
+        name: poetry
+            .get("name")
+            .and_then(Value::as_str)
+            .map(ToString::to_string),
+        version: poetry
+            .get("version")
+            .and_then(Value::as_str)
+            .map(ToString::to_string),
+        description: poetry
+            .get("description")
+            .and_then(Value::as_str)
+            .map(ToString::to_string),
+        ..Default::default()
+    };
+
+    for key in poetry.keys().filter(|it| {
+        ![
+            "name",
+            "version",
+            "description",
+            "dependencies",
+            "dev-dependencies",
+            "group",
+            "extras",
+        ]
+        .contains(&it.as_str())
+    }) {
+        project.skip(format!("`[tool.poetry] {key}` wasn't imported"));
+    }
+
+    let extras = poetry_extras(poetry);
+
+    for (group, dependencies) in poetry_groups(poetry) {
+        let Some(dependencies) = dependencies.and_then(Value::as_table) else {
+            continue;
+        };
+
+        for (name, value) in dependencies {
+            if name == "python" && group.is_none() {
+                match value.as_str().map(poetry_version_specifiers) {
+                    Some(Ok(it)) => project.requires_python = Some(it),
+                    Some(Err(reason)) => {
+                        project.skip(format!("the Python requirement wasn't imported: {reason}"));
+                    }
+                    None => {
+                        project.skip("the Python requirement wasn't imported: it isn't a version");
+                    }
+                }
+                continue;
+            }
+
+            let req = match poetry_requirement(name, value, dir, &mut project) {
+                Ok(it) => it,
+                Err(reason) => {
+                    let table = group.map_or("dependencies".to_string(), |it| {
+                        format!("group.{it}.dependencies")
+                    });
+                    project.skip(format!(
+                        "`{name}` in [tool.poetry.{table}] wasn't imported: {reason}"
+                    ));
+                    continue;
+                }
+            };
+
+            let optional = value.get("optional").and_then(Value::as_bool) == Some(true);
+            match extras.get(&normalized_name(name)) {
+                Some(names) if optional && group.is_none() => {
+                    for extra in names {
+                        project.push(Some(extra), req.clone());
+                    }
+                }
+                _ => project.push(group, req),
+            }
+        }
+    }
+
+    Ok(project)
+}
+
+/// Map the normalized names of optional Poetry dependencies to the extras they're listed in.
+fn poetry_extras(poetry: &Table) -> BTreeMap<String, Vec<String>> {
+    let mut extras: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    for (extra, names) in poetry
+        .get("extras")
+        .and_then(Value::as_table)
+        .into_iter()
+        .flatten()
+    {
+        for name in string_array(Some(names)) {
+            extras
+                .entry(normalized_name(&name))
+                .or_default()
+                .push(extra.clone());
+        }
+    }
+
+    extras
+}
+
+/// Get each Poetry dependency table along with the group it's imported to. Required
+/// dependencies don't have a group.
+fn poetry_groups(poetry: &Table) -> Vec<(Option<&str>, Option<&Value>)> {
+    let mut groups = vec![(None, poetry.get("dependencies"))];
+    groups.push((Some("dev"), poetry.get("dev-dependencies")));
+
+    for (group, value) in poetry
+        .get("group")
+        .and_then(Value::as_table)
+        .into_iter()
+        .flatten()
+    {
+        groups.push((Some(group.as_str()), value.get("dependencies")));
+    }
+
+    groups
+}
+
+fn poetry_requirement(
+    name: &str,
+    value: &Value,
+    dir: &Path,
+    project: &mut ImportedProject,
+) -> Result<Requirement, String> {
+    let table = match value {
+        Value::String(constraint) => {
+            return requirement(
+                name,
+                &[],
+                &poetry_version_specifiers(constraint)?,
+                None,
+                &[],
+            );
+        }
+        Value::Table(it) => it,
+        Value::Array(_) => return Err("multiple constraints aren't supported".to_string()),
+        _ => return Err("the entry isn't a version or a table".to_string()),
+    };
+
+    if table.get("develop").and_then(Value::as_bool) == Some(true) {
+        project.skip(format!(
+            "`{name}` was imported as a non-editable requirement"
+        ));
+    }
+    if let Some(source) = table.get("source").and_then(Value::as_str) {
+        project.skip(format!(
+            "the source `{source}` for `{name}` wasn't imported: indexes aren't configured in pyproject.toml"
+        ));
+    }
+    if table.contains_key("allow-prereleases") {
+        project.skip(format!("`allow-prereleases` for `{name}` wasn't imported"));
+    }
+
+    let extras = string_array(table.get("extras"));
+    let mut markers = table
+        .get("markers")
+        .and_then(Value::as_str)
+        .map(ToString::to_string)
+        .into_iter()
+        .collect::<Vec<_>>();
+    if let Some(python) = table.get("python").and_then(Value::as_str) {
+        markers.extend(python_markers(&poetry_version_specifiers(python)?)?);
+    }
+    if let Some(platform) = table.get("platform").and_then(Value::as_str) {
+        markers.push(format!("sys_platform == \"{platform}\""));
+    }
+
+    let url = if let Some(git) = table.get("git").and_then(Value::as_str) {
+        let reference = ["rev", "tag", "branch"]
+            .iter()
+            .find_map(|key| table.get(*key).and_then(Value::as_str));
+        let url = vcs_url("git", git, reference);
+        match table.get("subdirectory").and_then(Value::as_str) {
+            Some(it) => Some(format!("{url}#subdirectory={it}")),
+            None => Some(url),
+        }
+    } else if let Some(path) = table.get("path").and_then(Value::as_str) {
+        Some(path_url(path, dir)?)
+    } else {
+        table
+            .get("url")
+            .and_then(Value::as_str)
+            .map(ToString::to_string)
+    };
+
+    let version = match table.get("version").and_then(Value::as_str) {
+        Some(it) => poetry_version_specifiers(it)?,
+        None => String::new(),
+    };
+
+    requirement(name, &extras, &version, url.as_deref(), &markers)
+}
+
+/// Convert a Poetry version constraint to PEP 440 version specifiers.
+///
+/// See <https://python-poetry.org/docs/dependency-specification/#version-constraints>.
+///
+/// - `^1.2.3` is written as `>=1.2.3,<2.0.0`. Versions below 1.0 are bound by their first
+///   non-zero component (`^0.2.3` is written as `>=0.2.3,<0.3.0`).
+/// - `~1.2.3` is written as `>=1.2.3,<1.3.0` and `~1` as `>=1,<2`.
+/// - `1.2.3` and `=1.2.3` are written as `==1.2.3`.
+/// - `*` is written as an empty string.
+///
+/// Alternatives (`||`) can't be written as PEP 440 version specifiers.
+pub fn poetry_version_specifiers(constraint: &str) -> Result<String, String> {
+    let constraint = constraint.trim();
+
+    if constraint.is_empty() || constraint == "*" {
+        return Ok(String::new());
+    }
+
+    if constraint.contains('|') {
+        return Err(format!(
+            "`{constraint}` uses alternatives, which PEP 440 doesn't support"
+        ));
+    }
+
+    let mut specifiers = Vec::new();
+    for part in constraint.split(',') {
+        // Constraints can be separated by spaces (`>=1.2 <2.0`) and operators can be followed
+        // by a space (`>= 1.2`).
+        let mut tokens = part.split_whitespace().peekable();
+        while let Some(token) = tokens.next() {
+            let token = if token.chars().all(|c| "<>=!~^".contains(c)) {
+                format!("{token}{}", tokens.next().unwrap_or_default())
+            } else {
+                token.to_string()
+            };
+            specifiers.push(poetry_specifier(&token)?);
+        }
+    }
+
+    let specifiers = specifiers.join(",");
+    VersionSpecifiers::from_str(&specifiers)
+        .map_err(|_| format!("`{constraint}` isn't a valid version constraint"))?;
+
+    Ok(specifiers)
+}
+
+fn poetry_specifier(constraint: &str) -> Result<String, String> {
+    if let Some(version) = constraint.strip_prefix('^') {
+        let release = release_components(version)?;
+        let bump = release
+            .iter()
+            .position(|it| *it != 0)
+            .unwrap_or(release.len() - 1);
+        Ok(format!(">={version},<{}", upper_bound(&release, bump)))
+    } else if constraint.starts_with("~=") {
+        Ok(constraint.to_string())
+    } else if let Some(version) = constraint.strip_prefix('~') {
+        let release = release_components(version)?;
+        let bump = usize::from(release.len() > 1);
+        Ok(format!(">={version},<{}", upper_bound(&release, bump)))
+    } else if [">=", "<=", "==", "!=", ">", "<"]
+        .iter()
+        .any(|op| constraint.starts_with(op))
+    {
+        Ok(constraint.to_string())
+    } else if let Some(version) = constraint.strip_prefix('=') {
+        Ok(format!("=={version}"))
+    } else {
+        Ok(format!("=={constraint}"))
+    }
+}
+
+/// Get the numeric release components of a version (`1.2.3b1` has the components 1, 2 and 3).
+fn release_components(version: &str) -> Result<Vec<u64>, String> {
+    let mut components = Vec::new();
+
+    for part in version.split('.') {
+        let digits = part
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .collect::<String>();
+        let Ok(it) = digits.parse() else {
+            break;
+        };
+        components.push(it);
+
+        if digits.len() != part.len() {
+            break;
+        }
+    }
+
+    if components.is_empty() {
+        Err(format!("`{version}` isn't a valid version"))
+    } else {
+        Ok(components)
+    }
+}
+
+/// Build the exclusive upper bound for a release by bumping the component at `index`. The
+/// components after it are zeroed.
+fn upper_bound(release: &[u64], index: usize) -> String {
+    release
+        .iter()
+        .enumerate()
+        .map(|(i, it)| match i.cmp(&index) {
+            std::cmp::Ordering::Less => it.to_string(),
+            std::cmp::Ordering::Equal => (it + 1).to_string(),
+            std::cmp::Ordering::Greater => "0".to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Write PEP 440 version specifiers for Python as environment markers.
+fn python_markers(specifiers: &str) -> Result<Vec<String>, String> {
+    let specifiers = VersionSpecifiers::from_str(specifiers).map_err(|e| e.to_string())?;
+
+    Ok(specifiers
+        .iter()
+        .map(|it| {
+            let version = it.version().to_string();
+            let key = if version.split('.').count() > 2 {
+                "python_full_version"
+            } else {
+                "python_version"
+            };
+            format!("{key} {} \"{version}\"", it.operator())
+        })
+        .collect())
+}
+
+fn vcs_url(kind: &str, url: &str, reference: Option<&str>) -> String {
+    match reference {
+        Some(it) => format!("{kind}+{url}@{it}"),
+        None => format!("{kind}+{url}"),
+    }
+}
+
+fn path_url(path: &str, dir: &Path) -> Result<String, String> {
+    let path = dir
+        .join(path)
+        .canonicalize()
+        .map_err(|_| format!("{path} doesn't exist"))?;
+
+    Url::from_file_path(&path)
+        .map(String::from)
+        .map_err(|()| format!("{} isn't a valid path", path.display()))
+}
+
+fn string_array(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Build a `Requirement` from its parts.
+fn requirement(
+    name: &str,
+    extras: &[String],
+    version: &str,
+    url: Option<&str>,
+    markers: &[String],
+) -> Result<Requirement, String> {
+    let extras = if extras.is_empty() {
+        String::new()
+    } else {
+        format!("[{}]", extras.join(","))
+    };
+    let reference = url.map_or_else(|| version.to_string(), |it| format!(" @ {it}"));
+    // A space is required between a URL and its markers.
+    let markers = match markers {
+        [] => String::new(),
+        [it] => format!(" ; {it}"),
+        _ => format!(
+            " ; {}",
+            markers
+                .iter()
+                .map(|it| format!("({it})"))
+                .collect::<Vec<_>>()
+                .join(" and ")
+        ),
+    };
+
+    Requirement::from_str(&format!("{name}{extras}{reference}{markers}")).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_poetry_version_specifiers() {
+        let cases = [
+            ("^1.2.3", ">=1.2.3,<2.0.0"),
+            ("^1.2", ">=1.2,<2.0"),
+            ("^1", ">=1,<2"),
+            ("^0.2.3", ">=0.2.3,<0.3.0"),
+            ("^0.0.3", ">=0.0.3,<0.0.4"),
+            ("^0.0", ">=0.0,<0.1"),
+            ("^0", ">=0,<1"),
+            ("~1.2.3", ">=1.2.3,<1.3.0"),
+            ("~1.2", ">=1.2,<1.3"),
+            ("~1", ">=1,<2"),
+            ("~=1.2", "~=1.2"),
+            ("1.2.3", "==1.2.3"),
+            ("=1.2.3", "==1.2.3"),
+            ("1.2.*", "==1.2.*"),
+            (">= 1.2, < 1.5", ">=1.2,<1.5"),
+            (">=1.2 <2.0", ">=1.2,<2.0"),
+            ("*", ""),
+        ];
+
+        for (constraint, expected) in cases {
+            assert_eq!(
+                poetry_version_specifiers(constraint).unwrap(),
+                expected,
+                "{constraint}"
+            );
+        }
+        assert!(poetry_version_specifiers("^1.2 || ^2.0").is_err());
+        assert!(poetry_version_specifiers("^abc").is_err());
+    }
+
+    #[test]
+    fn test_read_requirements() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("requirements.txt"),
+            r#"# Runtime dependencies
+-r base.txt
+-c constraints.txt
+--index-url https://example.com/simple
+requests[socks] >=2.31 ; python_version >= "3.8"  # comment
+click \
+    --hash=sha256:ae74fb96
+"#,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("base.txt"), "attrs==23.1.0\n").unwrap();
+        std::fs::write(
+            dir.path().join("constraints.txt"),
+            "click==8.1.7\nurllib3<2\n",
+        )
+        .unwrap();
+
+        let project = ImportSource::from_str("requirements.txt")
+            .unwrap()
+            .read(dir.path())
+            .unwrap();
+
+        assert_eq!(
+            project
+                .dependencies
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            [
+                "attrs ==23.1.0",
+                "requests[socks] >=2.31 ; python_version >= '3.8'",
+                "click ==8.1.7"
+            ]
+        );
+        assert_eq!(project.skipped.len(), 3);
+        assert!(project.skipped[0].contains("--index-url"));
+        assert!(project.skipped[1].contains("--hash"));
+        assert!(project.skipped[2].contains("urllib3"));
+    }
+
+    #[test]
+    fn test_read_pipfile() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("Pipfile"),
+            r#"[[source]]
+url = "https://pypi.org/simple"
+verify_ssl = true
+name = "pypi"
+
+[packages]
+requests = "*"
+click = "==8.1.7"
+httpx = { version = ">=0.25", extras = ["http2"], sys_platform = "== 'linux'" }
+xlcsv = { git = "https://github.com/cnpryer/xlcsv.git", ref = "v0.1.0" }
+
+[dev-packages]
+pytest = ">=7"
+
+[requires]
+python_version = "3.11"
+"#,
+        )
+        .unwrap();
+
+        let project = ImportSource::from_str("Pipfile")
+            .unwrap()
+            .read(dir.path())
+            .unwrap();
+
+        assert_eq!(project.requires_python.as_deref(), Some("==3.11.*"));
+        assert_eq!(
+            project
+                .dependencies
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            [
+                "requests",
+                "click ==8.1.7",
+                "httpx[http2] >=0.25 ; sys_platform == 'linux'",
+                "xlcsv @ git+https://github.com/cnpryer/xlcsv.git@v0.1.0"
+            ]
+        );
+        assert_eq!(
+            project.optional_dependencies["dev"]
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            ["pytest >=7"]
+        );
+        assert!(project.skipped.is_empty());
+    }
+
+    #[test]
+    fn test_read_poetry() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("pyproject.toml"),
+            r#"[tool.poetry]
+name = "mock-project"
+version = "0.1.0"
+description = ""
+authors = ["Chris Pryer <cnpryer@gmail.com>"]
+
+[tool.poetry.dependencies]
+python = "^3.8"
+click = "^8.1"
+pandas = { version = "~2.1", optional = true }
+tomli = { version = ">=2", python = "<3.11" }
+legacy = ">=1 || <3"
+
+[tool.poetry.extras]
+data = ["pandas"]
+
+[tool.poetry.group.test.dependencies]
+pytest = "^7.4.3"
+"#,
+        )
+        .unwrap();
+
+        let project = ImportSource::from_str("poetry")
+            .unwrap()
+            .read(dir.path())
+            .unwrap();
+
+        assert_eq!(project.name.as_deref(), Some("mock-project"));
+        assert_eq!(project.requires_python.as_deref(), Some(">=3.8,<4.0"));
+        assert_eq!(
+            project
+                .dependencies
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            ["click >=8.1, <9.0", "tomli >=2 ; python_version < '3.11'"]
+        );
+        assert_eq!(
+            project.optional_dependencies["data"]
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            ["pandas >=2.1, <2.2"]
+        );
+        assert_eq!(
+            project.optional_dependencies["test"]
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            ["pytest >=7.4.3, <8.0.0"]
+        );
+        assert_eq!(project.skipped.len(), 2);
+        assert!(project.skipped[0].contains("authors"));
+        assert!(project.skipped[1].contains("legacy"));
+    }
+}
@@ -0,0 +1,489 @@
+use crate::{
+    lock::normalized_name,
+    wheel::{normalize_path, parse_record},
+    CoreMetadata, Error, HuakResult,
+};
+use pep508_rs::Requirement;
+use serde::Deserialize;
+use std::{
+    collections::BTreeSet,
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// A Python distribution installed to a site-packages directory.
+///
+/// Installed distributions are described by a *.dist-info directory containing the
+/// distribution's METADATA. Distributions installed with setuptools' legacy methods are
+/// described by a *.egg-info directory (or file) containing PKG-INFO instead.
+///
+/// See <https://packaging.python.org/en/latest/specifications/recording-installed-packages/>.
+#[derive(Clone, Debug)]
+pub struct InstalledDistribution {
+    /// The path to the distribution's metadata directory.
+    path: PathBuf,
+    /// The distribution's `CoreMetadata`.
+    metadata: CoreMetadata,
+}
+
+impl InstalledDistribution {
+    /// Read an `InstalledDistribution` from its *.dist-info directory or its *.egg-info
+    /// directory or file.
+    pub fn from_path<T: Into<PathBuf>>(path: T) -> HuakResult<InstalledDistribution> {
+        let path = path.into();
+        let metadata_path = if path.is_file() {
+            path.clone()
+        } else if is_egg_info(&path) {
+            path.join("PKG-INFO")
+        } else {
+            path.join("METADATA")
+        };
+        let metadata = CoreMetadata::from_str(&std::fs::read_to_string(metadata_path)?)?;
+
+        Ok(InstalledDistribution { path, metadata })
+    }
+
+    /// Get the name of the distribution as it's listed in its metadata.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        self.metadata.name().unwrap_or_default()
+    }
+
+    /// Get the normalized name of the distribution.
+    #[must_use]
+    pub fn normalized_name(&self) -> String {
+        normalized_name(self.name())
+    }
+
+    /// Get the version of the distribution as it's listed in its metadata.
+    #[must_use]
+    pub fn version(&self) -> &str {
+        self.metadata.get("Version").unwrap_or_default()
+    }
+
+    /// Get the `Requirement`s of the distribution (Requires-Dist). The requirements of
+    /// *.egg-info distributions are read from their requires.txt.
+    #[must_use]
+    pub fn requires_dist(&self) -> Vec<Requirement> {
+        let requirements = self.metadata.requires_dist();
+        if !requirements.is_empty() || !is_egg_info(&self.path) {
+            return requirements;
+        }
+
+        std::fs::read_to_string(self.path.join("requires.txt"))
+            .map(|it| egg_info_requirements(&it))
+            .unwrap_or_default()
+    }
+
+    /// Get the `DirectUrl` the distribution was installed from if it was installed from a
+    /// direct reference (`direct_url.json`).
+    #[must_use]
+    pub fn direct_url(&self) -> Option<DirectUrl> {
+        std::fs::read_to_string(self.path.join("direct_url.json"))
+            .ok()
+            .and_then(|it| serde_json::from_str::<RawDirectUrl>(&it).ok())
+            .map(DirectUrl::from)
+    }
+
+    /// Get the name of the tool that installed the distribution (INSTALLER).
+    #[must_use]
+    pub fn installer(&self) -> Option<String> {
+        std::fs::read_to_string(self.path.join("INSTALLER"))
+            .ok()
+            .map(|it| it.trim().to_string())
+            .filter(|it| !it.is_empty())
+    }
+
+    /// Get a reference to the distribution's `CoreMetadata`.
+    #[must_use]
+    pub fn metadata(&self) -> &CoreMetadata {
+        &self.metadata
+    }
+
+    /// Get a reference to the path of the distribution's metadata directory.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Get the paths of the files installed with the distribution from its RECORD. The
+    /// files of *.egg-info distributions are read from their installed-files.txt.
+    pub fn files(&self) -> HuakResult<Vec<PathBuf>> {
+        let installed_files = self.path.join("installed-files.txt");
+        if is_egg_info(&self.path) && installed_files.exists() {
+            // Paths in installed-files.txt are relative to the *.egg-info directory.
+            let files = std::fs::read_to_string(installed_files)?
+                .lines()
+                .map(str::trim)
+                .filter(|it| !it.is_empty())
+                .map(|it| normalize_path(&self.path.join(it)))
+                .collect();
+            return Ok(files);
+        }
+
+        let record = self.path.join("RECORD");
+        if !record.exists() {
+            return Err(Error::RecordNotFound(self.name().to_string()));
+        }
+
+        let root = self.root();
+        let files = parse_record(&std::fs::read_to_string(record)?)
+            .into_iter()
+            .filter_map(|row| row.into_iter().next())
+            .map(|it| normalize_path(&root.join(it)))
+            .collect();
+
+        Ok(files)
+    }
+
+    /// Uninstall the distribution by removing the files listed in its RECORD.
+    ///
+    /// Bytecode cached for the removed modules is removed too, as are directories in
+    /// site-packages left empty.
+    pub fn uninstall(&self) -> HuakResult<()> {
+        let root = self.root();
+        let mut dirs = BTreeSet::new();
+
+        for path in self.files()? {
+            if path.is_file() || path.is_symlink() {
+                std::fs::remove_file(&path)?;
+            }
+
+            let Some(parent) = path.parent() else {
+                continue;
+            };
+
+            if path
+                .extension()
+                .is_some_and(|it| it.eq_ignore_ascii_case("py"))
+            {
+                let cache = parent.join("__pycache__");
+                let prefix = path
+                    .file_stem()
+                    .map(|it| format!("{}.", it.to_string_lossy()))
+                    .unwrap_or_default();
+                if cache.is_dir() {
+                    for entry in std::fs::read_dir(&cache)? {
+                        let entry = entry?.path();
+                        let is_cached = entry
+                            .file_name()
+                            .is_some_and(|it| it.to_string_lossy().starts_with(&prefix))
+                            && entry
+                                .extension()
+                                .is_some_and(|it| it.eq_ignore_ascii_case("pyc"));
+                        if is_cached {
+                            std::fs::remove_file(entry)?;
+                        }
+                    }
+                }
+                dirs.insert(cache);
+            }
+
+            dirs.insert(parent.to_path_buf());
+        }
+
+        if self.path.is_dir() {
+            std::fs::remove_dir_all(&self.path)?;
+        } else if self.path.exists() {
+            std::fs::remove_file(&self.path)?;
+        }
+
+        // Deeper directories sort after their parents, so they're removed first.
+        for dir in dirs.iter().rev() {
+            let mut dir = dir.as_path();
+            while dir != root
+                && dir.starts_with(&root)
+                && std::fs::read_dir(dir).is_ok_and(|mut it| it.next().is_none())
+            {
+                std::fs::remove_dir(dir)?;
+                let Some(parent) = dir.parent() else {
+                    break;
+                };
+                dir = parent;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the directory containing the distribution's metadata directory.
+    fn root(&self) -> PathBuf {
+        normalize_path(
+            self.path
+                .parent()
+                .expect("metadata directory should have a parent directory"),
+        )
+    }
+}
+
+/// A direct reference a distribution was installed from.
+///
+/// See <https://packaging.python.org/en/latest/specifications/direct-url/>.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DirectUrl {
+    /// The URL of the archive, directory or repository.
+    pub url: String,
+    /// The version control system of a repository (like git).
+    pub vcs: Option<String>,
+    /// The commit of a repository that was installed.
+    pub commit_id: Option<String>,
+    /// Whether a local directory was installed in editable mode.
+    pub editable: bool,
+}
+
+impl Display for DirectUrl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.vcs.as_ref(), self.commit_id.as_ref()) {
+            (Some(vcs), Some(commit_id)) => write!(f, "{vcs}+{}@{commit_id}", self.url),
+            (Some(vcs), None) => write!(f, "{vcs}+{}", self.url),
+            _ => write!(f, "{}", self.url),
+        }
+    }
+}
+
+impl From<RawDirectUrl> for DirectUrl {
+    fn from(value: RawDirectUrl) -> Self {
+        DirectUrl {
+            url: value.url,
+            vcs: value.vcs_info.as_ref().map(|it| it.vcs.clone()),
+            commit_id: value.vcs_info.and_then(|it| it.commit_id),
+            editable: value.dir_info.is_some_and(|it| it.editable),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RawDirectUrl {
+    url: String,
+    #[serde(default)]
+    vcs_info: Option<RawVcsInfo>,
+    #[serde(default)]
+    dir_info: Option<RawDirInfo>,
+}
+
+#[derive(Deserialize)]
+struct RawVcsInfo {
+    vcs: String,
+    #[serde(default)]
+    commit_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawDirInfo {
+    #[serde(default)]
+    editable: bool,
+}
+
+/// Check if a path is a *.egg-info directory or file.
+fn is_egg_info(path: &Path) -> bool {
+    path.extension().is_some_and(|it| it == "egg-info")
+}
+
+/// Parse the requirements in an *.egg-info distribution's requires.txt. Requirements are
+/// listed in sections named "[extra]", "[:marker]" or "[extra:marker]".
+fn egg_info_requirements(contents: &str) -> Vec<Requirement> {
+    let mut section = String::new();
+
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|it| !it.is_empty() && !it.starts_with('#'))
+        .filter_map(|line| {
+            if let Some(it) = line.strip_prefix('[').and_then(|it| it.strip_suffix(']')) {
+                section = it.to_string();
+                return None;
+            }
+
+            let (extra, marker) = section.split_once(':').unwrap_or((&section, ""));
+            let mut markers = Vec::new();
+            if !marker.is_empty() {
+                markers.push(format!("({marker})"));
+            }
+            if !extra.is_empty() {
+                markers.push(format!("extra == \"{extra}\""));
+            }
+
+            if markers.is_empty() {
+                Requirement::from_str(line).ok()
+            } else {
+                Requirement::from_str(&format!("{line}; {}", markers.join(" and "))).ok()
+            }
+        })
+        .collect()
+}
+
+/// Get the `InstalledDistribution`s in a site-packages directory sorted by name.
+///
+/// Metadata directories that can't be read are skipped.
+pub fn installed_distributions<T: AsRef<Path>>(
+    site_packages: T,
+) -> HuakResult<Vec<InstalledDistribution>> {
+    let site_packages = site_packages.as_ref();
+    let mut distributions = Vec::new();
+
+    if !site_packages.exists() {
+        return Ok(distributions);
+    }
+
+    for entry in std::fs::read_dir(site_packages)? {
+        let path = entry?.path();
+        let is_dist_info = path.extension().is_some_and(|it| it == "dist-info") && path.is_dir();
+
+        if is_dist_info || is_egg_info(&path) {
+            if let Ok(it) = InstalledDistribution::from_path(path) {
+                distributions.push(it);
+            }
+        }
+    }
+
+    distributions.sort_by_key(InstalledDistribution::normalized_name);
+
+    Ok(distributions)
+}
+
+/// Write mock *.dist-info directories for (name, version, requires-dist) to a directory.
+#[cfg(test)]
+pub(crate) fn write_mock_distributions<T: AsRef<Path>>(
+    dir: T,
+    distributions: &[(&str, &str, &[&str])],
+) {
+    for (name, version, requires) in distributions {
+        let path = dir.as_ref().join(format!("{name}-{version}.dist-info"));
+        std::fs::create_dir_all(&path).unwrap();
+        let mut metadata = format!("Metadata-Version: 2.1\nName: {name}\nVersion: {version}\n");
+        for req in *requires {
+            metadata.push_str("Requires-Dist: ");
+            metadata.push_str(req);
+            metadata.push('\n');
+        }
+        std::fs::write(path.join("METADATA"), metadata).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_installed_distributions() {
+        let dir = tempdir().unwrap();
+        let write = |dir_name: &str, metadata: &str| {
+            let path = dir.path().join(dir_name);
+            std::fs::create_dir_all(&path).unwrap();
+            std::fs::write(path.join("METADATA"), metadata).unwrap();
+        };
+        write(
+            "typing_extensions-4.8.0.dist-info",
+            "Metadata-Version: 2.1\nName: typing_extensions\nVersion: 4.8.0\n",
+        );
+        write(
+            "click-8.1.7.dist-info",
+            "Metadata-Version: 2.1\nName: click\nVersion: 8.1.7\nRequires-Dist: colorama; platform_system == \"Windows\"\n",
+        );
+        std::fs::create_dir_all(dir.path().join("click")).unwrap();
+
+        let distributions = installed_distributions(dir.path()).unwrap();
+
+        assert_eq!(
+            distributions
+                .iter()
+                .map(|it| (it.normalized_name(), it.version()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("click".to_string(), "8.1.7"),
+                ("typing-extensions".to_string(), "4.8.0")
+            ]
+        );
+        assert_eq!(distributions[0].requires_dist()[0].name, "colorama");
+    }
+
+    #[test]
+    fn test_egg_info_distributions() {
+        let dir = tempdir().unwrap();
+        let egg_info = dir.path().join("PyYAML-6.0.1-py3.11.egg-info");
+        std::fs::create_dir_all(&egg_info).unwrap();
+        std::fs::write(
+            egg_info.join("PKG-INFO"),
+            "Metadata-Version: 2.1\nName: PyYAML\nVersion: 6.0.1\n",
+        )
+        .unwrap();
+        std::fs::write(
+            egg_info.join("requires.txt"),
+            "click>=7\n\n[dev]\npytest\n\n[:python_version < \"3.8\"]\nimportlib-metadata\n",
+        )
+        .unwrap();
+        std::fs::write(
+            egg_info.join("installed-files.txt"),
+            "../yaml/__init__.py\nPKG-INFO\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("distro-1.8.0.egg-info"),
+            "Metadata-Version: 1.0\nName: distro\nVersion: 1.8.0\n",
+        )
+        .unwrap();
+
+        let distributions = installed_distributions(dir.path()).unwrap();
+
+        assert_eq!(
+            distributions
+                .iter()
+                .map(|it| (it.normalized_name(), it.version()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("distro".to_string(), "1.8.0"),
+                ("pyyaml".to_string(), "6.0.1")
+            ]
+        );
+        assert_eq!(
+            distributions[1]
+                .requires_dist()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            [
+                "click >=7",
+                "pytest ; extra == 'dev'",
+                "importlib-metadata ; python_version < '3.8'"
+            ]
+        );
+        assert_eq!(
+            distributions[1].files().unwrap(),
+            [
+                dir.path().join("yaml").join("__init__.py"),
+                egg_info.join("PKG-INFO")
+            ]
+        );
+        assert!(distributions[0].requires_dist().is_empty());
+    }
+
+    #[test]
+    fn test_direct_url_and_installer() {
+        let dir = tempdir().unwrap();
+        write_mock_distributions(
+            dir.path(),
+            &[("xlcsv", "0.1.0", &[]), ("click", "8.1.7", &[])],
+        );
+        let dist_info = dir.path().join("xlcsv-0.1.0.dist-info");
+        std::fs::write(
+            dist_info.join("direct_url.json"),
+            r#"{"url": "https://github.com/cnpryer/xlcsv.git", "vcs_info": {"vcs": "git", "commit_id": "abc123"}}"#,
+        )
+        .unwrap();
+        std::fs::write(dist_info.join("INSTALLER"), "huak\n").unwrap();
+
+        let distributions = installed_distributions(dir.path()).unwrap();
+        let direct_url = distributions[1].direct_url().unwrap();
+
+        assert_eq!(
+            direct_url.to_string(),
+            "git+https://github.com/cnpryer/xlcsv.git@abc123"
+        );
+        assert!(!direct_url.editable);
+        assert_eq!(distributions[1].installer().as_deref(), Some("huak"));
+        assert!(distributions[0].direct_url().is_none());
+        assert!(distributions[0].installer().is_none());
+    }
+}
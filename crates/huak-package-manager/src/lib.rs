@@ -13,33 +13,63 @@
 //! 3. Running tests
 //! 4. Distributing the project
 
+mod audit;
+mod cache;
 mod config;
+mod credentials;
 mod dependency;
+mod diff;
 mod environment;
 mod error;
 mod fs;
 mod git;
+mod graph;
+mod import;
+mod installed;
+mod licenses;
+mod lock;
 mod manifest;
+mod metadata;
 pub mod ops;
 mod package;
 mod python_environment;
+mod resolve;
+mod sources;
 mod sys;
+mod venv;
+mod wheel;
 mod workspace;
 
+pub use audit::{AdvisoryDatabase, Vulnerability};
+pub use cache::{Cache, CacheInfo};
 pub use config::Config;
-pub use dependency::{dependency_iter, Dependency};
+pub use credentials::{redact, CredentialProvider, Credentials};
+pub use dependency::{dependency_iter, requirement_from_arg, Dependency, VersionBound};
 pub use environment::{env_path_string, env_path_values, Environment};
 pub use error::{Error, HuakResult};
 pub use fs::{copy_dir, last_path_component, CopyDirOptions};
 pub use git::{default_python_gitignore, init as git_init};
+pub use graph::{DependencyGraph, DependencyPath, GraphEdge, GraphPackage, GraphRoot};
+pub use import::{poetry_version_specifiers, ImportSource, ImportedProject};
+pub use installed::{installed_distributions, DirectUrl, InstalledDistribution};
+pub use licenses::{DistributionLicense, LicenseExpression, LicensePolicy};
+pub use lock::{LockedFile, LockedPackage, Lockfile};
 pub use manifest::{
     default_package_entrypoint_string, default_package_test_file_contents,
-    default_pyproject_toml_contents, LocalManifest,
+    default_pyproject_toml_contents, LocalManifest, ManifestGroup,
 };
+pub use metadata::CoreMetadata;
 pub use package::{importable_package_name, Package};
 pub use python_environment::{
-    active_python_env_path, directory_is_venv, initialize_venv, venv_executables_dir_path,
-    InstallOptions, PythonEnvironment,
+    active_python_env_path, directory_is_conda_env, directory_is_venv, initialize_venv,
+    venv_executables_dir_path, InstallOptions, PythonEnvironment,
 };
-pub use sys::{shell_name, shell_path, SubprocessError, TerminalOptions, Verbosity};
+pub use resolve::{
+    DistributionFile, PackageIndex, ProjectPage, Resolution, ResolvedDependency, ResolvedPackage,
+    Resolver, ResolverOptions, Tags, WheelFilename,
+};
+pub use sources::PackageSources;
+pub use sys::{shell_name, shell_path, OutputFormat, SubprocessError, TerminalOptions, Verbosity};
+pub use venv::{create_venv, VenvOptions};
+pub use wheel::{InstallScheme, UnpackedWheel, Wheel};
 pub use workspace::{Workspace, WorkspaceOptions};
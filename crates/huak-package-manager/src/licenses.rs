@@ -0,0 +1,534 @@
+use crate::{Error, InstalledDistribution};
+use serde::Serialize;
+use std::str::FromStr;
+
+/// The license of a distribution with no license metadata.
+const UNKNOWN_LICENSE: &str = "UNKNOWN";
+
+/// The prefix of Trove classifiers for licenses.
+const LICENSE_CLASSIFIER_PREFIX: &str = "License ::";
+
+/// The SPDX identifiers of common license classifiers by the classifier's last segment.
+const CLASSIFIER_LICENSES: &[(&str, &str)] = &[
+    ("Apache Software License", "Apache-2.0"),
+    ("Boost Software License 1.0 (BSL-1.0)", "BSL-1.0"),
+    (
+        "CC0 1.0 Universal (CC0 1.0) Public Domain Dedication",
+        "CC0-1.0",
+    ),
+    ("Eclipse Public License 2.0 (EPL-2.0)", "EPL-2.0"),
+    ("GNU Affero General Public License v3", "AGPL-3.0-only"),
+    (
+        "GNU Affero General Public License v3 or later (AGPLv3+)",
+        "AGPL-3.0-or-later",
+    ),
+    ("GNU General Public License v2 (GPLv2)", "GPL-2.0-only"),
+    (
+        "GNU General Public License v2 or later (GPLv2+)",
+        "GPL-2.0-or-later",
+    ),
+    ("GNU General Public License v3 (GPLv3)", "GPL-3.0-only"),
+    (
+        "GNU General Public License v3 or later (GPLv3+)",
+        "GPL-3.0-or-later",
+    ),
+    (
+        "GNU Lesser General Public License v2 (LGPLv2)",
+        "LGPL-2.0-only",
+    ),
+    (
+        "GNU Lesser General Public License v2 or later (LGPLv2+)",
+        "LGPL-2.0-or-later",
+    ),
+    (
+        "GNU Lesser General Public License v3 (LGPLv3)",
+        "LGPL-3.0-only",
+    ),
+    (
+        "GNU Lesser General Public License v3 or later (LGPLv3+)",
+        "LGPL-3.0-or-later",
+    ),
+    ("ISC License (ISCL)", "ISC"),
+    ("MIT License", "MIT"),
+    ("MIT No Attribution License (MIT-0)", "MIT-0"),
+    ("Mozilla Public License 2.0 (MPL 2.0)", "MPL-2.0"),
+    ("Python Software Foundation License", "PSF-2.0"),
+    ("The Unlicense (Unlicense)", "Unlicense"),
+    ("zlib/libpng License", "Zlib"),
+];
+
+/// The SPDX license identifiers the License field is recognized as an expression of. This is
+/// a subset of the SPDX license list covering the licenses Python packages commonly use.
+const SPDX_LICENSE_IDS: &[&str] = &[
+    "0BSD",
+    "AFL-3.0",
+    "AGPL-3.0",
+    "AGPL-3.0-only",
+    "AGPL-3.0-or-later",
+    "Apache-1.1",
+    "Apache-2.0",
+    "APSL-2.0",
+    "Artistic-1.0",
+    "Artistic-2.0",
+    "BlueOak-1.0.0",
+    "BSD-1-Clause",
+    "BSD-2-Clause",
+    "BSD-2-Clause-Patent",
+    "BSD-3-Clause",
+    "BSD-3-Clause-Clear",
+    "BSD-4-Clause",
+    "BSL-1.0",
+    "bzip2-1.0.6",
+    "CC-BY-3.0",
+    "CC-BY-4.0",
+    "CC-BY-NC-4.0",
+    "CC-BY-NC-SA-4.0",
+    "CC-BY-ND-4.0",
+    "CC-BY-SA-3.0",
+    "CC-BY-SA-4.0",
+    "CC0-1.0",
+    "CDDL-1.0",
+    "CDDL-1.1",
+    "CECILL-2.1",
+    "CECILL-B",
+    "CECILL-C",
+    "CNRI-Python",
+    "CPL-1.0",
+    "ECL-2.0",
+    "EFL-2.0",
+    "EPL-1.0",
+    "EPL-2.0",
+    "EUPL-1.1",
+    "EUPL-1.2",
+    "GFDL-1.3-only",
+    "GFDL-1.3-or-later",
+    "GPL-1.0-or-later",
+    "GPL-2.0",
+    "GPL-2.0-only",
+    "GPL-2.0-or-later",
+    "GPL-3.0",
+    "GPL-3.0-only",
+    "GPL-3.0-or-later",
+    "HPND",
+    "ISC",
+    "LGPL-2.0",
+    "LGPL-2.0-only",
+    "LGPL-2.0-or-later",
+    "LGPL-2.1",
+    "LGPL-2.1-only",
+    "LGPL-2.1-or-later",
+    "LGPL-3.0",
+    "LGPL-3.0-only",
+    "LGPL-3.0-or-later",
+    "LPPL-1.3c",
+    "MIT",
+    "MIT-0",
+    "MIT-CMU",
+    "MPL-1.1",
+    "MPL-2.0",
+    "MPL-2.0-no-copyleft-exception",
+    "MS-PL",
+    "MS-RL",
+    "MulanPSL-2.0",
+    "NCSA",
+    "ODbL-1.0",
+    "OFL-1.1",
+    "OpenSSL",
+    "OSL-3.0",
+    "PostgreSQL",
+    "PSF-2.0",
+    "Python-2.0",
+    "Python-2.0.1",
+    "Ruby",
+    "Unicode-3.0",
+    "Unicode-DFS-2016",
+    "Unlicense",
+    "UPL-1.0",
+    "Vim",
+    "W3C",
+    "WTFPL",
+    "X11",
+    "Zlib",
+    "ZPL-2.0",
+    "ZPL-2.1",
+];
+
+/// The license metadata of an installed distribution.
+///
+/// Licenses are read from the distribution's License-Expression (see
+/// <https://peps.python.org/pep-0639/>), License and license classifier fields.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct DistributionLicense {
+    /// The name of the distribution.
+    pub name: String,
+    /// The version of the distribution.
+    pub version: String,
+    /// A summary of the license. This is the license expression, a short License field or
+    /// the license classifiers.
+    pub license: String,
+    /// The SPDX license expression (License-Expression).
+    pub expression: Option<String>,
+    /// The License field. This is sometimes the full text of the license.
+    pub license_text: Option<String>,
+    /// The last segments of the license classifiers (like "MIT License").
+    pub classifiers: Vec<String>,
+}
+
+impl DistributionLicense {
+    /// Get the `DistributionLicense` of an `InstalledDistribution`.
+    #[must_use]
+    pub fn from_distribution(distribution: &InstalledDistribution) -> DistributionLicense {
+        let metadata = distribution.metadata();
+        let non_empty = |it: &str| {
+            let it = it.trim();
+            (!it.is_empty() && !it.eq_ignore_ascii_case(UNKNOWN_LICENSE)).then(|| it.to_string())
+        };
+        let expression = metadata.get("License-Expression").and_then(non_empty);
+        let license_text = metadata.get("License").and_then(non_empty);
+        let classifiers = metadata
+            .get_all("Classifier")
+            .filter(|it| it.starts_with(LICENSE_CLASSIFIER_PREFIX))
+            .filter_map(|it| it.rsplit("::").next())
+            .map(|it| it.trim().to_string())
+            .filter(|it| it != "OSI Approved")
+            .collect::<Vec<_>>();
+
+        let license = expression
+            .clone()
+            .or_else(|| license_text.clone().filter(|it| !it.contains('\n')))
+            .or_else(|| (!classifiers.is_empty()).then(|| classifiers.join(", ")))
+            .unwrap_or_else(|| UNKNOWN_LICENSE.to_string());
+
+        DistributionLicense {
+            name: distribution.name().to_string(),
+            version: distribution.version().to_string(),
+            license,
+            expression,
+            license_text,
+            classifiers,
+        }
+    }
+
+    /// Get the `LicenseExpression` of the distribution's SPDX license identifiers.
+    ///
+    /// The License-Expression is preferred, then a License field that's an expression of
+    /// recognized SPDX identifiers (free-form values like "GPLv3" aren't), then the known
+    /// license classifiers (any of which can be chosen). Returns `None` if the license is
+    /// unknown.
+    #[must_use]
+    pub fn spdx_expression(&self) -> Option<LicenseExpression> {
+        if let Some(it) = self
+            .expression
+            .as_deref()
+            .and_then(|it| LicenseExpression::from_str(it).ok())
+        {
+            return Some(it);
+        }
+
+        if let Some(it) = self
+            .license_text
+            .as_deref()
+            .and_then(|it| LicenseExpression::from_str(it).ok())
+            .filter(LicenseExpression::is_recognized)
+        {
+            return Some(it);
+        }
+
+        let mut licenses = self
+            .classifiers
+            .iter()
+            .filter_map(|classifier| {
+                CLASSIFIER_LICENSES
+                    .iter()
+                    .find(|(it, _)| it == classifier)
+                    .map(|(_, id)| LicenseExpression::License((*id).to_string()))
+            })
+            .collect::<Vec<_>>();
+
+        match licenses.len() {
+            0 => None,
+            1 => licenses.pop(),
+            _ => Some(LicenseExpression::Or(licenses)),
+        }
+    }
+}
+
+/// A parsed SPDX license expression (like "MIT OR Apache-2.0").
+///
+/// See <https://spdx.github.io/spdx-spec/v2.3/SPDX-license-expressions/>. License
+/// exceptions (WITH) are ignored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LicenseExpression {
+    /// A license identifier.
+    License(String),
+    /// Licenses that all apply.
+    And(Vec<LicenseExpression>),
+    /// Licenses that can be chosen from.
+    Or(Vec<LicenseExpression>),
+}
+
+impl LicenseExpression {
+    /// Check if the expression is satisfied by the licenses `f` accepts.
+    pub fn is_satisfied_by<F: Fn(&str) -> bool + Copy>(&self, f: F) -> bool {
+        match self {
+            LicenseExpression::License(it) => f(it),
+            LicenseExpression::And(it) => it.iter().all(|it| it.is_satisfied_by(f)),
+            LicenseExpression::Or(it) => it.iter().any(|it| it.is_satisfied_by(f)),
+        }
+    }
+
+    /// Check if every license identifier in the expression is in `SPDX_LICENSE_IDS`.
+    fn is_recognized(&self) -> bool {
+        match self {
+            LicenseExpression::License(id) => SPDX_LICENSE_IDS
+                .iter()
+                .any(|it| it.eq_ignore_ascii_case(id.trim_end_matches('+'))),
+            LicenseExpression::And(it) | LicenseExpression::Or(it) => {
+                it.iter().all(LicenseExpression::is_recognized)
+            }
+        }
+    }
+}
+
+impl FromStr for LicenseExpression {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let spaced = s.replace('(', " ( ").replace(')', " ) ");
+        let tokens = spaced.split_whitespace().collect::<Vec<_>>();
+        let mut position = 0;
+        let expression = parse_or(&tokens, &mut position)
+            .filter(|_| position == tokens.len())
+            .ok_or_else(|| Error::InvalidLicenseExpression(s.to_string()))?;
+
+        Ok(expression)
+    }
+}
+
+fn parse_or(tokens: &[&str], position: &mut usize) -> Option<LicenseExpression> {
+    let mut expressions = vec![parse_and(tokens, position)?];
+    while tokens
+        .get(*position)
+        .is_some_and(|it| it.eq_ignore_ascii_case("OR"))
+    {
+        *position += 1;
+        expressions.push(parse_and(tokens, position)?);
+    }
+
+    Some(match expressions.len() {
+        1 => expressions.remove(0),
+        _ => LicenseExpression::Or(expressions),
+    })
+}
+
+fn parse_and(tokens: &[&str], position: &mut usize) -> Option<LicenseExpression> {
+    let mut expressions = vec![parse_license(tokens, position)?];
+    while tokens
+        .get(*position)
+        .is_some_and(|it| it.eq_ignore_ascii_case("AND"))
+    {
+        *position += 1;
+        expressions.push(parse_license(tokens, position)?);
+    }
+
+    Some(match expressions.len() {
+        1 => expressions.remove(0),
+        _ => LicenseExpression::And(expressions),
+    })
+}
+
+fn parse_license(tokens: &[&str], position: &mut usize) -> Option<LicenseExpression> {
+    let token = *tokens.get(*position)?;
+    *position += 1;
+
+    if token == "(" {
+        let expression = parse_or(tokens, position)?;
+        if tokens.get(*position) != Some(&")") {
+            return None;
+        }
+        *position += 1;
+        return Some(expression);
+    }
+
+    if !is_license_id(token) {
+        return None;
+    }
+
+    // Exceptions don't change which licenses apply.
+    if tokens
+        .get(*position)
+        .is_some_and(|it| it.eq_ignore_ascii_case("WITH"))
+    {
+        if !tokens
+            .get(*position + 1)
+            .is_some_and(|it| is_license_id(it))
+        {
+            return None;
+        }
+        *position += 2;
+    }
+
+    Some(LicenseExpression::License(token.to_string()))
+}
+
+/// Check if a token is a license identifier (idstring in the SPDX grammar).
+fn is_license_id(token: &str) -> bool {
+    !["AND", "OR", "WITH", "(", ")"]
+        .iter()
+        .any(|it| token.eq_ignore_ascii_case(it))
+        && token
+            .trim_end_matches('+')
+            .chars()
+            .all(|it| it.is_ascii_alphanumeric() || it == '-' || it == '.')
+        && !token.trim_end_matches('+').is_empty()
+}
+
+/// A policy of allowed and denied SPDX license identifiers.
+///
+/// A license is acceptable if it isn't denied and, if any licenses are allowed, it's
+/// allowed. Identifiers are compared case-insensitively.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LicensePolicy {
+    /// The licenses that are allowed. Any license that isn't denied is allowed if this is
+    /// empty.
+    pub allow: Vec<String>,
+    /// The licenses that are denied.
+    pub deny: Vec<String>,
+}
+
+impl LicensePolicy {
+    /// Get the violation of the `LicensePolicy` by a `DistributionLicense` (like
+    /// "mock-package (GPL-3.0-only)") if it has one.
+    ///
+    /// Distributions with an unknown license only violate policies that allow licenses.
+    #[must_use]
+    pub fn violation(&self, license: &DistributionLicense) -> Option<String> {
+        match license.spdx_expression() {
+            Some(it) if it.is_satisfied_by(|id| self.accepts(id)) => None,
+            Some(_) => Some(format!("{} ({})", license.name, license.license)),
+            None if self.allow.is_empty() => None,
+            None => Some(format!("{} (unknown license)", license.name)),
+        }
+    }
+
+    fn accepts(&self, id: &str) -> bool {
+        let contains = |ids: &[String]| ids.iter().any(|it| it.eq_ignore_ascii_case(id));
+        !contains(&self.deny) && (self.allow.is_empty() || contains(&self.allow))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn mock_license(metadata: &str) -> DistributionLicense {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("mock_package-0.1.0.dist-info");
+        std::fs::create_dir_all(&path).unwrap();
+        std::fs::write(
+            path.join("METADATA"),
+            format!("Metadata-Version: 2.4\nName: mock-package\nVersion: 0.1.0\n{metadata}"),
+        )
+        .unwrap();
+
+        DistributionLicense::from_distribution(&InstalledDistribution::from_path(path).unwrap())
+    }
+
+    #[test]
+    fn test_license_expression() {
+        let license = |it: &str| LicenseExpression::License(it.to_string());
+
+        assert_eq!(
+            LicenseExpression::from_str("MIT OR (Apache-2.0 AND BSD-3-Clause)").unwrap(),
+            LicenseExpression::Or(vec![
+                license("MIT"),
+                LicenseExpression::And(vec![license("Apache-2.0"), license("BSD-3-Clause")])
+            ])
+        );
+        assert_eq!(
+            LicenseExpression::from_str("GPL-2.0-or-later WITH Classpath-exception-2.0").unwrap(),
+            license("GPL-2.0-or-later")
+        );
+        assert!(LicenseExpression::from_str("Apache 2.0").is_err());
+        assert!(LicenseExpression::from_str("(MIT").is_err());
+        assert!(LicenseExpression::from_str("MIT OR").is_err());
+    }
+
+    #[test]
+    fn test_distribution_license() {
+        let license = mock_license(
+            "License-Expression: MIT OR Apache-2.0\nLicense: MIT\nClassifier: License :: OSI Approved :: MIT License\n",
+        );
+        assert_eq!(license.license, "MIT OR Apache-2.0");
+        assert_eq!(license.classifiers, ["MIT License"]);
+
+        let license = mock_license(
+            "License: Copyright (c) Mock\n  Permission is hereby granted\nClassifier: License :: OSI Approved :: BSD License\nClassifier: License :: OSI Approved :: MIT License\n",
+        );
+        assert_eq!(license.license, "BSD License, MIT License");
+        assert_eq!(
+            license.spdx_expression(),
+            Some(LicenseExpression::License("MIT".to_string()))
+        );
+
+        // Free-form License fields aren't SPDX identifiers even if they parse as one.
+        let license = mock_license(
+            "License: GPLv3\nClassifier: License :: OSI Approved :: GNU General Public License v3 (GPLv3)\n",
+        );
+        assert_eq!(
+            license.spdx_expression(),
+            Some(LicenseExpression::License("GPL-3.0-only".to_string()))
+        );
+        assert_eq!(mock_license("License: BSD\n").spdx_expression(), None);
+        assert_eq!(
+            mock_license("License: mit OR Apache-2.0\n").spdx_expression(),
+            Some(LicenseExpression::Or(vec![
+                LicenseExpression::License("mit".to_string()),
+                LicenseExpression::License("Apache-2.0".to_string())
+            ]))
+        );
+
+        let license = mock_license("License: UNKNOWN\n");
+        assert_eq!(license.license, "UNKNOWN");
+        assert_eq!(license.spdx_expression(), None);
+    }
+
+    #[test]
+    fn test_license_policy() {
+        let policy = LicensePolicy {
+            allow: vec!["mit".to_string(), "Apache-2.0".to_string()],
+            deny: vec!["GPL-3.0-only".to_string()],
+        };
+
+        assert_eq!(
+            policy.violation(&mock_license("License-Expression: MIT OR GPL-3.0-only\n")),
+            None
+        );
+        assert_eq!(
+            policy.violation(&mock_license("License-Expression: MIT AND GPL-3.0-only\n")),
+            Some("mock-package (MIT AND GPL-3.0-only)".to_string())
+        );
+        assert_eq!(
+            policy.violation(&mock_license(
+                "Classifier: License :: OSI Approved :: Apache Software License\n"
+            )),
+            None
+        );
+        assert_eq!(
+            policy.violation(&mock_license("")),
+            Some("mock-package (unknown license)".to_string())
+        );
+        let deny = LicensePolicy {
+            allow: Vec::new(),
+            deny: vec!["GPL-3.0-only".to_string()],
+        };
+        assert_eq!(deny.violation(&mock_license("")), None);
+        assert_eq!(
+            deny.violation(&mock_license(
+                "License: GPLv3\nClassifier: License :: OSI Approved :: GNU General Public License v3 (GPLv3)\n"
+            )),
+            Some("mock-package (GPLv3)".to_string())
+        );
+    }
+}
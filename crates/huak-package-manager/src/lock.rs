@@ -0,0 +1,1239 @@
+use crate::{
+    dependency::{expand_root_uri, root_relative_url},
+    DistributionFile, Error, HuakResult, InstalledDistribution, PackageSources, Resolution,
+    Resolver,
+};
+use pep440_rs::Version;
+use pep508_rs::{MarkerExpression, MarkerTree, MarkerValue, Requirement};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+const DEFAULT_LOCK_FILE_NAME: &str = "huak.lock";
+const LOCK_FILE_VERSION: u32 = 1;
+const LOCK_FILE_HEADER: &str = "# This file is automatically generated by Huak.
+# It is not intended for manual editing.
+";
+pub(crate) const DEFAULT_PACKAGE_INDEX_URL: &str = "https://pypi.org/simple";
+/// The name of the group containing the project's required dependencies.
+pub(crate) const REQUIRED_GROUP_NAME: &str = "required";
+
+/// The `Lockfile` records every distribution resolved for a project (huak.lock).
+///
+/// Each `LockedPackage` contains its version, the index (or URL) it was resolved from, every
+/// file released for the version with its hashes (or the hashes of a direct reference's
+/// artifact), and any PEP 508 markers required to install it.
+///
+/// The `content-hash` is a hash of the manifest's requirements and `requires-python` used to
+/// tell if the lock file is up to date with the manifest.
+///
+/// ```toml
+/// version = 1
+/// content-hash = "sha256:2c8a5b0e4d9a0f6c1e3b7d5f9a8c6e4b2d0f1a3c5e7b9d8f6a4c2e0b1d3f5a7c"
+///
+/// [[package]]
+/// name = "click"
+/// version = "8.1.7"
+/// index = "https://pypi.org/simple"
+/// groups = ["required"]
+/// dependencies = ["colorama"]
+///
+/// [[package.file]]
+/// name = "click-8.1.7-py3-none-any.whl"
+/// url = "https://files.pythonhosted.org/packages/00/2e/d53fa4befbf2cfa713304affc7ca780ce4fc1fd8710527771b58311a3229/click-8.1.7-py3-none-any.whl"
+/// hashes = ["sha256:ae74fb96c20a0277a1d615f1e4d73c8414f5a98db8b799a7931d1582f3390c28"]
+///
+/// [[package.file]]
+/// name = "click-8.1.7.tar.gz"
+/// url = "https://files.pythonhosted.org/packages/96/d3/f04c7bfcf5c1862a2a5b845c6b2b360488cf47af55dfa79c98f6a6bf98b5/click-8.1.7.tar.gz"
+/// hashes = ["sha256:ca9853ad459e787e2192211578cc907e7594e294c7ccc834310722b41b9ca6de"]
+///
+/// [[package]]
+/// name = "colorama"
+/// version = "0.4.6"
+/// index = "https://pypi.org/simple"
+/// markers = "platform_system == \"Windows\""
+/// groups = ["required"]
+///
+/// [[package.file]]
+/// name = "colorama-0.4.6-py2.py3-none-any.whl"
+/// url = "https://files.pythonhosted.org/packages/d1/d6/3965ed04c63042e047cb6a3e6ed1a63a35087b6a609aa3a15ed8ac56c221/colorama-0.4.6-py2.py3-none-any.whl"
+/// hashes = ["sha256:4f1d9991f5acc0ca119f9d443620b77f9d6b33703e51011c16baf57afb285fc6"]
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lockfile {
+    /// The version of the lock file format.
+    version: u32,
+    /// The hash of the manifest's requirements the lock file was resolved for.
+    #[serde(
+        default,
+        rename = "content-hash",
+        skip_serializing_if = "Option::is_none"
+    )]
+    content_hash: Option<String>,
+    /// The locked distributions.
+    #[serde(default, rename = "package")]
+    packages: Vec<LockedPackage>,
+}
+
+impl Lockfile {
+    /// Initialize a `Lockfile` from `LockedPackage`s. Packages are sorted by name.
+    #[must_use]
+    pub fn new(mut packages: Vec<LockedPackage>) -> Self {
+        packages.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Self {
+            version: LOCK_FILE_VERSION,
+            content_hash: None,
+            packages,
+        }
+    }
+
+    /// Set the hash of the manifest's requirements the `Lockfile` is resolved for (see
+    /// `manifest_content_hash`).
+    #[must_use]
+    pub fn with_content_hash(mut self, content_hash: String) -> Self {
+        self.content_hash = Some(content_hash);
+        self
+    }
+
+    /// Lock every file released for each version resolved from an index (wheels for any
+    /// platform and source distributions) so that the `Lockfile` can be installed with hashes
+    /// on other platforms and Python versions.
+    ///
+    /// Hashes from the resolution that don't belong to any of the index's files are kept in
+    /// the package's `hashes`.
+    pub(crate) fn with_release_files(mut self, resolver: &Resolver) -> HuakResult<Self> {
+        for pkg in self.packages.iter_mut().filter(|it| it.index.is_some()) {
+            let Ok(version) = Version::from_str(&pkg.version) else {
+                continue;
+            };
+            let files = resolver
+                .files(&pkg.name, &version)?
+                .into_iter()
+                .map(|file| LockedFile {
+                    hashes: file.hash_strings(),
+                    name: file.filename,
+                    url: file.url.to_string(),
+                })
+                .collect::<Vec<_>>();
+            pkg.hashes
+                .retain(|hash| !files.iter().any(|file| file.hashes.contains(hash)));
+            pkg.files = files;
+        }
+
+        Ok(self)
+    }
+
+    /// Read a `Lockfile` from a huak.lock file.
+    ///
+    /// `{root:uri}` in direct references is expanded to the lock file's directory.
+    pub fn read<T: AsRef<Path>>(path: T) -> HuakResult<Self> {
+        let path = path.as_ref();
+        let mut lockfile = Self::from_str(&std::fs::read_to_string(path)?)?;
+
+        if let Some(root) = path.parent() {
+            for url in lockfile
+                .packages
+                .iter_mut()
+                .filter_map(|it| it.url.as_mut())
+            {
+                *url = expand_root_uri(url, root);
+            }
+        }
+
+        Ok(lockfile)
+    }
+
+    /// Write the `Lockfile` to a huak.lock file.
+    ///
+    /// Direct references to local paths are written relative to the lock file's directory
+    /// (`{root:uri}/libs/my-lib`).
+    pub fn write<T: AsRef<Path>>(&self, path: T) -> HuakResult<()> {
+        let path = path.as_ref();
+        let mut lockfile = self.clone();
+
+        if let Some(root) = path.parent() {
+            for url in lockfile
+                .packages
+                .iter_mut()
+                .filter_map(|it| it.url.as_mut())
+            {
+                if let Some(it) = url::Url::parse(url)
+                    .ok()
+                    .and_then(|it| root_relative_url(&it, root))
+                {
+                    *url = it;
+                }
+            }
+        }
+
+        Ok(std::fs::write(path, lockfile.to_toml_string()?)?)
+    }
+
+    /// Get a reference to the version of the lock file format.
+    #[must_use]
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Get the hash of the manifest's requirements the `Lockfile` is resolved for.
+    #[must_use]
+    pub fn content_hash(&self) -> Option<&str> {
+        self.content_hash.as_deref()
+    }
+
+    /// Get a reference to the `LockedPackage`s.
+    #[must_use]
+    pub fn packages(&self) -> &[LockedPackage] {
+        &self.packages
+    }
+
+    /// Get a `LockedPackage` by its name.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&LockedPackage> {
+        let name = normalized_name(name);
+        self.packages.iter().find(|it| it.name == name)
+    }
+
+    /// Get the `LockedPackage`s for `names` along with all of their locked dependencies.
+    ///
+    /// `None` is returned if any of the `names` aren't locked.
+    #[must_use]
+    pub fn packages_with_dependencies(&self, names: &[&str]) -> Option<Vec<&LockedPackage>> {
+        let mut packages: Vec<&LockedPackage> = Vec::new();
+        let mut stack = Vec::new();
+        for name in names {
+            stack.push(self.get(name)?);
+        }
+
+        while let Some(pkg) = stack.pop() {
+            if packages.iter().any(|it| it.name == pkg.name) {
+                continue;
+            }
+
+            stack.extend(pkg.dependencies.iter().filter_map(|it| self.get(it)));
+            packages.push(pkg);
+        }
+
+        packages.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Some(packages)
+    }
+
+    /// Get an `Iterator` over the `LockedPackage`s belonging to any of the `groups`.
+    pub fn packages_in_groups<'a>(
+        &'a self,
+        groups: &'a [String],
+    ) -> impl Iterator<Item = &'a LockedPackage> + 'a {
+        self.packages
+            .iter()
+            .filter(|pkg| pkg.groups.iter().any(|g| groups.contains(g)))
+    }
+
+    fn to_toml_string(&self) -> HuakResult<String> {
+        Ok(format!("{LOCK_FILE_HEADER}{}", toml::to_string(self)?))
+    }
+}
+
+impl FromStr for Lockfile {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lockfile: Lockfile = toml::from_str(s)?;
+
+        if lockfile.version != LOCK_FILE_VERSION {
+            return Err(Error::LockFileVersionNotSupported(lockfile.version));
+        }
+
+        Ok(lockfile)
+    }
+}
+
+/// A distribution recorded in the `Lockfile`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedPackage {
+    /// The normalized name of the distribution.
+    pub name: String,
+    /// The PEP 440 version of the distribution.
+    pub version: String,
+    /// The package index the distribution was resolved from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<String>,
+    /// The URL of the distribution if it was resolved from a direct reference.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Hashes of a direct reference's artifact (or of an artifact that isn't one of the
+    /// `files`) formatted as "<algorithm>:<digest>".
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hashes: Vec<String>,
+    /// PEP 508 environment markers required for the distribution to be installed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub markers: Option<String>,
+    /// The dependency groups the distribution is resolved for.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<String>,
+    /// The normalized names of the distribution's dependencies.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<String>,
+    /// The files released for the distribution's version on its package index.
+    #[serde(default, rename = "file", skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<LockedFile>,
+}
+
+/// A file released for a `LockedPackage`'s version.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedFile {
+    /// The name of the file.
+    pub name: String,
+    /// The URL of the file.
+    pub url: String,
+    /// Hashes of the file formatted as "<algorithm>:<digest>".
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hashes: Vec<String>,
+}
+
+impl LockedPackage {
+    /// Get the hashes of every artifact the `LockedPackage` can be installed from.
+    #[must_use]
+    pub fn artifact_hashes(&self) -> Vec<String> {
+        self.files
+            .iter()
+            .flat_map(|it| it.hashes.iter())
+            .chain(self.hashes.iter())
+            .cloned()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Get the requirements.txt entry for the `LockedPackage`.
+    ///
+    /// Direct references are pinned with their URL. Anything else is pinned to its
+    /// version.
+    #[must_use]
+    pub fn requirement_line(&self, include_hashes: bool) -> String {
+        let mut line = match self.url.as_ref() {
+            Some(url) => format!("{} @ {url}", self.name),
+            None => format!("{}=={}", self.name, self.version),
+        };
+
+        if let Some(markers) = self.markers.as_ref() {
+            line.push_str(" ; ");
+            line.push_str(markers);
+        }
+
+        if include_hashes {
+            for hash in &self.artifact_hashes() {
+                line.push_str(" --hash=");
+                line.push_str(hash);
+            }
+        }
+
+        line
+    }
+}
+
+/// The JSON installation report generated by pip (`pip install --report`).
+///
+/// See <https://pip.pypa.io/en/stable/reference/installation-report/>.
+#[derive(Debug, Deserialize)]
+pub struct InstallationReport {
+    #[serde(default)]
+    install: Vec<InstallationReportItem>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct InstallationReportItem {
+    download_info: DownloadInfo,
+    #[serde(default)]
+    is_direct: bool,
+    metadata: ReportMetadata,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct DownloadInfo {
+    url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    archive_info: Option<ArchiveInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    vcs_info: Option<VcsInfo>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ArchiveInfo {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hashes: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct VcsInfo {
+    vcs: String,
+    commit_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReportMetadata {
+    name: String,
+    version: String,
+    #[serde(default)]
+    requires_dist: Vec<String>,
+}
+
+impl InstallationReportItem {
+    pub(crate) fn name(&self) -> &str {
+        &self.metadata.name
+    }
+
+    pub(crate) fn version(&self) -> &str {
+        &self.metadata.version
+    }
+
+    pub(crate) fn is_direct(&self) -> bool {
+        self.is_direct
+    }
+
+    /// Get a requirement string pinning the item to its version or direct reference.
+    pub(crate) fn requirement(&self) -> String {
+        match self.url() {
+            Some(url) => format!("{} @ {url}", self.metadata.name),
+            None => format!("{}=={}", self.metadata.name, self.metadata.version),
+        }
+    }
+
+    /// Get a requirement string pinning the item to the file it was resolved to. Files
+    /// resolved from an index are referenced by URL with their sha256 hash so that they
+    /// aren't resolved again from other indexes.
+    pub(crate) fn file_requirement(&self) -> String {
+        let Some(mut file) = self.distribution_file().filter(|_| !self.is_direct) else {
+            return self.requirement();
+        };
+        if let Some(digest) = file.hashes.get("sha256") {
+            file.url.set_fragment(Some(&format!("sha256={digest}")));
+        }
+
+        format!("{} @ {}", self.metadata.name, file.url)
+    }
+
+    /// Get the item's wheel `DistributionFile` with the hashes from the report. Returns
+    /// `None` if the item isn't a wheel.
+    pub(crate) fn wheel_file(&self) -> Option<DistributionFile> {
+        self.distribution_file().filter(DistributionFile::is_wheel)
+    }
+
+    /// Get the item's `DistributionFile` with the hashes from the report. Returns `None`
+    /// for VCS references.
+    pub(crate) fn distribution_file(&self) -> Option<DistributionFile> {
+        if self.download_info.vcs_info.is_some() {
+            return None;
+        }
+
+        let mut file = DistributionFile::from_url(&self.download_info.url).ok()?;
+        for hash in self.hashes() {
+            if let Some((algorithm, digest)) = hash.split_once(':') {
+                file.hashes
+                    .insert(algorithm.to_string(), digest.to_string());
+            }
+        }
+
+        Some(file)
+    }
+
+    /// Get the `direct_url.json` contents for a direct reference.
+    ///
+    /// See <https://packaging.python.org/en/latest/specifications/direct-url/>.
+    pub(crate) fn direct_url_json(&self) -> Option<String> {
+        if !self.is_direct {
+            return None;
+        }
+
+        serde_json::to_string(&self.download_info).ok()
+    }
+
+    fn hashes(&self) -> Vec<String> {
+        let Some(info) = self.download_info.archive_info.as_ref() else {
+            return Vec::new();
+        };
+
+        if let Some(hashes) = info.hashes.as_ref() {
+            return hashes.iter().map(|(k, v)| format!("{k}:{v}")).collect();
+        }
+
+        // Older reports only include a single "<algorithm>=<digest>" hash.
+        info.hash
+            .as_ref()
+            .map(|it| vec![it.replacen('=', ":", 1)])
+            .unwrap_or_default()
+    }
+
+    fn url(&self) -> Option<String> {
+        if !self.is_direct {
+            return None;
+        }
+
+        match self.download_info.vcs_info.as_ref() {
+            Some(vcs) => Some(format!(
+                "{}+{}@{}",
+                vcs.vcs, self.download_info.url, vcs.commit_id
+            )),
+            None => Some(self.download_info.url.clone()),
+        }
+    }
+}
+
+impl InstallationReport {
+    /// Get the distributions to install.
+    pub(crate) fn items(&self) -> &[InstallationReportItem] {
+        &self.install
+    }
+
+    /// Check that every distribution with hashes in `hashes` (by normalized name) matches
+    /// one of them. Distributions without any hashes in the report can't be checked.
+    pub(crate) fn verify_hashes(&self, hashes: &HashMap<String, Vec<String>>) -> HuakResult<()> {
+        for item in &self.install {
+            let Some(expected) = hashes
+                .get(&normalized_name(item.name()))
+                .filter(|it| !it.is_empty())
+            else {
+                continue;
+            };
+            let actual = item.hashes();
+
+            if !actual.is_empty() && !actual.iter().any(|it| expected.contains(it)) {
+                return Err(Error::InvalidChecksum(format!(
+                    "{} {} doesn't match its locked hashes",
+                    item.name(),
+                    item.version()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Build an `InstallationReport` from the packages of a `Resolution`. Packages that
+    /// weren't found on an index are direct references.
+    #[must_use]
+    pub fn from_resolution(resolution: &Resolution) -> InstallationReport {
+        let install = resolution
+            .packages()
+            .map(|it| {
+                let hashes = &it.distribution.hashes;
+
+                InstallationReportItem {
+                    download_info: DownloadInfo {
+                        url: it.distribution.url.to_string(),
+                        archive_info: Some(ArchiveInfo {
+                            hash: None,
+                            hashes: (!hashes.is_empty()).then(|| hashes.clone()),
+                        }),
+                        vcs_info: None,
+                    },
+                    is_direct: it.index.is_none(),
+                    metadata: ReportMetadata {
+                        name: it.name.clone(),
+                        version: it.version.to_string(),
+                        requires_dist: it
+                            .dependencies
+                            .iter()
+                            .map(|dep| dep.requirement.to_string())
+                            .collect(),
+                    },
+                }
+            })
+            .collect();
+
+        InstallationReport { install }
+    }
+
+    /// Build an `InstallationReport` from `InstalledDistribution`s as if they were installed
+    /// together. Direct references are read from each distribution's `direct_url.json`.
+    ///
+    /// See <https://packaging.python.org/en/latest/specifications/direct-url/>.
+    #[must_use]
+    pub fn from_distributions(distributions: &[InstalledDistribution]) -> InstallationReport {
+        let install = distributions
+            .iter()
+            .map(|it| {
+                let direct_url = std::fs::read_to_string(it.path().join("direct_url.json"))
+                    .ok()
+                    .and_then(|it| serde_json::from_str::<DownloadInfo>(&it).ok());
+
+                InstallationReportItem {
+                    is_direct: direct_url.is_some(),
+                    download_info: direct_url.unwrap_or(DownloadInfo {
+                        url: String::new(),
+                        archive_info: None,
+                        vcs_info: None,
+                    }),
+                    metadata: ReportMetadata {
+                        name: it.name().to_string(),
+                        version: it.version().to_string(),
+                        requires_dist: it.requires_dist().iter().map(ToString::to_string).collect(),
+                    },
+                }
+            })
+            .collect();
+
+        InstallationReport { install }
+    }
+}
+
+impl FromStr for InstallationReport {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(serde_json::from_str(s)?)
+    }
+}
+
+/// Build a `Lockfile` from an `InstallationReport`.
+///
+/// Every distribution in the report that is reachable from the `groups` roots is locked.
+/// Each root group maps a group name to the requirements listed for it in the manifest.
+/// Markers are propagated from the roots to the transitive dependencies so that
+/// distributions only required on some platforms keep their markers.
+pub fn lockfile_from_report(
+    report: &InstallationReport,
+    groups: &BTreeMap<String, Vec<Requirement>>,
+    python_version: &Version,
+    sources: Option<&PackageSources>,
+) -> Lockfile {
+    let items = report
+        .install
+        .iter()
+        .map(|it| (normalized_name(&it.metadata.name), it))
+        .collect::<HashMap<_, _>>();
+    let python_versions = [python_version.clone()];
+
+    let mut packages = BTreeMap::new();
+    let mut markers: HashMap<String, Option<MarkerTree>> = HashMap::new();
+    let mut package_groups: HashMap<String, BTreeSet<String>> = HashMap::new();
+    let mut dependencies: HashMap<String, BTreeSet<String>> = HashMap::new();
+
+    for (group, requirements) in groups {
+        // Walk the requirements graph from the group's roots. Each entry on the stack
+        // contains the name of the distribution, the extras requested, and the markers
+        // accumulated along the path.
+        let mut stack = requirements
+            .iter()
+            .map(|req| {
+                (
+                    normalized_name(&req.name),
+                    req.extras.clone().unwrap_or_default(),
+                    req.marker.as_ref().and_then(without_extras),
+                )
+            })
+            .collect::<Vec<_>>();
+        let mut visited = HashSet::new();
+
+        while let Some((name, extras, marker)) = stack.pop() {
+            let Some(item) = items.get(&name) else {
+                continue;
+            };
+
+            let combined = match markers.get(&name) {
+                Some(current) => or_markers(current.clone(), marker.clone()),
+                None => marker.clone(),
+            };
+            markers.insert(name.clone(), combined);
+            package_groups
+                .entry(name.clone())
+                .or_default()
+                .insert(group.clone());
+            packages.entry(name.clone()).or_insert(*item);
+
+            if !visited.insert((name.clone(), extras.clone(), marker.clone())) {
+                continue;
+            }
+
+            let active_extras = extras.iter().cloned().collect::<HashSet<_>>();
+            for req in item
+                .metadata
+                .requires_dist
+                .iter()
+                .filter_map(|it| Requirement::from_str(it).ok())
+            {
+                let active = req.marker.as_ref().map_or(true, |it| {
+                    it.evaluate_extras_and_python_version(&active_extras, &python_versions)
+                });
+                let dep = normalized_name(&req.name);
+
+                if !active || !items.contains_key(&dep) {
+                    continue;
+                }
+
+                dependencies
+                    .entry(name.clone())
+                    .or_default()
+                    .insert(dep.clone());
+
+                let dep_marker =
+                    and_markers(marker.clone(), req.marker.as_ref().and_then(without_extras));
+                stack.push((dep, req.extras.clone().unwrap_or_default(), dep_marker));
+            }
+        }
+    }
+
+    let packages = packages
+        .into_iter()
+        .map(|(name, item)| LockedPackage {
+            version: item.metadata.version.clone(),
+            index: if item.is_direct {
+                None
+            } else {
+                Some(
+                    sources
+                        .and_then(|it| it.index_url(&name))
+                        .unwrap_or(DEFAULT_PACKAGE_INDEX_URL)
+                        .to_string(),
+                )
+            },
+            url: item.url(),
+            hashes: item.hashes(),
+            markers: markers.remove(&name).flatten().map(|it| it.to_string()),
+            groups: package_groups
+                .remove(&name)
+                .map(|it| it.into_iter().collect())
+                .unwrap_or_default(),
+            dependencies: dependencies
+                .remove(&name)
+                .map(|it| it.into_iter().collect())
+                .unwrap_or_default(),
+            files: Vec::new(),
+            name,
+        })
+        .collect();
+
+    Lockfile::new(packages)
+}
+
+/// Get the dependency groups (including the required group) listed in a manifest.
+///
+/// Optional dependency groups and `[dependency-groups]` are both included with any
+/// `include-group` entries expanded. Groups sharing a name are merged. `{root:uri}` is
+/// expanded to the project `root`.
+pub(crate) fn manifest_dependency_groups(
+    manifest: &huak_pyproject_toml::PyProjectToml,
+    root: &Path,
+) -> HuakResult<BTreeMap<String, Vec<Requirement>>> {
+    let mut groups = BTreeMap::new();
+
+    for (group, deps) in manifest_dependency_strings(manifest)? {
+        let reqs: &mut Vec<Requirement> = groups.entry(group).or_default();
+        for req in parse_requirements(&deps, root) {
+            if !reqs.contains(&req) {
+                reqs.push(req);
+            }
+        }
+    }
+
+    Ok(groups)
+}
+
+/// Get the requirement strings listed for each dependency group in a manifest as they're
+/// written (see `manifest_dependency_groups`).
+fn manifest_dependency_strings(
+    manifest: &huak_pyproject_toml::PyProjectToml,
+) -> HuakResult<BTreeMap<String, Vec<String>>> {
+    let mut groups = BTreeMap::new();
+
+    groups.insert(
+        REQUIRED_GROUP_NAME.to_string(),
+        manifest.project_dependencies().unwrap_or_default(),
+    );
+
+    if let Some(optional) = manifest.project_optional_dependencies() {
+        groups.extend(optional);
+    }
+
+    for group in manifest.dependency_group_names().unwrap_or_default() {
+        let deps = manifest.dependency_group(&group)?;
+        let strings: &mut Vec<String> = groups.entry(group).or_default();
+        for dep in deps {
+            if !strings.contains(&dep) {
+                strings.push(dep);
+            }
+        }
+    }
+
+    Ok(groups)
+}
+
+/// Get the hash of a manifest's dependency groups and `requires-python` formatted as
+/// "sha256:<digest>".
+///
+/// Requirements are normalized and sorted so that reordering or reformatting them doesn't
+/// change the hash. `{root:uri}` isn't expanded so the hash is the same on every machine.
+pub(crate) fn manifest_content_hash(
+    manifest: &huak_pyproject_toml::PyProjectToml,
+) -> HuakResult<String> {
+    let mut hasher = Sha256::new();
+
+    let requires_python = manifest
+        .project_table()
+        .and_then(|it| it.get("requires-python"))
+        .and_then(toml_edit::Item::as_str)
+        .unwrap_or_default();
+    hasher.update(format!("requires-python={}\n", requires_python.trim()));
+
+    for (group, reqs) in manifest_dependency_strings(manifest)? {
+        let mut reqs = reqs
+            .iter()
+            .map(|it| match Requirement::from_str(it) {
+                Ok(mut req) => {
+                    req.name = normalized_name(&req.name);
+                    req.to_string()
+                }
+                Err(_) => it.trim().to_string(),
+            })
+            .collect::<Vec<_>>();
+        reqs.sort();
+        reqs.dedup();
+
+        hasher.update(format!("[{group}]\n"));
+        for req in reqs {
+            hasher.update(format!("{req}\n"));
+        }
+    }
+
+    Ok(format!("sha256:{}", hex::encode(hasher.finalize())))
+}
+
+/// Get the package index URL from pip-style trailing arguments if one is provided.
+pub(crate) fn index_url_from_args(values: Option<&Vec<String>>) -> Option<&str> {
+    let values = values?;
+    let mut iter = values.iter();
+
+    while let Some(it) = iter.next() {
+        if it == "--index-url" || it == "-i" {
+            return iter.next().map(String::as_str);
+        }
+        if let Some(url) = it.strip_prefix("--index-url=") {
+            return Some(url);
+        }
+    }
+
+    None
+}
+
+/// Get the path to the lock file for a workspace root.
+pub(crate) fn lock_file_path<T: AsRef<Path>>(root: T) -> PathBuf {
+    root.as_ref().join(DEFAULT_LOCK_FILE_NAME)
+}
+
+/// Parse requirement strings listed in a manifest, expanding `{root:uri}` to the project
+/// `root`. Strings that aren't valid PEP 508 requirements are skipped.
+pub(crate) fn parse_requirements(deps: &[String], root: &Path) -> Vec<Requirement> {
+    deps.iter()
+        .filter_map(|it| Requirement::from_str(&expand_root_uri(it, root)).ok())
+        .collect()
+}
+
+/// Normalize a distribution name (see <https://peps.python.org/pep-0503/#normalized-names>).
+pub(crate) fn normalized_name(name: &str) -> String {
+    huak_pyproject_toml::normalize_name(name)
+}
+
+/// Remove `extra` expressions from markers. `None` is returned if what's left always applies.
+fn without_extras(marker: &MarkerTree) -> Option<MarkerTree> {
+    match marker {
+        MarkerTree::Expression(MarkerExpression {
+            l_value, r_value, ..
+        }) => {
+            if matches!(l_value, MarkerValue::Extra) || matches!(r_value, MarkerValue::Extra) {
+                None
+            } else {
+                Some(marker.clone())
+            }
+        }
+        MarkerTree::And(trees) => {
+            let trees = trees.iter().filter_map(without_extras).collect::<Vec<_>>();
+            match trees.len() {
+                0 => None,
+                1 => trees.into_iter().next(),
+                _ => Some(MarkerTree::And(trees)),
+            }
+        }
+        MarkerTree::Or(trees) => {
+            let mut or = Vec::new();
+            for tree in trees {
+                // If any branch always applies then the entire tree always applies.
+                or.push(without_extras(tree)?);
+            }
+            Some(MarkerTree::Or(or))
+        }
+    }
+}
+
+fn and_markers(a: Option<MarkerTree>, b: Option<MarkerTree>) -> Option<MarkerTree> {
+    match (a, b) {
+        (None, it) | (it, None) => it,
+        (Some(a), Some(b)) if a == b => Some(a),
+        (Some(MarkerTree::And(mut a)), Some(b)) => {
+            if !a.contains(&b) {
+                a.push(b);
+            }
+            Some(MarkerTree::And(a))
+        }
+        (Some(a), Some(b)) => Some(MarkerTree::And(vec![a, b])),
+    }
+}
+
+/// Combine markers for a distribution required from more than one path. `None` means
+/// the distribution is always required.
+fn or_markers(a: Option<MarkerTree>, b: Option<MarkerTree>) -> Option<MarkerTree> {
+    match (a, b) {
+        (None, _) | (_, None) => None,
+        (Some(a), Some(b)) if a == b => Some(a),
+        (Some(MarkerTree::Or(mut a)), Some(b)) => {
+            if !a.contains(&b) {
+                a.push(b);
+            }
+            Some(MarkerTree::Or(a))
+        }
+        (Some(a), Some(b)) => Some(MarkerTree::Or(vec![a, b])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn mock_report() -> InstallationReport {
+        InstallationReport::from_str(
+            r#"{
+  "version": "1",
+  "install": [
+    {
+      "download_info": {
+        "url": "https://files.pythonhosted.org/packages/click-8.1.7-py3-none-any.whl",
+        "archive_info": {"hash": "sha256=abc", "hashes": {"sha256": "abc"}}
+      },
+      "is_direct": false,
+      "requested": true,
+      "metadata": {
+        "name": "click",
+        "version": "8.1.7",
+        "requires_dist": ["colorama ; platform_system == \"Windows\""]
+      }
+    },
+    {
+      "download_info": {
+        "url": "https://files.pythonhosted.org/packages/colorama-0.4.6-py2.py3-none-any.whl",
+        "archive_info": {"hash": "sha256=def"}
+      },
+      "is_direct": false,
+      "requested": false,
+      "metadata": {"name": "colorama", "version": "0.4.6"}
+    },
+    {
+      "download_info": {
+        "url": "https://github.com/pytest-dev/pytest",
+        "vcs_info": {"vcs": "git", "commit_id": "0123abc"}
+      },
+      "is_direct": true,
+      "requested": true,
+      "metadata": {
+        "name": "PyTest",
+        "version": "7.4.3",
+        "requires_dist": ["iniconfig", "pygments ; extra == \"testing\""]
+      }
+    },
+    {
+      "download_info": {
+        "url": "https://files.pythonhosted.org/packages/iniconfig-2.0.0-py3-none-any.whl",
+        "archive_info": {"hashes": {"sha256": "123"}}
+      },
+      "is_direct": false,
+      "requested": false,
+      "metadata": {"name": "iniconfig", "version": "2.0.0"}
+    }
+  ]
+}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_lockfile_from_report() {
+        let mut groups = BTreeMap::new();
+        groups.insert(
+            REQUIRED_GROUP_NAME.to_string(),
+            vec![Requirement::from_str("click").unwrap()],
+        );
+        groups.insert(
+            "dev".to_string(),
+            vec![Requirement::from_str("pytest ; python_version >= \"3.8\"").unwrap()],
+        );
+        let lockfile = lockfile_from_report(
+            &mock_report(),
+            &groups,
+            &Version::from_str("3.11.0").unwrap(),
+            Some(&PackageSources {
+                package_indexes: BTreeMap::from([(
+                    "colorama".to_string(),
+                    "https://example.com/simple".to_string(),
+                )]),
+                ..Default::default()
+            }),
+        );
+
+        let click = lockfile.get("click").unwrap();
+        assert_eq!(click.version, "8.1.7");
+        assert_eq!(click.hashes, vec!["sha256:abc".to_string()]);
+        assert_eq!(click.index.as_deref(), Some(DEFAULT_PACKAGE_INDEX_URL));
+        assert_eq!(click.groups, vec![REQUIRED_GROUP_NAME.to_string()]);
+        assert_eq!(click.dependencies, vec!["colorama".to_string()]);
+        assert!(click.markers.is_none());
+
+        let colorama = lockfile.get("colorama").unwrap();
+        assert_eq!(colorama.hashes, vec!["sha256:def".to_string()]);
+        assert_eq!(
+            colorama.index.as_deref(),
+            Some("https://example.com/simple")
+        );
+        assert_eq!(
+            colorama.markers.as_deref(),
+            Some("platform_system == 'Windows'")
+        );
+
+        let pytest = lockfile.get("pytest").unwrap();
+        assert_eq!(
+            pytest.url.as_deref(),
+            Some("git+https://github.com/pytest-dev/pytest@0123abc")
+        );
+        assert_eq!(pytest.groups, vec!["dev".to_string()]);
+        assert_eq!(pytest.dependencies, vec!["iniconfig".to_string()]);
+        assert_eq!(
+            lockfile.get("iniconfig").unwrap().markers.as_deref(),
+            Some("python_version >= '3.8'")
+        );
+        assert_eq!(
+            lockfile
+                .packages()
+                .iter()
+                .map(|it| it.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["click", "colorama", "iniconfig", "pytest"]
+        );
+    }
+
+    #[test]
+    fn test_lockfile_with_release_files() {
+        let dir = tempdir().unwrap();
+        let project = dir.path().join("simple").join("click");
+        std::fs::create_dir_all(&project).unwrap();
+        std::fs::write(
+            project.join("index.html"),
+            r#"<a href="click-8.1.7-py3-none-any.whl#sha256=abc">click-8.1.7-py3-none-any.whl</a>
+<a href="click-8.1.7-cp311-cp311-win_amd64.whl#sha256=bcd">click-8.1.7-cp311-cp311-win_amd64.whl</a>
+<a href="click-8.1.7.tar.gz#sha256=cde">click-8.1.7.tar.gz</a>
+<a href="click-8.1.6-py3-none-any.whl#sha256=def">click-8.1.6-py3-none-any.whl</a>"#,
+        )
+        .unwrap();
+        let url = url::Url::from_directory_path(dir.path().join("simple")).unwrap();
+        let resolver = Resolver::new(crate::ResolverOptions {
+            index_urls: vec![url.to_string()],
+            find_links: Vec::new(),
+            package_indexes: HashMap::new(),
+            credentials: crate::CredentialProvider::default(),
+            marker_environment: crate::python_environment::mock_marker_environment(),
+            prereleases: false,
+            offline: false,
+        })
+        .unwrap();
+        let mut groups = BTreeMap::new();
+        groups.insert(
+            REQUIRED_GROUP_NAME.to_string(),
+            vec![Requirement::from_str("click").unwrap()],
+        );
+
+        let lockfile = lockfile_from_report(
+            &mock_report(),
+            &groups,
+            &Version::from_str("3.11.0").unwrap(),
+            None,
+        )
+        .with_release_files(&resolver)
+        .unwrap();
+
+        let click = lockfile.get("click").unwrap();
+
+        assert!(click.hashes.is_empty());
+        assert_eq!(
+            click
+                .files
+                .iter()
+                .map(|it| (it.name.as_str(), it.hashes.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "click-8.1.7-py3-none-any.whl",
+                    vec!["sha256:abc".to_string()]
+                ),
+                (
+                    "click-8.1.7-cp311-cp311-win_amd64.whl",
+                    vec!["sha256:bcd".to_string()]
+                ),
+                ("click-8.1.7.tar.gz", vec!["sha256:cde".to_string()]),
+            ]
+        );
+        assert_eq!(
+            click.files[2].url,
+            url.join("click/click-8.1.7.tar.gz").unwrap().to_string()
+        );
+        assert_eq!(
+            click.artifact_hashes(),
+            vec!["sha256:abc", "sha256:bcd", "sha256:cde"]
+        );
+    }
+
+    #[test]
+    fn test_installation_report_verify_hashes() {
+        let report = mock_report();
+        let locked = |name: &str, hashes: &[&str]| {
+            HashMap::from([(
+                name.to_string(),
+                hashes.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            )])
+        };
+
+        assert!(report
+            .verify_hashes(&locked("click", &["sha256:abc", "sha256:bcd"]))
+            .is_ok());
+        assert!(report
+            .verify_hashes(&locked("pytest", &["sha256:abc"]))
+            .is_ok());
+        assert!(report.verify_hashes(&locked("click", &[])).is_ok());
+        assert!(matches!(
+            report.verify_hashes(&locked("colorama", &["sha256:abc"])),
+            Err(Error::InvalidChecksum(_))
+        ));
+    }
+
+    #[test]
+    fn test_lockfile_read_write() {
+        let dir = tempdir().unwrap();
+        let path = lock_file_path(dir.path());
+        let mut groups = BTreeMap::new();
+        groups.insert(
+            REQUIRED_GROUP_NAME.to_string(),
+            vec![Requirement::from_str("click").unwrap()],
+        );
+        let lockfile = lockfile_from_report(
+            &mock_report(),
+            &groups,
+            &Version::from_str("3.11.0").unwrap(),
+            Some(&PackageSources {
+                index_urls: vec!["https://example.com/simple".to_string()],
+                ..Default::default()
+            }),
+        );
+
+        lockfile.write(&path).unwrap();
+
+        assert_eq!(Lockfile::read(&path).unwrap(), lockfile);
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            r#"# This file is automatically generated by Huak.
+# It is not intended for manual editing.
+version = 1
+
+[[package]]
+name = "click"
+version = "8.1.7"
+index = "https://example.com/simple"
+hashes = ["sha256:abc"]
+groups = ["required"]
+dependencies = ["colorama"]
+
+[[package]]
+name = "colorama"
+version = "0.4.6"
+index = "https://example.com/simple"
+hashes = ["sha256:def"]
+markers = "platform_system == 'Windows'"
+groups = ["required"]
+"#
+        );
+    }
+
+    #[test]
+    fn test_lockfile_local_paths() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("libs").join("my-lib")).unwrap();
+        let url = url::Url::from_file_path(root.join("libs").join("my-lib")).unwrap();
+        let lockfile = Lockfile::new(vec![LockedPackage {
+            name: "my-lib".to_string(),
+            version: "0.1.0".to_string(),
+            index: None,
+            url: Some(url.to_string()),
+            hashes: Vec::new(),
+            markers: None,
+            groups: vec![REQUIRED_GROUP_NAME.to_string()],
+            dependencies: Vec::new(),
+            files: Vec::new(),
+        }]);
+        let path = lock_file_path(&root);
+
+        lockfile.write(&path).unwrap();
+
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("url = \"{root:uri}/libs/my-lib\""));
+        assert_eq!(Lockfile::read(&path).unwrap(), lockfile);
+    }
+
+    #[test]
+    fn test_locked_package_requirement_line() {
+        let pkg = LockedPackage {
+            name: "colorama".to_string(),
+            version: "0.4.6".to_string(),
+            index: Some(DEFAULT_PACKAGE_INDEX_URL.to_string()),
+            url: None,
+            hashes: vec!["sha256:def".to_string()],
+            markers: Some("platform_system == 'Windows'".to_string()),
+            groups: Vec::new(),
+            dependencies: Vec::new(),
+            files: Vec::new(),
+        };
+
+        assert_eq!(
+            pkg.requirement_line(true),
+            "colorama==0.4.6 ; platform_system == 'Windows' --hash=sha256:def"
+        );
+        assert_eq!(
+            pkg.requirement_line(false),
+            "colorama==0.4.6 ; platform_system == 'Windows'"
+        );
+    }
+
+    #[test]
+    fn test_installation_report_file_requirement() {
+        let report = mock_report();
+        let requirements = report
+            .items()
+            .iter()
+            .map(InstallationReportItem::file_requirement)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            requirements,
+            vec![
+                "click @ https://files.pythonhosted.org/packages/click-8.1.7-py3-none-any.whl#sha256=abc",
+                "colorama @ https://files.pythonhosted.org/packages/colorama-0.4.6-py2.py3-none-any.whl#sha256=def",
+                "PyTest @ git+https://github.com/pytest-dev/pytest@0123abc",
+                "iniconfig @ https://files.pythonhosted.org/packages/iniconfig-2.0.0-py3-none-any.whl#sha256=123",
+            ]
+        );
+    }
+}
@@ -48,6 +48,12 @@ impl LocalManifest {
         &self.manifest_data
     }
 
+    /// Get a reference to the path to the `LocalManifest` file.
+    #[must_use]
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
     /// Get a mutable reference to the manifest data.
     pub fn manifest_data_mut(&mut self) -> &mut PyProjectToml {
         &mut self.manifest_data
@@ -59,6 +65,61 @@ impl LocalManifest {
     }
 }
 
+/// A group of dependencies listed in a manifest other than `[project.dependencies]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManifestGroup {
+    /// An optional dependency group (extra) in `[project.optional-dependencies]`.
+    Optional(String),
+    /// A dependency group in `[dependency-groups]` (<https://peps.python.org/pep-0735/>).
+    /// Unlike extras, dependency groups aren't published with the project.
+    Dependency(String),
+}
+
+impl ManifestGroup {
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            ManifestGroup::Optional(it) | ManifestGroup::Dependency(it) => it,
+        }
+    }
+
+    /// Check if a dependency is listed in the group.
+    pub(crate) fn contains(&self, manifest: &PyProjectToml, dependency: &str) -> bool {
+        match self {
+            ManifestGroup::Optional(group) => {
+                manifest.contains_project_optional_dependency(dependency, group)
+            }
+            ManifestGroup::Dependency(group) => {
+                manifest.contains_dependency_group_dependency(dependency, group)
+            }
+        }
+    }
+
+    /// Add a dependency to the group, replacing any entry for the same package.
+    pub(crate) fn add(&self, manifest: &mut PyProjectToml, dependency: &str) {
+        match self {
+            ManifestGroup::Optional(group) => {
+                manifest.add_project_optional_dependency(dependency, group);
+            }
+            ManifestGroup::Dependency(group) => {
+                manifest.add_dependency_group_dependency(dependency, group);
+            }
+        }
+    }
+
+    /// Remove a dependency from the group.
+    pub(crate) fn remove(&self, manifest: &mut PyProjectToml, dependency: &str) {
+        match self {
+            ManifestGroup::Optional(group) => {
+                manifest.remove_project_optional_dependency(dependency, group);
+            }
+            ManifestGroup::Dependency(group) => {
+                manifest.remove_dependency_group_dependency(dependency, group);
+            }
+        }
+    }
+}
+
 /// Create `LocalManifest` from a pyproject.toml file.
 fn read_local_manifest<T: Into<PathBuf>>(path: T) -> HuakResult<LocalManifest> {
     let path = path.into();
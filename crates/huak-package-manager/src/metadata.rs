@@ -0,0 +1,183 @@
+use crate::Error;
+use pep440_rs::{Version, VersionSpecifiers};
+use pep508_rs::Requirement;
+use std::str::FromStr;
+
+/// The `CoreMetadata` of a Python distribution (METADATA or PKG-INFO).
+///
+/// The metadata is formatted as email headers. Fields like `Requires-Dist` can be used
+/// more than once. The description is included as the message body.
+///
+/// See <https://packaging.python.org/en/latest/specifications/core-metadata/>.
+///
+/// ```
+/// use huak_package_manager::CoreMetadata;
+/// use std::str::FromStr;
+///
+/// let metadata = CoreMetadata::from_str("Metadata-Version: 2.1\nName: click\nVersion: 8.1.7\n").unwrap();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CoreMetadata {
+    /// The header fields in the order they're listed.
+    fields: Vec<(String, String)>,
+    /// The description included as the message body.
+    body: Option<String>,
+}
+
+impl CoreMetadata {
+    /// Get the first value of a field. Field names are case-insensitive.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Get an `Iterator` over every value of a field. Field names are case-insensitive.
+    pub fn get_all<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.fields
+            .iter()
+            .filter(move |(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Get the name of the distribution.
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        self.get("Name")
+    }
+
+    /// Get the PEP 440 `Version` of the distribution.
+    #[must_use]
+    pub fn version(&self) -> Option<Version> {
+        self.get("Version")
+            .and_then(|it| Version::from_str(it).ok())
+    }
+
+    /// Get the `Requirement`s of the distribution (Requires-Dist). Invalid requirements
+    /// are skipped.
+    #[must_use]
+    pub fn requires_dist(&self) -> Vec<Requirement> {
+        self.get_all("Requires-Dist")
+            .filter_map(|it| Requirement::from_str(it).ok())
+            .collect()
+    }
+
+    /// Get the Python versions supported by the distribution (Requires-Python).
+    #[must_use]
+    pub fn requires_python(&self) -> Option<VersionSpecifiers> {
+        self.get("Requires-Python")
+            .and_then(|it| VersionSpecifiers::from_str(it).ok())
+    }
+
+    /// Get the extras provided by the distribution (Provides-Extra).
+    pub fn provides_extra(&self) -> impl Iterator<Item = &str> {
+        self.get_all("Provides-Extra")
+    }
+
+    /// Get the description included as the message body.
+    #[must_use]
+    pub fn body(&self) -> Option<&str> {
+        self.body.as_deref()
+    }
+}
+
+impl FromStr for CoreMetadata {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields: Vec<(String, String)> = Vec::new();
+        let mut lines = s.lines();
+        let mut body = None;
+
+        while let Some(line) = lines.next() {
+            // Headers end at the first empty line.
+            if line.trim().is_empty() {
+                let rest = lines.collect::<Vec<_>>().join("\n");
+                if !rest.trim().is_empty() {
+                    body = Some(rest);
+                }
+                break;
+            }
+
+            // Continuation lines are folded into the previous value.
+            if line.starts_with([' ', '\t']) {
+                if let Some((_, value)) = fields.last_mut() {
+                    value.push('\n');
+                    value.push_str(line.trim());
+                }
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once(':') else {
+                return Err(Error::InvalidMetadata(format!("invalid line: {line}")));
+            };
+            fields.push((key.trim().to_string(), value.trim().to_string()));
+        }
+
+        Ok(Self { fields, body })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_core_metadata() {
+        let metadata = CoreMetadata::from_str(
+            r#"Metadata-Version: 2.1
+Name: pytest
+Version: 7.4.3
+Summary: pytest: simple powerful testing with Python
+License: MIT
+Requires-Python: >=3.7
+Provides-Extra: testing
+Requires-Dist: iniconfig
+Requires-Dist: packaging
+Requires-Dist: colorama ; sys_platform == "win32"
+Requires-Dist: pygments (>=2.7.2) ; extra == 'testing'
+Description-Content-Type: text/x-rst
+
+pytest
+======
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(metadata.name(), Some("pytest"));
+        assert_eq!(
+            metadata.version(),
+            Some(Version::from_str("7.4.3").unwrap())
+        );
+        assert_eq!(metadata.get("license"), Some("MIT"));
+        assert_eq!(
+            metadata
+                .requires_dist()
+                .iter()
+                .map(|it| it.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["iniconfig", "packaging", "colorama", "pygments"]
+        );
+        assert!(metadata
+            .requires_python()
+            .unwrap()
+            .contains(&Version::from_str("3.11").unwrap()));
+        assert_eq!(
+            metadata.provides_extra().collect::<Vec<_>>(),
+            vec!["testing"]
+        );
+        assert_eq!(metadata.body(), Some("pytest\n======"));
+    }
+
+    #[test]
+    fn test_core_metadata_continuation_lines() {
+        let metadata =
+            CoreMetadata::from_str("Name: a\nLicense: line one\n        line two\nVersion: 1.0\n")
+                .unwrap();
+
+        assert_eq!(metadata.get("License"), Some("line one\nline two"));
+        assert_eq!(metadata.version(), Some(Version::from_str("1.0").unwrap()));
+    }
+}
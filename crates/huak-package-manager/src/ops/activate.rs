@@ -1,6 +1,6 @@
 use std::process::Command;
 
-use crate::{Config, HuakResult};
+use crate::{Config, Error, HuakResult};
 
 pub fn activate_python_environment(config: &Config) -> HuakResult<()> {
     let workspace = config.workspace();
@@ -10,6 +10,13 @@ pub fn activate_python_environment(config: &Config) -> HuakResult<()> {
         return Ok(());
     }
 
+    // Only virtual environments have activation scripts.
+    if python_env.is_conda() || python_env.is_system() {
+        return Err(Error::UnsupportedPythonEnvironment(
+            python_env.root().to_path_buf(),
+        ));
+    }
+
     #[cfg(unix)]
     let mut cmd = Command::new("bash");
     #[cfg(unix)]
@@ -1,118 +1,183 @@
-use crate::{dependency_iter, Config, Dependency, HuakResult, InstallOptions};
-use pep440_rs::VersionSpecifiers;
+use super::{
+    ensure_manifest_writable,
+    lock::{manifest_root, update_lock_file},
+};
+use crate::{
+    dependency::root_relative_requirement, lock::normalized_name, Config, Dependency, Error,
+    HuakResult, InstallOptions, LocalManifest, ManifestGroup, PythonEnvironment, VersionBound,
+};
 use pep508_rs::VersionOrUrl;
 use std::str::FromStr;
+use toml_edit::Item;
 
 pub struct AddOptions {
+    /// The `VersionBound` to write for dependencies added without a specifier. Defaults to
+    /// `add-bound` in `[tool.huak]`.
+    pub bound: Option<VersionBound>,
+    /// Install direct references to local directories in editable mode.
+    pub editable: bool,
     pub install_options: InstallOptions,
 }
 
+/// Add dependencies to `[project.dependencies]` and install them.
+///
+/// Dependencies with a version specifier or a direct reference (`name @ url`) are written to
+/// the manifest as they're provided. Direct references to local paths are written relative to
+/// the project root (`name @ {root:uri}/libs/my-lib`). Dependencies without either are written with a
+/// `VersionBound` for the installed version.
 pub fn add_project_dependencies(
     dependencies: &[String],
     config: &Config,
     options: &AddOptions,
+) -> HuakResult<()> {
+    add_dependencies(dependencies, None, config, options)
+}
+
+/// Add dependencies to an optional dependency group and install them.
+///
+/// See `add_project_dependencies`.
+pub fn add_project_optional_dependencies(
+    dependencies: &[String],
+    group: &str,
+    config: &Config,
+    options: &AddOptions,
+) -> HuakResult<()> {
+    let group = ManifestGroup::Optional(group.to_string());
+    add_dependencies(dependencies, Some(&group), config, options)
+}
+
+/// Add dependencies to a `[dependency-groups]` group and install them.
+///
+/// See `add_project_dependencies`.
+pub fn add_dependency_group_dependencies(
+    dependencies: &[String],
+    group: &str,
+    config: &Config,
+    options: &AddOptions,
+) -> HuakResult<()> {
+    let group = ManifestGroup::Dependency(group.to_string());
+    add_dependencies(dependencies, Some(&group), config, options)
+}
+
+/// Add dependencies to `[project.dependencies]` or a `ManifestGroup` and install them.
+fn add_dependencies(
+    dependencies: &[String],
+    group: Option<&ManifestGroup>,
+    config: &Config,
+    options: &AddOptions,
 ) -> HuakResult<()> {
     let workspace = config.workspace();
     let mut manifest = workspace.current_local_manifest()?;
+    let contains = |manifest: &LocalManifest, name: &str| match group {
+        Some(it) => it.contains(manifest.manifest_data(), name),
+        None => manifest.manifest_data().contains_project_dependency(name),
+    };
 
     // Collect all dependencies that need to be added to the manifest file.
-    let mut deps = dependency_iter(dependencies)
-        .filter(|dep| {
-            !manifest
-                .manifest_data()
-                .contains_project_dependency(dep.name())
-        })
+    let mut deps = parse_dependencies(dependencies)
+        .into_iter()
+        .filter(|(dep, _)| !contains(&manifest, dep.name()))
         .collect::<Vec<_>>();
 
     if deps.is_empty() {
         return Ok(());
     }
 
+    ensure_manifest_writable(config)?;
+    let bound = version_bound(&manifest, options.bound)?;
+
     let python_env = workspace.resolve_python_environment()?;
-    python_env.install_packages(&deps, &options.install_options, config)?;
+    install_dependencies(&python_env, &deps, options, config)?;
 
     // If there's no version data then get the installed version and add to manifest file.
     let packages = python_env.installed_packages()?; // TODO: Only run if versions weren't provided.
-    for dep in &mut deps {
+    for (dep, arg) in &mut deps {
         if dep.requirement().version_or_url.is_none() {
             // TODO: Optimize this .find
-            if let Some(pkg) = packages.iter().find(|p| p.name() == dep.name()) {
+            let name = normalized_name(dep.name());
+            if let Some(pkg) = packages.iter().find(|p| normalized_name(p.name()) == name) {
                 dep.requirement_mut().version_or_url = Some(VersionOrUrl::VersionSpecifier(
-                    VersionSpecifiers::from_str(&format!("=={}", pkg.version()))
-                        .expect("package should have a version"),
+                    bound.version_specifiers(pkg.version())?,
                 ));
+                *arg = dep.to_string();
             }
         }
 
-        if !manifest
-            .manifest_data()
-            .contains_project_dependency(dep.name())
-        {
-            manifest
-                .manifest_data_mut()
-                .add_project_dependency(&dep.to_string());
+        if !contains(&manifest, dep.name()) {
+            // Local paths are written relative to the project so the manifest is portable.
+            let arg = root_relative_requirement(arg, manifest_root(&manifest));
+            match group {
+                Some(it) => it.add(manifest.manifest_data_mut(), &arg),
+                None => {
+                    manifest.manifest_data_mut().add_project_dependency(&arg);
+                }
+            }
         }
     }
 
     manifest.manifest_data_mut().formatted();
     manifest.write_file()?;
 
-    Ok(())
+    update_lock_file(&manifest, &python_env, &options.install_options, config)
 }
 
-pub fn add_project_optional_dependencies(
-    dependencies: &[String],
-    group: &str,
-    config: &Config,
-    options: &AddOptions,
-) -> HuakResult<()> {
-    let workspace = config.workspace();
-    let mut manifest = workspace.current_local_manifest()?;
-
-    // Collect all dependencies that need to be added.
-    // TODO(cnpryer): Allow
-    let mut deps = dependency_iter(dependencies)
-        .filter(|dep| {
-            !manifest
-                .manifest_data()
-                .contains_project_optional_dependency(dep.name(), group)
+/// Parse each `Dependency` along with the requirement string it was parsed from. Strings
+/// that aren't valid PEP 508 requirements are skipped.
+fn parse_dependencies(dependencies: &[String]) -> Vec<(Dependency, String)> {
+    dependencies
+        .iter()
+        .filter_map(|it| {
+            Dependency::from_str(it)
+                .ok()
+                .map(|dep| (dep, it.trim().to_string()))
         })
-        .collect::<Vec<Dependency>>();
-
-    if deps.is_empty() {
-        return Ok(());
-    };
+        .collect()
+}
 
-    let python_env = workspace.resolve_python_environment()?;
-    python_env.install_packages(&deps, &options.install_options, config)?;
+/// Install `Dependency`s to a `PythonEnvironment`. With `AddOptions::editable` direct
+/// references to local directories are installed in editable mode.
+fn install_dependencies(
+    python_env: &PythonEnvironment,
+    deps: &[(Dependency, String)],
+    options: &AddOptions,
+    config: &Config,
+) -> HuakResult<()> {
+    if !options.editable {
+        let deps = deps.iter().map(|(dep, _)| dep).collect::<Vec<_>>();
+        return python_env.install_packages(&deps, &options.install_options, config);
+    }
 
-    // If there's no version data then get the installed version and add to manifest file.
-    let packages = python_env.installed_packages()?; // TODO: Only run if versions weren't provided.
-    for dep in &mut deps {
-        if dep.requirement().version_or_url.is_none() {
-            // TODO: Optimize this .find
-            if let Some(pkg) = packages.iter().find(|p| p.name() == dep.name()) {
-                dep.requirement_mut().version_or_url = Some(VersionOrUrl::VersionSpecifier(
-                    VersionSpecifiers::from_str(&format!("=={}", pkg.version()))
-                        .expect("package should have a version"),
-                ));
+    let mut paths = Vec::new();
+    for (dep, arg) in deps {
+        match dep.local_path() {
+            Some(it) => paths.push(it),
+            None => {
+                return Err(Error::InvalidDirectReference(format!(
+                    "{arg} isn't a local directory and can't be installed in editable mode"
+                )))
             }
         }
-
-        if !manifest
-            .manifest_data()
-            .contains_project_optional_dependency(dep.name(), group)
-        {
-            manifest
-                .manifest_data_mut()
-                .add_project_optional_dependency(&dep.to_string(), group);
-        }
     }
 
-    manifest.manifest_data_mut().formatted();
-    manifest.write_file()?;
+    python_env.install_editable_packages(&paths, &options.install_options, config)
+}
 
-    Ok(())
+/// Get the `VersionBound` to write for a dependency's version. A `VersionBound` that's
+/// provided is preferred over `add-bound` in `[tool.huak]`.
+pub(crate) fn version_bound(
+    manifest: &LocalManifest,
+    bound: Option<VersionBound>,
+) -> HuakResult<VersionBound> {
+    if let Some(it) = bound {
+        return Ok(it);
+    }
+
+    manifest
+        .manifest_data()
+        .huak_table()
+        .and_then(|it| it.get("add-bound"))
+        .and_then(Item::as_str)
+        .map_or(Ok(VersionBound::default()), VersionBound::from_str)
 }
 
 #[cfg(test)]
@@ -146,6 +211,8 @@ mod tests {
         let ws = config.workspace();
         let venv = ws.resolve_python_environment().unwrap();
         let options = AddOptions {
+            bound: None,
+            editable: false,
             install_options: InstallOptions { values: None },
         };
 
@@ -160,6 +227,99 @@ mod tests {
             .contains_project_dependency(dep.name()));
     }
 
+    #[test]
+    fn test_add_project_dependencies_local_path() {
+        let dir = tempdir().unwrap();
+        copy_dir(
+            &dev_resources_dir().join("mock-project"),
+            &dir.path().join("mock-project"),
+            &CopyDirOptions::default(),
+        )
+        .unwrap();
+        let lib = dir.path().join("my-lib");
+        std::fs::create_dir_all(lib.join("src").join("my_lib")).unwrap();
+        std::fs::write(lib.join("src").join("my_lib").join("__init__.py"), "").unwrap();
+        std::fs::write(
+            lib.join("pyproject.toml"),
+            "[build-system]\nrequires = [\"hatchling\"]\nbuild-backend = \"hatchling.build\"\n\n[project]\nname = \"my-lib\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        let workspace_root = dir.path().join("mock-project");
+        let cwd = workspace_root.clone();
+        let terminal_options = TerminalOptions {
+            verbosity: Verbosity::Quiet,
+            ..Default::default()
+        };
+        let config = Config {
+            workspace_root,
+            cwd,
+            terminal_options,
+            ..Default::default()
+        };
+        let ws = config.workspace();
+        let options = AddOptions {
+            bound: None,
+            editable: true,
+            install_options: InstallOptions { values: None },
+        };
+        let requirement = crate::requirement_from_arg("my-lib @ ../my-lib", &config.cwd).unwrap();
+
+        add_project_dependencies(std::slice::from_ref(&requirement), &config, &options).unwrap();
+
+        let venv = ws.resolve_python_environment().unwrap();
+        let manifest = ws.current_local_manifest().unwrap();
+
+        assert!(venv
+            .installed_packages()
+            .unwrap()
+            .iter()
+            .any(|it| it.name() == "my-lib"));
+        assert!(manifest
+            .manifest_data()
+            .project_dependencies()
+            .unwrap()
+            .contains(&"my-lib @ {root:uri}/../my-lib".to_string()));
+    }
+
+    #[test]
+    fn test_add_project_dependencies_locked() {
+        let dir = tempdir().unwrap();
+        copy_dir(
+            &dev_resources_dir().join("mock-project"),
+            &dir.path().join("mock-project"),
+            &CopyDirOptions::default(),
+        )
+        .unwrap();
+        let workspace_root = dir.path().join("mock-project");
+        let cwd = workspace_root.clone();
+        let terminal_options = TerminalOptions {
+            verbosity: Verbosity::Quiet,
+            ..Default::default()
+        };
+        let config = Config {
+            workspace_root,
+            cwd,
+            terminal_options,
+            locked: true,
+            ..Default::default()
+        };
+        let options = AddOptions {
+            bound: None,
+            editable: false,
+            install_options: InstallOptions { values: None },
+        };
+        let before = std::fs::read_to_string(config.workspace_root.join("pyproject.toml")).unwrap();
+
+        let res = add_project_dependencies(&[String::from("ruff")], &config, &options);
+
+        assert!(matches!(res, Err(crate::Error::ManifestFileLocked)));
+        assert_eq!(
+            std::fs::read_to_string(config.workspace_root.join("pyproject.toml")).unwrap(),
+            before
+        );
+        assert!(!config.workspace_root.join(".venv").exists());
+    }
+
     #[test]
     fn test_add_optional_project_dependencies() {
         let dir = tempdir().unwrap();
@@ -186,6 +346,8 @@ mod tests {
         initialize_venv(ws.root().join(".venv"), &ws.environment()).unwrap();
         let venv = ws.resolve_python_environment().unwrap();
         let options = AddOptions {
+            bound: None,
+            editable: false,
             install_options: InstallOptions { values: None },
         };
 
@@ -200,4 +362,87 @@ mod tests {
             .manifest_data()
             .contains_project_optional_dependency(dep.name(), "dev"));
     }
+
+    #[test]
+    fn test_add_dependency_group_dependencies() {
+        let dir = tempdir().unwrap();
+        copy_dir(
+            &dev_resources_dir().join("mock-project"),
+            &dir.path().join("mock-project"),
+            &CopyDirOptions::default(),
+        )
+        .unwrap();
+        let group = "lint";
+        let workspace_root = dir.path().join("mock-project");
+        let cwd = workspace_root.clone();
+        let terminal_options = TerminalOptions {
+            verbosity: Verbosity::Quiet,
+            ..Default::default()
+        };
+        let config = Config {
+            workspace_root,
+            cwd,
+            terminal_options,
+            ..Default::default()
+        };
+        let ws = config.workspace();
+        initialize_venv(ws.root().join(".venv"), &ws.environment()).unwrap();
+        let venv = ws.resolve_python_environment().unwrap();
+        let options = AddOptions {
+            bound: None,
+            editable: false,
+            install_options: InstallOptions { values: None },
+        };
+
+        add_dependency_group_dependencies(&[String::from("isort")], group, &config, &options)
+            .unwrap();
+
+        let manifest = ws.current_local_manifest().unwrap();
+
+        assert!(venv.contains_module("isort").unwrap());
+        assert!(manifest
+            .manifest_data()
+            .contains_dependency_group_dependency("isort", group));
+        assert!(!manifest
+            .manifest_data()
+            .contains_project_optional_dependency("isort", group));
+    }
+
+    #[test]
+    fn test_version_bound() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("pyproject.toml");
+        std::fs::write(
+            &path,
+            "[project]\nname = \"mock-project\"\n\n[tool.huak]\nadd-bound = \"compatible\"\n",
+        )
+        .unwrap();
+        let manifest = LocalManifest::new(&path).unwrap();
+        let mut options = AddOptions {
+            bound: None,
+            editable: false,
+            install_options: InstallOptions { values: None },
+        };
+
+        assert_eq!(
+            version_bound(&manifest, options.bound).unwrap(),
+            VersionBound::Compatible
+        );
+
+        options.bound = Some(VersionBound::Lower);
+
+        assert_eq!(
+            version_bound(&manifest, options.bound).unwrap(),
+            VersionBound::Lower
+        );
+
+        std::fs::write(&path, "[tool.huak]\nadd-bound = \"loose\"\n").unwrap();
+        let manifest = LocalManifest::new(&path).unwrap();
+        options.bound = None;
+
+        assert!(matches!(
+            version_bound(&manifest, options.bound),
+            Err(crate::Error::InvalidVersionBound(_))
+        ));
+    }
 }
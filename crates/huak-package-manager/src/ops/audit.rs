@@ -0,0 +1,306 @@
+use super::required_lock_file;
+use crate::{
+    AdvisoryDatabase, Config, CredentialProvider, DistributionFile, Error, HuakResult,
+    OutputFormat, Vulnerability,
+};
+use pep440_rs::Version;
+use std::{collections::BTreeMap, path::Path, str::FromStr};
+use termcolor::Color;
+use toml_edit::{Item, Value};
+
+/// The OSV database of advisories for Python packages.
+const DEFAULT_ADVISORY_DATABASE_URL: &str =
+    "https://osv-vulnerabilities.storage.googleapis.com/PyPI/all.zip";
+
+pub struct AuditOptions {
+    /// The format of the report.
+    pub format: OutputFormat,
+    /// The advisory database to use (a directory, a zip file or a URL). Defaults to
+    /// `database` in `[tool.huak.audit]` or OSV's database of Python packages.
+    pub database: Option<String>,
+    /// Advisory IDs or aliases to ignore in addition to `ignore` in `[tool.huak.audit]`.
+    pub ignore: Vec<String>,
+    /// Audit the packages in the lock file instead of the installed packages.
+    pub lock_file: bool,
+}
+
+/// Audit the project's packages for known vulnerabilities.
+///
+/// The installed packages (or the packages in the lock file) are checked against an OSV
+/// advisory database. Advisories are ignored with `[tool.huak.audit]`:
+///
+/// ```toml
+/// [tool.huak.audit]
+/// database = "advisories.zip"
+/// ignore = ["PYSEC-2023-0001", "CVE-2023-0001"]
+/// ```
+///
+/// Returns `Error::VulnerablePackages` if a package is affected by an advisory that isn't
+/// ignored.
+pub fn audit_project(config: &Config, options: &AuditOptions) -> HuakResult<()> {
+    let workspace = config.workspace();
+    let manifest = workspace.current_local_manifest()?;
+    let root = manifest
+        .path()
+        .parent()
+        .unwrap_or(workspace.root())
+        .to_path_buf();
+    let audit_table = manifest
+        .manifest_data()
+        .huak_table()
+        .and_then(|it| it.get("audit"));
+
+    let mut ignore = options.ignore.clone();
+    ignore.extend(ignored_advisories(audit_table)?);
+    let database = match options.database.as_ref() {
+        Some(it) => it.clone(),
+        None => audit_table
+            .and_then(|it| it.get("database"))
+            .and_then(Item::as_str)
+            .unwrap_or(DEFAULT_ADVISORY_DATABASE_URL)
+            .to_string(),
+    };
+
+    let packages = if options.lock_file {
+        required_lock_file(&manifest, config)?
+            .packages()
+            .iter()
+            .map(|it| (it.name.clone(), it.version.clone()))
+            .collect::<Vec<_>>()
+    } else {
+        workspace
+            .current_python_environment()?
+            .installed_distributions()?
+            .iter()
+            .map(|it| (it.normalized_name(), it.version().to_string()))
+            .collect()
+    };
+
+    let db = advisory_database(&database, &root, config)?;
+    let vulnerabilities = vulnerabilities(&db, &packages, &ignore)?;
+    let mut terminal = config.terminal();
+
+    match options.format {
+        OutputFormat::Json => {
+            terminal.print_stdout(serde_json::to_string_pretty(&vulnerabilities)?)
+        }
+        OutputFormat::Table if vulnerabilities.is_empty() => terminal.print_custom(
+            "Success",
+            format!(
+                "no known vulnerabilities found in {} packages",
+                packages.len()
+            ),
+            Color::Green,
+            true,
+        ),
+        OutputFormat::Table => terminal.print_stdout(render_table(&vulnerabilities)),
+    }?;
+
+    if vulnerabilities.is_empty() {
+        return Ok(());
+    }
+
+    let mut names = vulnerabilities
+        .iter()
+        .map(|it| it.name.as_str())
+        .collect::<Vec<_>>();
+    names.dedup();
+
+    Err(Error::VulnerablePackages(names.join(", ")))
+}
+
+/// Get the advisory IDs ignored in `[tool.huak.audit]`.
+fn ignored_advisories(table: Option<&Item>) -> HuakResult<Vec<String>> {
+    let Some(item) = table.and_then(|it| it.get("ignore")) else {
+        return Ok(Vec::new());
+    };
+
+    item.as_array()
+        .and_then(|it| {
+            it.iter()
+                .map(|it| Value::as_str(it).map(ToString::to_string))
+                .collect::<Option<Vec<_>>>()
+        })
+        .ok_or_else(|| {
+            Error::HuakConfigurationError(
+                "[tool.huak.audit] ignore must be an array of advisory IDs".to_string(),
+            )
+        })
+}
+
+/// Load the `AdvisoryDatabase` from a path (relative to the project's root) or a URL.
+///
+/// Databases downloaded from a URL are kept in the cache and reused in offline mode.
+fn advisory_database(database: &str, root: &Path, config: &Config) -> HuakResult<AdvisoryDatabase> {
+    if !(database.starts_with("http://") || database.starts_with("https://")) {
+        let path = match url::Url::parse(database) {
+            Ok(url) if url.scheme() == "file" => url
+                .to_file_path()
+                .map_err(|()| Error::InvalidAdvisory(format!("invalid file url: {url}")))?,
+            _ => root.join(database),
+        };
+        return AdvisoryDatabase::from_path(path);
+    }
+
+    let path = config.cache().map(|it| it.advisory_database(database));
+    if config.offline {
+        return match path {
+            Some(it) if it.exists() => AdvisoryDatabase::from_path(it),
+            _ => Err(Error::OfflineNetworkRequest(database.to_string())),
+        };
+    }
+
+    let file = DistributionFile::from_url(database)?;
+    let credentials = CredentialProvider::new(config.home.as_deref(), &BTreeMap::new())?;
+    let bytes = file.download(credentials.credentials(&file.url).as_ref())?;
+    let db = AdvisoryDatabase::from_zip(std::io::Cursor::new(&bytes))?;
+
+    if let Some(path) = path {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, bytes)?;
+    }
+
+    Ok(db)
+}
+
+/// Get the `Vulnerability`s affecting (name, version) packages sorted by package. Advisories
+/// with an ignored ID or alias are skipped.
+fn vulnerabilities(
+    db: &AdvisoryDatabase,
+    packages: &[(String, String)],
+    ignore: &[String],
+) -> HuakResult<Vec<Vulnerability>> {
+    let mut packages = packages.to_vec();
+    packages.sort();
+    packages.dedup();
+
+    let mut vulnerabilities = Vec::new();
+    for (name, version) in packages
+        .iter()
+        .filter_map(|(name, version)| Some((name, Version::from_str(version).ok()?)))
+    {
+        vulnerabilities.extend(
+            db.vulnerabilities(name, &version)?
+                .into_iter()
+                .filter(|it| !ignore.iter().any(|id| it.has_id(id))),
+        );
+    }
+
+    Ok(vulnerabilities)
+}
+
+fn render_table(vulnerabilities: &[Vulnerability]) -> String {
+    let mut rows = vec![[
+        "Package".to_string(),
+        "Version".to_string(),
+        "Advisory".to_string(),
+        "Affected".to_string(),
+        "Fixed".to_string(),
+    ]];
+    for it in vulnerabilities {
+        let fixed = if it.fixed.is_empty() {
+            "-".to_string()
+        } else {
+            it.fixed.join(", ")
+        };
+        rows.push([
+            it.name.clone(),
+            it.version.clone(),
+            it.id.clone(),
+            it.affected.clone(),
+            fixed,
+        ]);
+    }
+
+    let mut widths = [0; 5];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{cell:width$}"))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::write_mock_advisories;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_vulnerabilities() {
+        let dir = tempdir().unwrap();
+        write_mock_advisories(
+            dir.path(),
+            &[
+                (
+                    "PYSEC-0000-1",
+                    "CVE-0000-1",
+                    "click",
+                    &[("introduced", "0"), ("fixed", "8.0.0")],
+                ),
+                (
+                    "PYSEC-0000-2",
+                    "CVE-0000-2",
+                    "click",
+                    &[
+                        ("introduced", "7.0"),
+                        ("fixed", "7.1.3"),
+                        ("fixed", "8.1.0"),
+                    ],
+                ),
+                (
+                    "GHSA-mock",
+                    "CVE-0000-3",
+                    "ruff",
+                    &[("introduced", "0"), ("last_affected", "0.1.5")],
+                ),
+            ],
+        );
+        let db = AdvisoryDatabase::from_path(dir.path()).unwrap();
+        let packages = [
+            ("ruff".to_string(), "0.1.5".to_string()),
+            ("click".to_string(), "7.1.2".to_string()),
+            ("xlcsv".to_string(), "0.1.0".to_string()),
+        ];
+
+        let vulnerabilities = vulnerabilities(&db, &packages, &["cve-0000-3".to_string()]).unwrap();
+
+        assert_eq!(
+            render_table(&vulnerabilities),
+            r"Package  Version  Advisory      Affected       Fixed
+click    7.1.2    PYSEC-0000-1  <8.0.0         8.0.0
+click    7.1.2    PYSEC-0000-2  >=7.0, <7.1.3  7.1.3, 8.1.0"
+        );
+    }
+
+    #[test]
+    fn test_ignored_advisories() {
+        let doc = "[audit]\nignore = [\"PYSEC-0000-1\"]\n"
+            .parse::<toml_edit::Document>()
+            .unwrap();
+        assert_eq!(
+            ignored_advisories(doc.get("audit")).unwrap(),
+            ["PYSEC-0000-1"]
+        );
+
+        let doc = "[audit]\nignore = \"PYSEC-0000-1\"\n"
+            .parse::<toml_edit::Document>()
+            .unwrap();
+        assert!(ignored_advisories(doc.get("audit")).is_err());
+    }
+}
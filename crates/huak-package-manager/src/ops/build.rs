@@ -1,4 +1,4 @@
-use super::add_venv_to_command;
+use super::{add_tools_to_manifest, add_venv_to_command, install_tools};
 use crate::{Config, Dependency, HuakResult, InstallOptions};
 use std::{process::Command, str::FromStr};
 
@@ -16,27 +16,23 @@ pub fn build_project(config: &Config, options: &BuildOptions) -> HuakResult<()>
     // Install the `build` package if it isn't already installed.
     let build_dep = Dependency::from_str("build")?;
     if !python_env.contains_module(build_dep.name())? {
-        python_env.install_packages(&[&build_dep], &options.install_options, config)?;
+        install_tools(
+            &[&build_dep],
+            &manifest,
+            &python_env,
+            &options.install_options,
+            config,
+        )?;
     }
 
     // Add the installed `build` package to the manifest file.
-    if !manifest
-        .manifest_data()
-        .contains_project_dependency_any(build_dep.name())
-    {
-        for pkg in python_env
-            .installed_packages()?
-            .iter()
-            .filter(|pkg| pkg.name() == build_dep.name())
-        {
-            manifest
-                .manifest_data_mut()
-                .add_project_optional_dependency(&pkg.to_string(), "dev");
-        }
-    }
-
-    manifest.manifest_data_mut().formatted();
-    manifest.write_file()?;
+    add_tools_to_manifest(
+        &[&build_dep],
+        &mut manifest,
+        &python_env,
+        &options.install_options,
+        config,
+    )?;
 
     // Run `build`.
     let mut cmd = Command::new(python_env.python_path());
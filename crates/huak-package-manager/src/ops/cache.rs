@@ -0,0 +1,88 @@
+use crate::{Cache, CacheInfo, Config, Error, HuakResult};
+use termcolor::Color;
+
+/// Display the path to the cache directory.
+pub fn display_cache_dir(config: &Config) -> HuakResult<()> {
+    let cache = home_cache(config)?;
+
+    config.terminal().print_stdout(cache.root().display())
+}
+
+/// Display the location of the cache and the number and size of the wheels in it.
+pub fn display_cache_info(config: &Config) -> HuakResult<()> {
+    let cache = home_cache(config)?;
+    let info = cache.info()?;
+    let mut terminal = config.terminal();
+
+    terminal.print_stdout(format!("location: {}", cache.root().display()))?;
+    terminal.print_stdout(format!("wheels: {}", info.wheels))?;
+    terminal.print_stdout(format!("files: {}", info.files))?;
+    terminal.print_stdout(format!("size: {}", format_size(info.size)))
+}
+
+/// Remove everything from the cache.
+pub fn clean_cache(config: &Config) -> HuakResult<()> {
+    let info = home_cache(config)?.clean()?;
+
+    print_removed(&info, config)
+}
+
+/// Remove the cached wheels that aren't installed to any environment.
+pub fn prune_cache(config: &Config) -> HuakResult<()> {
+    let info = home_cache(config)?.prune()?;
+
+    print_removed(&info, config)
+}
+
+fn home_cache(config: &Config) -> HuakResult<Cache> {
+    config.cache().ok_or(Error::HuakHomeNotFound)
+}
+
+fn print_removed(info: &CacheInfo, config: &Config) -> HuakResult<()> {
+    config.terminal().print_custom(
+        "Removed",
+        format!(
+            "{} wheel{} ({} files, {})",
+            info.wheels,
+            if info.wheels == 1 { "" } else { "s" },
+            info.files,
+            format_size(info.size)
+        ),
+        Color::Green,
+        true,
+    )
+}
+
+/// Format a number of bytes with binary units (for example "1.5 MiB").
+#[allow(clippy::cast_precision_loss)]
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+    for it in &UNITS[1..] {
+        if size < 1024.0 {
+            break;
+        }
+        size /= 1024.0;
+        unit = it;
+    }
+
+    format!("{size:.1} {unit}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MiB");
+    }
+}
@@ -0,0 +1,551 @@
+use super::lock::{current_lock_file, manifest_root, required_lock_file};
+use crate::{
+    lock::{
+        lockfile_from_report, manifest_dependency_groups, InstallationReport, REQUIRED_GROUP_NAME,
+    },
+    Config, Error, HuakResult, LocalManifest, LockedPackage, Lockfile, PythonEnvironment,
+};
+use pep440_rs::Version;
+use serde::Serialize;
+use std::{collections::BTreeMap, fmt::Display, path::PathBuf, str::FromStr};
+use termcolor::Color;
+
+/// The file formats `huak export` can write.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// A pip requirements file.
+    #[default]
+    RequirementsTxt,
+    /// A pip constraints file. Every package is pinned to its version.
+    Constraints,
+    /// A pylock.toml file (see <https://peps.python.org/pep-0751/>).
+    Pylock,
+}
+
+impl FromStr for ExportFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "requirements-txt" => Ok(ExportFormat::RequirementsTxt),
+            "constraints" => Ok(ExportFormat::Constraints),
+            "pylock" => Ok(ExportFormat::Pylock),
+            _ => Err(Error::InvalidExportFormat(s.to_string())),
+        }
+    }
+}
+
+impl Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportFormat::RequirementsTxt => write!(f, "requirements-txt"),
+            ExportFormat::Constraints => write!(f, "constraints"),
+            ExportFormat::Pylock => write!(f, "pylock"),
+        }
+    }
+}
+
+pub struct ExportOptions {
+    pub format: ExportFormat,
+    /// The optional dependency groups and dependency groups to export. All groups are exported
+    /// if `None`.
+    pub groups: Option<Vec<String>>,
+    /// Include the hashes of each package. Hashes are only available from huak.lock. pylock.toml
+    /// files always include hashes.
+    pub hashes: bool,
+    /// The file to write to. The export is written to stdout if `None`.
+    pub output: Option<PathBuf>,
+}
+
+/// Export the project's dependencies to a requirements file, a constraints file or pylock.toml.
+///
+/// Packages are exported from huak.lock if it's up to date with the manifest. Otherwise they're
+/// exported from the packages installed to the current Python environment. Packages are sorted
+/// by name and keep the markers they're locked with.
+///
+/// pylock.toml files list every locked file of each package, so they're always exported from
+/// huak.lock.
+pub fn export_project(config: &Config, options: &ExportOptions) -> HuakResult<()> {
+    let workspace = config.workspace();
+    let manifest = workspace.current_local_manifest()?;
+
+    let (lockfile, source) =
+        if config.is_locked() || options.hashes || options.format == ExportFormat::Pylock {
+            (required_lock_file(&manifest, config)?, "huak.lock")
+        } else if let Some(it) = current_lock_file(&manifest)? {
+            (it, "huak.lock")
+        } else {
+            let python_env = workspace.current_python_environment()?;
+            (
+                environment_lock_file(&manifest, &python_env)?,
+                "the Python environment",
+            )
+        };
+
+    let groups = if let Some(gs) = options.groups.as_ref() {
+        let mut groups = vec![REQUIRED_GROUP_NAME.to_string()];
+        groups.extend(gs.iter().cloned());
+        groups
+    } else {
+        let mut groups = lockfile
+            .packages()
+            .iter()
+            .flat_map(|pkg| pkg.groups.iter().cloned())
+            .collect::<Vec<_>>();
+        groups.sort_by(|a, b| (a != REQUIRED_GROUP_NAME, a).cmp(&(b != REQUIRED_GROUP_NAME, b)));
+        groups.dedup();
+        groups
+    };
+    let packages = lockfile.packages_in_groups(&groups).collect::<Vec<_>>();
+
+    let content = match options.format {
+        ExportFormat::RequirementsTxt | ExportFormat::Constraints => {
+            render_requirements(&packages, options.format, &groups, source, options.hashes)
+        }
+        ExportFormat::Pylock => render_pylock(
+            &packages,
+            manifest
+                .manifest_data()
+                .project_table()
+                .and_then(|it| it.get("requires-python"))
+                .and_then(toml_edit::Item::as_str),
+        )?,
+    };
+
+    let mut terminal = config.terminal();
+    if let Some(path) = options.output.as_ref() {
+        std::fs::write(path, content)?;
+        terminal.print_custom(
+            "Success",
+            format!("exported {} packages to {}", packages.len(), path.display()),
+            Color::Green,
+            true,
+        )
+    } else {
+        terminal.print_stdout(content.trim_end())
+    }
+}
+
+/// Build a `Lockfile` from the packages installed to a `PythonEnvironment` that are required by
+/// the manifest's dependency groups.
+fn environment_lock_file(
+    manifest: &LocalManifest,
+    python_env: &PythonEnvironment,
+) -> HuakResult<Lockfile> {
+    let groups = manifest_dependency_groups(manifest.manifest_data(), manifest_root(manifest))?;
+    let report = InstallationReport::from_distributions(&python_env.installed_distributions()?);
+    let python_version = Version::from_str(&python_env.python_version().to_string())
+        .map_err(Error::InvalidVersionString)?;
+
+    Ok(lockfile_from_report(
+        &report,
+        &groups,
+        &python_version,
+        None,
+    ))
+}
+
+fn render_requirements(
+    packages: &[&LockedPackage],
+    format: ExportFormat,
+    groups: &[String],
+    source: &str,
+    hashes: bool,
+) -> String {
+    let mut lines = vec![
+        format!("# This file was generated by `huak export --format {format}` from {source}."),
+        format!("# Groups: {}", groups.join(", ")),
+    ];
+
+    for pkg in packages {
+        if format == ExportFormat::Constraints && pkg.url.is_some() {
+            let pinned = LockedPackage {
+                url: None,
+                ..(*pkg).clone()
+            };
+            lines.push(pinned.requirement_line(hashes));
+        } else {
+            lines.push(pkg.requirement_line(hashes));
+        }
+    }
+
+    let mut content = lines.join("\n");
+    content.push('\n');
+    content
+}
+
+/// A pylock.toml file (see <https://packaging.python.org/en/latest/specifications/pylock-toml/>).
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct Pylock<'a> {
+    lock_version: &'static str,
+    created_by: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    requires_python: Option<&'a str>,
+    packages: Vec<PylockPackage<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct PylockPackage<'a> {
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    marker: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    vcs: Option<PylockVcs>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    directory: Option<PylockDirectory>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    archive: Option<PylockArchive>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sdist: Option<PylockFile<'a>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    wheels: Vec<PylockFile<'a>>,
+}
+
+/// The version control systems pip supports for direct references.
+const VCS_KINDS: [&str; 4] = ["git", "hg", "svn", "bzr"];
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct PylockVcs {
+    #[serde(rename = "type")]
+    kind: String,
+    url: String,
+    commit_id: String,
+}
+
+#[derive(Serialize)]
+struct PylockDirectory {
+    path: String,
+}
+
+#[derive(Serialize)]
+struct PylockArchive {
+    url: String,
+    hashes: BTreeMap<String, String>,
+}
+
+/// A wheel or source distribution released for a package's version on its index.
+#[derive(Serialize)]
+struct PylockFile<'a> {
+    name: &'a str,
+    url: &'a str,
+    hashes: BTreeMap<String, String>,
+}
+
+fn render_pylock(packages: &[&LockedPackage], requires_python: Option<&str>) -> HuakResult<String> {
+    let mut entries = Vec::with_capacity(packages.len());
+
+    for pkg in packages {
+        let mut entry = PylockPackage {
+            name: &pkg.name,
+            version: None,
+            marker: pkg.markers.as_deref(),
+            index: None,
+            vcs: None,
+            directory: None,
+            archive: None,
+            sdist: None,
+            wheels: Vec::new(),
+        };
+
+        let url = pkg.url.as_deref();
+        if let Some(vcs) = url.and_then(vcs_reference) {
+            entry.vcs = Some(vcs);
+        } else if let Some(path) = url
+            .and_then(|it| it.strip_prefix("file://"))
+            .filter(|it| !is_archive(it))
+        {
+            entry.directory = Some(PylockDirectory {
+                path: path.to_string(),
+            });
+        } else if let Some(url) = url {
+            entry.archive = Some(PylockArchive {
+                url: url.to_string(),
+                hashes: hash_table(&pkg.hashes),
+            });
+        } else {
+            if pkg.files.is_empty() {
+                return Err(Error::LockedFilesNotFound(pkg.name.clone()));
+            }
+
+            entry.version = Some(&pkg.version);
+            entry.index = pkg.index.as_deref();
+            for file in &pkg.files {
+                let it = PylockFile {
+                    name: &file.name,
+                    url: &file.url,
+                    hashes: hash_table(&file.hashes),
+                };
+                if std::path::Path::new(&file.name)
+                    .extension()
+                    .is_some_and(|ext| ext == "whl")
+                {
+                    entry.wheels.push(it);
+                } else if entry.sdist.is_none() {
+                    entry.sdist = Some(it);
+                }
+            }
+        }
+
+        entries.push(entry);
+    }
+
+    Ok(toml::to_string(&Pylock {
+        lock_version: "1.0",
+        created_by: "huak",
+        requires_python,
+        packages: entries,
+    })?)
+}
+
+/// Convert "<algorithm>:<digest>" hashes to a table of digests by algorithm.
+fn hash_table(hashes: &[String]) -> BTreeMap<String, String> {
+    hashes
+        .iter()
+        .filter_map(|it| it.split_once(':'))
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+/// Parse a VCS direct reference (`git+https://github.com/owner/repo.git@commit`). The commit
+/// is taken from the last `@` in the URL's path so user info like `git@` isn't mistaken for it.
+fn vcs_reference(url: &str) -> Option<PylockVcs> {
+    let (kind, rest) = url.split_once('+')?;
+    if !VCS_KINDS.contains(&kind) {
+        return None;
+    }
+
+    let rest = rest.split_once('#').map_or(rest, |(it, _)| it);
+    let path_start = match rest.find("://") {
+        Some(i) => rest[i + 3..].find('/').map_or(rest.len(), |it| i + 3 + it),
+        None => 0,
+    };
+    let (url, commit_id) = match rest[path_start..].rsplit_once('@') {
+        Some((path, commit)) => (format!("{}{path}", &rest[..path_start]), commit),
+        None => (rest.to_string(), ""),
+    };
+
+    Some(PylockVcs {
+        kind: kind.to_string(),
+        url,
+        commit_id: commit_id.to_string(),
+    })
+}
+
+fn is_archive(path: &str) -> bool {
+    std::path::Path::new(path)
+        .extension()
+        .is_some_and(|ext| ext == "whl" || ext == "gz" || ext == "zip")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LockedFile;
+
+    fn mock_packages() -> Vec<LockedPackage> {
+        let file = |name: &str, hash: &str| LockedFile {
+            name: name.to_string(),
+            url: format!("https://files.pythonhosted.org/packages/{name}"),
+            hashes: vec![hash.to_string()],
+        };
+
+        vec![
+            LockedPackage {
+                name: "click".to_string(),
+                version: "8.1.7".to_string(),
+                index: Some("https://pypi.org/simple".to_string()),
+                url: None,
+                hashes: Vec::new(),
+                markers: None,
+                groups: vec![REQUIRED_GROUP_NAME.to_string()],
+                dependencies: vec!["colorama".to_string()],
+                files: vec![
+                    file("click-8.1.7-py3-none-any.whl", "sha256:ae74fb96"),
+                    file("click-8.1.7.tar.gz", "sha256:ca9853ad"),
+                ],
+            },
+            LockedPackage {
+                name: "colorama".to_string(),
+                version: "0.4.6".to_string(),
+                index: Some("https://pypi.org/simple".to_string()),
+                url: None,
+                hashes: Vec::new(),
+                markers: Some("platform_system == \"Windows\"".to_string()),
+                groups: vec![REQUIRED_GROUP_NAME.to_string()],
+                dependencies: Vec::new(),
+                files: vec![file(
+                    "colorama-0.4.6-py2.py3-none-any.whl",
+                    "sha256:4f1d9991",
+                )],
+            },
+            LockedPackage {
+                name: "xlcsv".to_string(),
+                version: "0.1.0".to_string(),
+                index: None,
+                url: Some("git+https://github.com/cnpryer/xlcsv.git@1a2b3c".to_string()),
+                hashes: Vec::new(),
+                markers: None,
+                groups: vec!["dev".to_string()],
+                dependencies: Vec::new(),
+                files: Vec::new(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_render_requirements() {
+        let packages = mock_packages();
+        let packages = packages.iter().collect::<Vec<_>>();
+        let groups = [REQUIRED_GROUP_NAME.to_string(), "dev".to_string()];
+
+        assert_eq!(
+            render_requirements(
+                &packages,
+                ExportFormat::RequirementsTxt,
+                &groups,
+                "huak.lock",
+                true
+            ),
+            r#"# This file was generated by `huak export --format requirements-txt` from huak.lock.
+# Groups: required, dev
+click==8.1.7 --hash=sha256:ae74fb96 --hash=sha256:ca9853ad
+colorama==0.4.6 ; platform_system == "Windows" --hash=sha256:4f1d9991
+xlcsv @ git+https://github.com/cnpryer/xlcsv.git@1a2b3c
+"#
+        );
+        assert_eq!(
+            render_requirements(
+                &packages,
+                ExportFormat::Constraints,
+                &groups,
+                "huak.lock",
+                false
+            ),
+            r#"# This file was generated by `huak export --format constraints` from huak.lock.
+# Groups: required, dev
+click==8.1.7
+colorama==0.4.6 ; platform_system == "Windows"
+xlcsv==0.1.0
+"#
+        );
+    }
+
+    #[test]
+    fn test_render_pylock() {
+        let packages = mock_packages();
+        let packages = packages.iter().collect::<Vec<_>>();
+
+        assert_eq!(
+            render_pylock(&packages, Some(">=3.8")).unwrap(),
+            r#"lock-version = "1.0"
+created-by = "huak"
+requires-python = ">=3.8"
+
+[[packages]]
+name = "click"
+version = "8.1.7"
+index = "https://pypi.org/simple"
+
+[packages.sdist]
+name = "click-8.1.7.tar.gz"
+url = "https://files.pythonhosted.org/packages/click-8.1.7.tar.gz"
+
+[packages.sdist.hashes]
+sha256 = "ca9853ad"
+
+[[packages.wheels]]
+name = "click-8.1.7-py3-none-any.whl"
+url = "https://files.pythonhosted.org/packages/click-8.1.7-py3-none-any.whl"
+
+[packages.wheels.hashes]
+sha256 = "ae74fb96"
+
+[[packages]]
+name = "colorama"
+version = "0.4.6"
+marker = "platform_system == \"Windows\""
+index = "https://pypi.org/simple"
+
+[[packages.wheels]]
+name = "colorama-0.4.6-py2.py3-none-any.whl"
+url = "https://files.pythonhosted.org/packages/colorama-0.4.6-py2.py3-none-any.whl"
+
+[packages.wheels.hashes]
+sha256 = "4f1d9991"
+
+[[packages]]
+name = "xlcsv"
+
+[packages.vcs]
+type = "git"
+url = "https://github.com/cnpryer/xlcsv.git"
+commit-id = "1a2b3c"
+"#
+        );
+    }
+
+    #[test]
+    fn test_render_pylock_without_files() {
+        let mut packages = mock_packages();
+        packages[0].files.clear();
+        let packages = packages.iter().collect::<Vec<_>>();
+
+        assert!(matches!(
+            render_pylock(&packages, None),
+            Err(Error::LockedFilesNotFound(name)) if name == "click"
+        ));
+    }
+
+    #[test]
+    fn test_render_pylock_direct_references() {
+        let package = |name: &str, url: &str| LockedPackage {
+            name: name.to_string(),
+            version: "0.0.0".to_string(),
+            index: None,
+            url: Some(url.to_string()),
+            hashes: Vec::new(),
+            markers: None,
+            groups: vec![REQUIRED_GROUP_NAME.to_string()],
+            dependencies: Vec::new(),
+            files: Vec::new(),
+        };
+        let packages = [
+            package(
+                "torch",
+                "file:///wheels/torch-2.1.0+cpu-cp311-cp311-linux_x86_64.whl",
+            ),
+            package("xlcsv", "git+ssh://git@github.com/cnpryer/xlcsv.git@1a2b3c"),
+        ];
+        let packages = packages.iter().collect::<Vec<_>>();
+
+        assert_eq!(
+            render_pylock(&packages, None).unwrap(),
+            r#"lock-version = "1.0"
+created-by = "huak"
+
+[[packages]]
+name = "torch"
+
+[packages.archive]
+url = "file:///wheels/torch-2.1.0+cpu-cp311-cp311-linux_x86_64.whl"
+
+[packages.archive.hashes]
+
+[[packages]]
+name = "xlcsv"
+
+[packages.vcs]
+type = "git"
+url = "ssh://git@github.com/cnpryer/xlcsv.git"
+commit-id = "1a2b3c"
+"#
+        );
+    }
+}
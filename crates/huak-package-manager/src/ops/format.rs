@@ -1,7 +1,7 @@
 use crate::{Config, Dependency, HuakResult, InstallOptions};
 use std::{process::Command, str::FromStr};
 
-use super::add_venv_to_command;
+use super::{add_tools_to_manifest, add_venv_to_command, install_tools};
 
 pub struct FormatOptions {
     /// A values vector of format options typically used for passing on arguments.
@@ -23,33 +23,23 @@ pub fn format_project(config: &Config, options: &FormatOptions) -> HuakResult<()
         .collect::<Vec<_>>();
 
     if !new_format_deps.is_empty() {
-        python_env.install_packages(&new_format_deps, &options.install_options, config)?;
+        install_tools(
+            &new_format_deps,
+            &manifest,
+            &python_env,
+            &options.install_options,
+            config,
+        )?;
     }
 
     // Add the installed `ruff` package to the manifest file if not already there.
-    let new_format_deps = format_deps
-        .iter()
-        .filter(|dep| {
-            !manifest
-                .manifest_data()
-                .contains_project_dependency_any(dep.name())
-        })
-        .map(Dependency::name)
-        .collect::<Vec<_>>();
-
-    if !new_format_deps.is_empty() {
-        for pkg in python_env
-            .installed_packages()?
-            .iter()
-            .filter(|pkg| new_format_deps.contains(&pkg.name()))
-        {
-            manifest
-                .manifest_data_mut()
-                .add_project_optional_dependency(&pkg.to_string(), "dev");
-        }
-    }
-
-    manifest.write_file()?;
+    add_tools_to_manifest(
+        &format_deps.iter().collect::<Vec<_>>(),
+        &mut manifest,
+        &python_env,
+        &options.install_options,
+        config,
+    )?;
 
     // Run `ruff` for formatting imports and the rest of the Python code in the workspace.
     // NOTE: This needs to be refactored https://github.com/cnpryer/huak/issues/784, https://github.com/cnpryer/huak/issues/718
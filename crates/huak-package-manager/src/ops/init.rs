@@ -1,12 +1,19 @@
 use toml_edit::{Item, Table};
 
-use super::init_git;
+use super::{
+    ensure_manifest_writable, init_git,
+    lock::{
+        current_lock_file, install_lock_file, manifest_root, required_lock_file, update_lock_file,
+    },
+};
 use crate::{
-    default_package_entrypoint_string, directory_is_venv, importable_package_name,
-    last_path_component, Config, Dependency, Error, HuakResult, InstallOptions, LocalManifest,
-    WorkspaceOptions,
+    default_package_entrypoint_string,
+    dependency::{expand_root_uri, root_relative_requirement},
+    directory_is_venv, importable_package_name, last_path_component, Config, Dependency, Error,
+    HuakResult, ImportSource, InstallOptions, LocalManifest, WorkspaceOptions,
 };
 use std::{path::PathBuf, str::FromStr};
+use termcolor::Color;
 
 pub fn init_app_project(config: &Config, options: &WorkspaceOptions) -> HuakResult<()> {
     init_lib_project(config, options)?;
@@ -43,6 +50,8 @@ pub fn init_lib_project(config: &Config, options: &WorkspaceOptions) -> HuakResu
         Err(_) => LocalManifest::template(workspace.root().join("pyproject.toml")),
     };
 
+    ensure_manifest_writable(config)?;
+
     if options.uses_git {
         init_git(&config.workspace_root)?;
     }
@@ -52,10 +61,99 @@ pub fn init_lib_project(config: &Config, options: &WorkspaceOptions) -> HuakResu
     manifest.write_file()
 }
 
+/// Import the dependencies listed in a requirements file, Pipfile or Poetry project into the
+/// workspace's manifest file.
+///
+/// Required dependencies are added to `[project.dependencies]` and any other group is added
+/// to `[project.optional-dependencies]`. A warning is displayed for each entry that couldn't
+/// be translated.
+pub fn import_dependencies(config: &Config, source: &ImportSource) -> HuakResult<()> {
+    ensure_manifest_writable(config)?;
+
+    let workspace = config.workspace();
+    let mut manifest = workspace.current_local_manifest()?;
+    let mut imported = source.read(&config.cwd)?;
+    let root = manifest_root(&manifest).to_path_buf();
+    let manifest_data = manifest.manifest_data_mut();
+
+    // Poetry projects don't need a `[project]` table.
+    if manifest_data.project_table().is_none() {
+        manifest_data.doc["project"] = Item::Table(Table::new());
+    }
+
+    if manifest_data.project_name().is_none() {
+        let name = match imported.name.as_ref() {
+            Some(it) => it.clone(),
+            None => last_path_component(&config.workspace_root)?,
+        };
+        manifest_data.set_project_name(&name);
+    }
+    if manifest_data.project_version().is_none() {
+        manifest_data.set_project_version(imported.version.as_deref().unwrap_or("0.0.1"));
+    }
+    if manifest_data.project_description().is_none() {
+        if let Some(it) = imported.description.as_ref() {
+            manifest_data.set_project_description(it);
+        }
+    }
+
+    if let Some(requires_python) = imported.requires_python.as_ref() {
+        let table = manifest_data
+            .project_table_mut()
+            .expect("project table should exist");
+
+        match table.get("requires-python").and_then(Item::as_str) {
+            Some(it) if it != requires_python => imported.skipped.push(format!(
+                "requires-python `{requires_python}` wasn't imported: the project requires `{it}`"
+            )),
+            Some(_) => (),
+            None => table["requires-python"] = toml_edit::value(requires_python.as_str()),
+        }
+    }
+
+    // Local paths are written relative to the project so the manifest is portable.
+    for req in &imported.dependencies {
+        manifest_data.add_project_dependency(&root_relative_requirement(&req.to_string(), &root));
+    }
+    for (group, reqs) in &imported.optional_dependencies {
+        for req in reqs {
+            manifest_data.add_project_optional_dependency(
+                &root_relative_requirement(&req.to_string(), &root),
+                group,
+            );
+        }
+    }
+
+    manifest_data.formatted();
+    manifest.write_file()?;
+
+    let mut terminal = config.terminal();
+    let count = imported.dependencies.len()
+        + imported
+            .optional_dependencies
+            .values()
+            .map(Vec::len)
+            .sum::<usize>();
+    terminal.print_custom(
+        "Imported",
+        format!("{count} dependencies from {source}"),
+        Color::Green,
+        true,
+    )?;
+
+    for message in &imported.skipped {
+        terminal.print_warning(message)?;
+    }
+
+    Ok(())
+}
+
 // TODO(cnpryer): Remove current huak install ops
+#[allow(clippy::needless_pass_by_value)]
 pub fn init_python_env(
     manifest: Option<PathBuf>,
     optional_dependencies: Option<Vec<String>>,
+    dependency_groups: Option<Vec<String>>,
     force: bool,
     options: &InstallOptions,
     config: &Config,
@@ -81,7 +179,13 @@ pub fn init_python_env(
 
     let mut dependencies = Vec::new();
 
-    if let Some(gs) = optional_dependencies {
+    if let Some(gs) = dependency_groups.as_ref() {
+        for g in gs {
+            dependencies.extend(manifest.manifest_data().dependency_group(g)?);
+        }
+    }
+
+    if let Some(gs) = optional_dependencies.as_ref() {
         // If the group "required" is passed and isn't a valid optional dependency group
         // then install just the required dependencies.
         // TODO(cnpryer): Refactor/move
@@ -102,9 +206,9 @@ pub fn init_python_env(
                 }
             }
         }
-    } else {
+    } else if dependency_groups.is_none() {
         // If no groups are passed then install all dependencies listed in the manifest file
-        // including the optional dependencies.
+        // including the optional dependencies and dependency groups.
         if let Some(reqs) = manifest.manifest_data().project_dependencies() {
             dependencies.extend(reqs);
         }
@@ -122,9 +226,20 @@ pub fn init_python_env(
                 }
             }
         }
+
+        for g in manifest
+            .manifest_data()
+            .dependency_group_names()
+            .unwrap_or_default()
+        {
+            dependencies.extend(manifest.manifest_data().dependency_group(&g)?);
+        }
     }
 
     dependencies.dedup();
+    for it in &mut dependencies {
+        *it = expand_root_uri(it, manifest_root(&manifest));
+    }
 
     if dependencies.is_empty() {
         return Ok(());
@@ -146,7 +261,32 @@ pub fn init_python_env(
     }
 
     let python_env = ws.resolve_python_environment()?;
-    python_env.install_packages(&dependencies, options, config)
+
+    // Install the locked dependencies if the lock file is up to date with the manifest file.
+    // Locked configurations only install from the lock file.
+    let lockfile = if config.is_locked() {
+        Some(required_lock_file(&manifest, config)?)
+    } else {
+        current_lock_file(&manifest)?
+    };
+
+    if let Some(lockfile) = lockfile {
+        let groups = match (optional_dependencies, dependency_groups) {
+            (None, None) => None,
+            (optional, groups) => Some(
+                optional
+                    .into_iter()
+                    .chain(groups)
+                    .flatten()
+                    .collect::<Vec<_>>(),
+            ),
+        };
+        return install_lock_file(&lockfile, groups.as_deref(), &python_env, options, config);
+    }
+
+    python_env.install_packages(&dependencies, options, config)?;
+
+    update_lock_file(&manifest, &python_env, options, config)
 }
 
 #[cfg(test)]
@@ -190,6 +330,69 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_import_dependencies() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("mock-project")).unwrap();
+        let workspace_root = dir.path().join("mock-project");
+        let cwd = workspace_root.clone();
+        let terminal_options = TerminalOptions {
+            verbosity: Verbosity::Quiet,
+            ..Default::default()
+        };
+        let config = Config {
+            workspace_root,
+            cwd,
+            terminal_options,
+            ..Default::default()
+        };
+        let options = WorkspaceOptions {
+            uses_git: false,
+            values: None,
+        };
+        std::fs::write(
+            config.cwd.join("Pipfile"),
+            r#"[packages]
+click = "==8.1.7"
+
+[dev-packages]
+pytest = "*"
+
+[requires]
+python_version = "3.11"
+"#,
+        )
+        .unwrap();
+
+        init_lib_project(&config, &options).unwrap();
+        import_dependencies(&config, &ImportSource::from_str("Pipfile").unwrap()).unwrap();
+
+        let ws = config.workspace();
+        let manifest = ws.current_local_manifest().unwrap();
+
+        assert_eq!(
+            manifest.manifest_data().to_string(),
+            r#"[build-system]
+requires = ["hatchling"]
+build-backend = "hatchling.build"
+
+[project]
+name = "mock-project"
+version = "0.0.1"
+description = ""
+dependencies = [
+    "click ==8.1.7",
+]
+requires-python = "==3.11.*"
+
+[project.optional-dependencies]
+dev = [
+    "pytest",
+]
+"#
+        );
+    }
+
     #[test]
     fn test_init_app_project() {
         let dir = tempdir().unwrap();
@@ -262,7 +465,7 @@ mock-project = "mock_project.main:main"
         let test_package = Package::from_str("click==8.1.3").unwrap();
         let had_package = venv.contains_package(&test_package);
 
-        init_python_env(None, None, true, &options, &config).unwrap();
+        init_python_env(None, None, None, true, &options, &config).unwrap();
 
         assert!(!had_package);
         assert!(venv.contains_package(&test_package));
@@ -298,6 +501,7 @@ mock-project = "mock_project.main:main"
         init_python_env(
             None,
             Some(vec![String::from("dev")]),
+            None,
             true,
             &options,
             &config,
@@ -3,13 +3,13 @@ use huak_toolchain::{Channel, LocalTool, LocalToolchain};
 use pep508_rs::Requirement;
 
 use super::toolchain::{add_tool_to_toolchain, install_minimal_toolchain};
-use crate::{Config, Error, HuakResult};
+use crate::{Config, Error, HuakResult, InstallOptions};
 
 // TODO(cnpryer): <https://github.com/cnpryer/huak/issues/850>
 pub fn install(
     package: &Requirement,
     python_version: Option<RequestedVersion>,
-    _package_index_url: &str,
+    package_index_url: Option<&str>,
     config: &Config,
 ) -> HuakResult<()> {
     // TODO(cnpryer): Since we're treating the bin dir as a toolchain that'd mean Huak home is
@@ -47,5 +47,10 @@ pub fn install(
     let bin = LocalToolchain::new(home);
     let package = LocalTool::from_spec(package.name.clone(), package.to_string());
 
-    add_tool_to_toolchain(&package, &bin, config)
+    // The package index replaces the default index configured in `[tool.huak.repositories]`.
+    let options = InstallOptions {
+        values: package_index_url.map(|it| vec!["--index-url".to_string(), it.to_string()]),
+    };
+
+    add_tool_to_toolchain(&package, &bin, &options, config)
 }
@@ -0,0 +1,235 @@
+use crate::{lock::normalized_name, Config, DistributionLicense, Error, HuakResult, LicensePolicy};
+use std::{fmt::Display, str::FromStr};
+use toml_edit::{Item, Value};
+
+/// The formats `huak licenses` can print.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LicensesFormat {
+    #[default]
+    Table,
+    Csv,
+    Json,
+}
+
+impl FromStr for LicensesFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "table" => Ok(LicensesFormat::Table),
+            "csv" => Ok(LicensesFormat::Csv),
+            "json" => Ok(LicensesFormat::Json),
+            _ => Err(Error::InvalidLicensesFormat(s.to_string())),
+        }
+    }
+}
+
+impl Display for LicensesFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LicensesFormat::Table => write!(f, "table"),
+            LicensesFormat::Csv => write!(f, "csv"),
+            LicensesFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+pub struct LicensesOptions {
+    /// The format of the inventory.
+    pub format: LicensesFormat,
+}
+
+/// Display the licenses of the packages installed to the project's Python environment.
+///
+/// Licenses are checked against the SPDX identifiers allowed and denied in
+/// `[tool.huak.licenses]`:
+///
+/// ```toml
+/// [tool.huak.licenses]
+/// allow = ["MIT", "Apache-2.0", "BSD-3-Clause"]
+/// deny = ["GPL-3.0-only"]
+/// ```
+///
+/// Returns `Error::LicensePolicyViolation` if a package's license violates the policy.
+pub fn display_licenses(config: &Config, options: &LicensesOptions) -> HuakResult<()> {
+    let workspace = config.workspace();
+    let manifest = workspace.current_local_manifest()?;
+    let python_env = workspace.current_python_environment()?;
+    let policy = license_policy(
+        manifest
+            .manifest_data()
+            .huak_table()
+            .and_then(|it| it.get("licenses")),
+    )?;
+
+    // The project itself isn't a dependency.
+    let project_name = manifest
+        .manifest_data()
+        .project_name()
+        .map(|it| normalized_name(&it));
+    let licenses = python_env
+        .installed_distributions()?
+        .iter()
+        .filter(|it| Some(it.normalized_name()) != project_name)
+        .map(DistributionLicense::from_distribution)
+        .collect::<Vec<_>>();
+
+    let mut terminal = config.terminal();
+    match options.format {
+        LicensesFormat::Table => terminal.print_stdout(render_table(&licenses)),
+        LicensesFormat::Csv => terminal.print_stdout(render_csv(&licenses)),
+        LicensesFormat::Json => terminal.print_stdout(serde_json::to_string_pretty(&licenses)?),
+    }?;
+
+    let violations = licenses
+        .iter()
+        .filter_map(|it| policy.violation(it))
+        .collect::<Vec<_>>();
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::LicensePolicyViolation(violations.join(", ")))
+    }
+}
+
+/// Get the `LicensePolicy` from `[tool.huak.licenses]`.
+fn license_policy(table: Option<&Item>) -> HuakResult<LicensePolicy> {
+    let ids = |key: &str| -> HuakResult<Vec<String>> {
+        let Some(item) = table.and_then(|it| it.get(key)) else {
+            return Ok(Vec::new());
+        };
+
+        item.as_array()
+            .and_then(|it| {
+                it.iter()
+                    .map(|it| Value::as_str(it).map(ToString::to_string))
+                    .collect::<Option<Vec<_>>>()
+            })
+            .ok_or_else(|| {
+                Error::HuakConfigurationError(format!(
+                    "[tool.huak.licenses] {key} must be an array of SPDX license identifiers"
+                ))
+            })
+    };
+
+    Ok(LicensePolicy {
+        allow: ids("allow")?,
+        deny: ids("deny")?,
+    })
+}
+
+fn rows(licenses: &[DistributionLicense]) -> Vec<[String; 3]> {
+    let mut rows = vec![[
+        "Package".to_string(),
+        "Version".to_string(),
+        "License".to_string(),
+    ]];
+    for it in licenses {
+        rows.push([it.name.clone(), it.version.clone(), it.license.clone()]);
+    }
+
+    rows
+}
+
+fn render_table(licenses: &[DistributionLicense]) -> String {
+    let rows = rows(licenses);
+
+    let mut widths = [0; 3];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{cell:width$}"))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render the licenses as CSV (see <https://www.rfc-editor.org/rfc/rfc4180>).
+fn render_csv(licenses: &[DistributionLicense]) -> String {
+    rows(licenses)
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|cell| {
+                    if cell.contains([',', '"', '\n']) {
+                        format!("\"{}\"", cell.replace('"', "\"\""))
+                    } else {
+                        cell.clone()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mock_licenses() -> Vec<DistributionLicense> {
+        [
+            ("click", "8.1.7", "BSD-3-Clause"),
+            ("xlcsv", "0.1.0", "BSD License, MIT License"),
+        ]
+        .into_iter()
+        .map(|(name, version, license)| DistributionLicense {
+            name: name.to_string(),
+            version: version.to_string(),
+            license: license.to_string(),
+            expression: None,
+            license_text: None,
+            classifiers: Vec::new(),
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_render_licenses() {
+        let licenses = mock_licenses();
+
+        assert_eq!(
+            render_table(&licenses),
+            r"Package  Version  License
+click    8.1.7    BSD-3-Clause
+xlcsv    0.1.0    BSD License, MIT License"
+        );
+        assert_eq!(
+            render_csv(&licenses),
+            r#"Package,Version,License
+click,8.1.7,BSD-3-Clause
+xlcsv,0.1.0,"BSD License, MIT License""#
+        );
+    }
+
+    #[test]
+    fn test_license_policy() {
+        let doc = "[licenses]\nallow = [\"MIT\"]\ndeny = [\"GPL-3.0-only\"]\n"
+            .parse::<toml_edit::Document>()
+            .unwrap();
+        assert_eq!(
+            license_policy(doc.get("licenses")).unwrap(),
+            LicensePolicy {
+                allow: vec!["MIT".to_string()],
+                deny: vec!["GPL-3.0-only".to_string()]
+            }
+        );
+
+        let doc = "[licenses]\ndeny = \"GPL-3.0-only\"\n"
+            .parse::<toml_edit::Document>()
+            .unwrap();
+        assert!(license_policy(doc.get("licenses")).is_err());
+    }
+}
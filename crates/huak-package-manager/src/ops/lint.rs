@@ -1,4 +1,4 @@
-use super::add_venv_to_command;
+use super::{add_tools_to_manifest, add_venv_to_command, install_tools};
 use crate::{Config, Dependency, HuakResult, InstallOptions};
 use std::{process::Command, str::FromStr};
 
@@ -18,7 +18,13 @@ pub fn lint_project(config: &Config, options: &LintOptions) -> HuakResult<()> {
     let ruff_dep = Dependency::from_str("ruff")?;
     let mut lint_deps = vec![ruff_dep.clone()];
     if !python_env.contains_module("ruff")? {
-        python_env.install_packages(&[&ruff_dep], &options.install_options, config)?;
+        install_tools(
+            &[&ruff_dep],
+            &manifest,
+            &python_env,
+            &options.install_options,
+            config,
+        )?;
     }
 
     let mut terminal = config.terminal();
@@ -27,7 +33,13 @@ pub fn lint_project(config: &Config, options: &LintOptions) -> HuakResult<()> {
         // Install `mypy` if it isn't already installed.
         let mypy_dep = Dependency::from_str("mypy")?;
         if !python_env.contains_module("mypy")? {
-            python_env.install_packages(&[&mypy_dep], &options.install_options, config)?;
+            install_tools(
+                &[&mypy_dep],
+                &manifest,
+                &python_env,
+                &options.install_options,
+                config,
+            )?;
         }
 
         // Keep track of the fact that `mypy` is a needed lint dep.
@@ -53,32 +65,13 @@ pub fn lint_project(config: &Config, options: &LintOptions) -> HuakResult<()> {
     terminal.run_command(&mut cmd)?;
 
     // Add installed lint deps (potentially both `mypy` and `ruff`) to manifest file if not already there.
-    let new_lint_deps = lint_deps
-        .iter()
-        .filter(|dep| {
-            !manifest
-                .manifest_data()
-                .contains_project_dependency_any(dep.name())
-        })
-        .map(Dependency::name)
-        .collect::<Vec<_>>();
-
-    if !new_lint_deps.is_empty() {
-        for pkg in python_env
-            .installed_packages()?
-            .iter()
-            .filter(|pkg| new_lint_deps.contains(&pkg.name()))
-        {
-            manifest
-                .manifest_data_mut()
-                .add_project_optional_dependency(&pkg.to_string(), "dev");
-        }
-    }
-
-    manifest.manifest_data_mut().formatted();
-    manifest.write_file()?;
-
-    Ok(())
+    add_tools_to_manifest(
+        &lint_deps.iter().collect::<Vec<_>>(),
+        &mut manifest,
+        &python_env,
+        &options.install_options,
+        config,
+    )
 }
 
 #[cfg(test)]
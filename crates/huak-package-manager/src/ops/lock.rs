@@ -0,0 +1,323 @@
+use crate::{
+    lock::{
+        index_url_from_args, lock_file_path, lockfile_from_report, manifest_content_hash,
+        manifest_dependency_groups,
+    },
+    Config, Error, HuakResult, InstallOptions, LocalManifest, LockedPackage, Lockfile,
+    PackageSources, PythonEnvironment, Resolver,
+};
+use pep440_rs::Version;
+use std::{collections::HashMap, str::FromStr};
+
+pub struct LockOptions {
+    pub install_options: InstallOptions,
+}
+
+/// Resolve the project's dependencies and write them to the lock file (huak.lock).
+///
+/// If the `Config` is locked then the lock file is only checked.
+pub fn lock_project(config: &Config, options: &LockOptions) -> HuakResult<()> {
+    let workspace = config.workspace();
+    let manifest = workspace.current_local_manifest()?;
+
+    if config.is_locked() {
+        return required_lock_file(&manifest, config).map(|_| ());
+    }
+
+    let python_env = workspace.resolve_python_environment()?;
+
+    update_lock_file(&manifest, &python_env, &options.install_options, config)
+}
+
+/// Rewrite the lock file for a `LocalManifest` using the `PythonEnvironment` to resolve
+/// its dependencies.
+pub(crate) fn update_lock_file(
+    manifest: &LocalManifest,
+    python_env: &PythonEnvironment,
+    options: &InstallOptions,
+    config: &Config,
+) -> HuakResult<()> {
+    let groups = manifest_dependency_groups(manifest.manifest_data(), manifest_root(manifest))?;
+    let path = lock_file_path(manifest_root(manifest));
+
+    let mut requirements = groups
+        .values()
+        .flatten()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    requirements.sort();
+    requirements.dedup();
+
+    let content_hash = manifest_content_hash(manifest.manifest_data())?;
+
+    if requirements.is_empty() {
+        return Lockfile::new(Vec::new())
+            .with_content_hash(content_hash)
+            .write(path);
+    }
+
+    let report = python_env.installation_report(&requirements, options, config)?;
+    let python_version = Version::from_str(&python_env.python_version().to_string())
+        .map_err(Error::InvalidVersionString)?;
+    let sources =
+        PackageSources::from_config(config, index_url_from_args(options.values.as_ref()))?;
+    let resolver = Resolver::new(sources.resolver_options(python_env.marker_environment()?))?;
+    let lockfile = lockfile_from_report(&report, &groups, &python_version, Some(&sources))
+        .with_release_files(&resolver)?
+        .with_content_hash(content_hash);
+
+    lockfile.write(path)
+}
+
+/// Get the `Lockfile` for a `LocalManifest` if one exists and it's up to date with
+/// the manifest's dependencies.
+///
+/// The lock file is up to date if its content hash matches the manifest's requirements
+/// and `requires-python`.
+pub(crate) fn current_lock_file(manifest: &LocalManifest) -> HuakResult<Option<Lockfile>> {
+    let path = lock_file_path(manifest_root(manifest));
+
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let lockfile = Lockfile::read(path)?;
+    let content_hash = manifest_content_hash(manifest.manifest_data())?;
+    let up_to_date = lockfile.content_hash() == Some(content_hash.as_str());
+
+    Ok(up_to_date.then_some(lockfile))
+}
+
+/// Get the `Lockfile` required by a locked `Config`.
+///
+/// With `frozen` the lock file is used as-is. Otherwise it must be up to date with the
+/// `LocalManifest`.
+pub(crate) fn required_lock_file(
+    manifest: &LocalManifest,
+    config: &Config,
+) -> HuakResult<Lockfile> {
+    let path = lock_file_path(manifest_root(manifest));
+
+    if !path.exists() {
+        return Err(Error::LockFileNotFound);
+    }
+
+    if config.frozen {
+        return Lockfile::read(path);
+    }
+
+    current_lock_file(manifest)?.ok_or(Error::LockFileOutdated)
+}
+
+/// Get the `Lockfile` for a `LocalManifest`. If the lock file doesn't exist or is out of date
+/// then it's updated first unless the `Config` is locked.
+pub(crate) fn resolve_lock_file(
+    manifest: &LocalManifest,
+    python_env: &PythonEnvironment,
+    options: &InstallOptions,
+    config: &Config,
+) -> HuakResult<Lockfile> {
+    if config.is_locked() {
+        return required_lock_file(manifest, config);
+    }
+
+    if let Some(lockfile) = current_lock_file(manifest)? {
+        return Ok(lockfile);
+    }
+
+    update_lock_file(manifest, python_env, options, config)?;
+
+    Lockfile::read(lock_file_path(manifest_root(manifest)))
+}
+
+/// Install the `LockedPackage`s from a `Lockfile`. If `groups` is `None` then all
+/// packages are installed.
+pub(crate) fn install_lock_file(
+    lockfile: &Lockfile,
+    groups: Option<&[String]>,
+    python_env: &PythonEnvironment,
+    options: &InstallOptions,
+    config: &Config,
+) -> HuakResult<()> {
+    let packages = match groups {
+        Some(gs) => lockfile.packages_in_groups(gs).collect::<Vec<_>>(),
+        None => lockfile.packages().iter().collect(),
+    };
+
+    install_locked_packages(&packages, python_env, options, config)
+}
+
+/// Install `LockedPackage`s pinned to their locked versions or direct references.
+///
+/// Distributions are checked against the hashes locked for them.
+pub(crate) fn install_locked_packages(
+    packages: &[&LockedPackage],
+    python_env: &PythonEnvironment,
+    options: &InstallOptions,
+    config: &Config,
+) -> HuakResult<()> {
+    if packages.is_empty() {
+        return Ok(());
+    }
+
+    let requirements = packages
+        .iter()
+        .map(|pkg| pkg.requirement_line(false))
+        .collect::<Vec<_>>();
+    let hashes = packages
+        .iter()
+        .map(|pkg| (pkg.name.clone(), pkg.artifact_hashes()))
+        .collect::<HashMap<_, _>>();
+
+    python_env.install_locked(&requirements, &hashes, options, config)
+}
+
+pub(super) fn manifest_root(manifest: &LocalManifest) -> &std::path::Path {
+    manifest
+        .path()
+        .parent()
+        .expect("manifest file should have a parent directory")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{copy_dir, CopyDirOptions, LockedPackage};
+    use huak_dev::dev_resources_dir;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_current_lock_file() {
+        let dir = tempdir().unwrap();
+        copy_dir(
+            &dev_resources_dir().join("mock-project"),
+            &dir.path().join("mock-project"),
+            &CopyDirOptions::default(),
+        )
+        .unwrap();
+        let root = dir.path().join("mock-project");
+        let manifest = LocalManifest::new(root.join("pyproject.toml")).unwrap();
+        let locked = |name: &str, version: &str, group: &str| LockedPackage {
+            name: name.to_string(),
+            version: version.to_string(),
+            index: None,
+            url: None,
+            hashes: Vec::new(),
+            markers: None,
+            groups: vec![group.to_string()],
+            dependencies: Vec::new(),
+            files: Vec::new(),
+        };
+
+        assert!(current_lock_file(&manifest).unwrap().is_none());
+
+        let packages = vec![
+            locked("click", "8.1.7", "required"),
+            locked("pytest", "7.4.3", "dev"),
+            locked("ruff", "0.1.5", "dev"),
+        ];
+        Lockfile::new(packages.clone())
+            .write(root.join("huak.lock"))
+            .unwrap();
+
+        assert!(current_lock_file(&manifest).unwrap().is_none());
+
+        let content_hash = manifest_content_hash(manifest.manifest_data()).unwrap();
+        Lockfile::new(packages)
+            .with_content_hash(content_hash)
+            .write(root.join("huak.lock"))
+            .unwrap();
+
+        assert!(current_lock_file(&manifest).unwrap().is_some());
+
+        // Changing a requirement's specifier makes the lock file outdated.
+        let mut manifest = manifest;
+        manifest
+            .manifest_data_mut()
+            .remove_project_dependency("click")
+            .add_project_dependency("click<8");
+        manifest.write_file().unwrap();
+        let manifest = LocalManifest::new(root.join("pyproject.toml")).unwrap();
+
+        assert!(current_lock_file(&manifest).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_manifest_content_hash() {
+        let hash = |toml: &str| {
+            let manifest = huak_pyproject_toml::PyProjectToml::from_str(toml).unwrap();
+            manifest_content_hash(&manifest).unwrap()
+        };
+        let base = hash(
+            r#"[project]
+name = "mock"
+dependencies = ["click>=8", "Ruff"]
+"#,
+        );
+
+        assert_eq!(
+            base,
+            hash(
+                r#"[project]
+name = "mock"
+dependencies = ["ruff", "click >= 8"]
+"#
+            )
+        );
+        assert_ne!(
+            base,
+            hash(
+                r#"[project]
+name = "mock"
+dependencies = ["click<8", "ruff"]
+"#
+            )
+        );
+        assert_ne!(
+            base,
+            hash(
+                r#"[project]
+name = "mock"
+requires-python = ">=3.12"
+dependencies = ["click>=8", "ruff"]
+"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_required_lock_file() {
+        let dir = tempdir().unwrap();
+        copy_dir(
+            &dev_resources_dir().join("mock-project"),
+            &dir.path().join("mock-project"),
+            &CopyDirOptions::default(),
+        )
+        .unwrap();
+        let root = dir.path().join("mock-project");
+        let manifest = LocalManifest::new(root.join("pyproject.toml")).unwrap();
+        let locked = Config {
+            locked: true,
+            ..Default::default()
+        };
+        let frozen = Config {
+            frozen: true,
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            required_lock_file(&manifest, &locked),
+            Err(Error::LockFileNotFound)
+        ));
+
+        Lockfile::new(Vec::new())
+            .write(root.join("huak.lock"))
+            .unwrap();
+
+        assert!(matches!(
+            required_lock_file(&manifest, &locked),
+            Err(Error::LockFileOutdated)
+        ));
+        assert!(required_lock_file(&manifest, &frozen).is_ok());
+    }
+}
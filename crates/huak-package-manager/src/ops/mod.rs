@@ -1,45 +1,68 @@
 mod activate;
 mod add;
+mod audit;
 mod build;
+mod cache;
 mod clean;
+mod export;
 mod format;
 mod init;
 mod install;
+mod licenses;
 mod lint;
+mod lock;
 mod new;
+mod outdated;
 mod publish;
 mod python;
 mod remove;
 mod run;
+mod sync;
 mod test;
 mod toolchain;
+mod tree;
 mod update;
 mod version;
+mod why;
 
 use crate::{
-    default_python_gitignore, env_path_values, git_init, Error, HuakResult, PythonEnvironment,
+    default_python_gitignore, env_path_values, git_init, Config, Dependency, Error, HuakResult,
+    InstallOptions, LocalManifest, PythonEnvironment,
 };
 pub use activate::activate_python_environment;
-pub use add::{add_project_dependencies, add_project_optional_dependencies, AddOptions};
+pub use add::{
+    add_dependency_group_dependencies, add_project_dependencies, add_project_optional_dependencies,
+    AddOptions,
+};
+pub use audit::{audit_project, AuditOptions};
 pub use build::{build_project, BuildOptions};
+pub use cache::{clean_cache, display_cache_dir, display_cache_info, prune_cache};
 pub use clean::{clean_project, CleanOptions};
+pub use export::{export_project, ExportFormat, ExportOptions};
 pub use format::{format_project, FormatOptions};
-pub use init::{init_app_project, init_lib_project, init_python_env};
+pub use init::{import_dependencies, init_app_project, init_lib_project, init_python_env};
 pub use install::install;
+pub use licenses::{display_licenses, LicensesFormat, LicensesOptions};
 pub use lint::{lint_project, LintOptions};
+use lock::{install_locked_packages, required_lock_file, update_lock_file};
+pub use lock::{lock_project, LockOptions};
 pub use new::{new_app_project, new_lib_project};
+pub use outdated::{display_outdated_dependencies, OutdatedDependency, OutdatedOptions};
 pub use publish::{publish_project, PublishOptions};
 pub use python::{install_python, list_python, use_python};
 pub use remove::{remove_project_dependencies, RemoveOptions};
 pub use run::run_command_str;
 use std::{path::PathBuf, process::Command};
+pub use sync::{sync_project, SyncOptions};
 pub use test::{test_project, TestOptions};
 pub use toolchain::{
     add_tool, install_toolchain, list_toolchains, remove_tool, run_tool, toolchain_info,
     uninstall_toolchain, update_toolchain, use_toolchain,
 };
-pub use update::{update_project_dependencies, UpdateOptions};
+pub use tree::{display_dependency_tree, TreeOptions};
+pub use update::{update_project_dependencies, UpdateOptions, UpdateStrategy};
 pub use version::display_project_version;
+pub use why::display_dependency_paths;
 
 const DEFAULT_PYTHON_INIT_FILE_CONTENTS: &str = r#"__version__ = "0.0.1"
 "#;
@@ -56,20 +79,109 @@ if __name__ == "__main__":
 /// - Adds the virtual environment's executables directory path to the top of the command's
 ///   `PATH` environment variable.
 /// - Adds `VIRTUAL_ENV` environment variable to the command pointing at the virtual environment's
-///   root.
+///   root. conda environments get `CONDA_PREFIX` instead.
 fn add_venv_to_command(cmd: &mut Command, venv: &PythonEnvironment) -> HuakResult<()> {
     let mut paths = env_path_values().unwrap_or_default();
 
     paths.insert(0, venv.executables_dir_path().clone());
+    // conda environments on Windows have their interpreter outside of the executables dir.
+    if let Some(dir) = venv.python_path().parent() {
+        if dir != venv.executables_dir_path() {
+            paths.insert(0, dir.to_path_buf());
+        }
+    }
     cmd.env(
         "PATH",
         std::env::join_paths(paths).map_err(|e| Error::InternalError(e.to_string()))?,
-    )
-    .env("VIRTUAL_ENV", venv.root());
+    );
+
+    if venv.is_conda() {
+        cmd.env("CONDA_PREFIX", venv.root());
+    } else if !venv.is_system() {
+        cmd.env("VIRTUAL_ENV", venv.root());
+    }
 
     Ok(())
 }
 
+/// Check if the `Config` allows the manifest file to be modified.
+fn ensure_manifest_writable(config: &Config) -> HuakResult<()> {
+    if config.is_locked() {
+        Err(Error::ManifestFileLocked)
+    } else {
+        Ok(())
+    }
+}
+
+/// Install tools like `pytest` or `ruff` to a `PythonEnvironment`.
+///
+/// If the `Config` is locked then the tools and their dependencies are installed from the
+/// lock file. An error is returned if they aren't locked.
+fn install_tools(
+    deps: &[&Dependency],
+    manifest: &LocalManifest,
+    python_env: &PythonEnvironment,
+    options: &InstallOptions,
+    config: &Config,
+) -> HuakResult<()> {
+    if !config.is_locked() {
+        return python_env.install_packages(deps, options, config);
+    }
+
+    let lockfile = required_lock_file(manifest, config)?;
+    let names = deps.iter().map(|dep| dep.name()).collect::<Vec<_>>();
+    let Some(packages) = lockfile.packages_with_dependencies(&names) else {
+        return Err(Error::PythonEnvironmentLocked(format!(
+            "{} must be locked to be installed",
+            names.join(", ")
+        )));
+    };
+
+    install_locked_packages(&packages, python_env, options, config)
+}
+
+/// Add installed tools to the manifest file's "dev" group if they aren't already listed.
+///
+/// The lock file is updated if the manifest file is modified.
+fn add_tools_to_manifest(
+    deps: &[&Dependency],
+    manifest: &mut LocalManifest,
+    python_env: &PythonEnvironment,
+    options: &InstallOptions,
+    config: &Config,
+) -> HuakResult<()> {
+    let new_deps = deps
+        .iter()
+        .filter(|dep| {
+            !manifest
+                .manifest_data()
+                .contains_project_dependency_any(dep.name())
+        })
+        .map(|dep| dep.name())
+        .collect::<Vec<_>>();
+
+    if new_deps.is_empty() {
+        return Ok(());
+    }
+
+    ensure_manifest_writable(config)?;
+
+    for pkg in python_env
+        .installed_packages()?
+        .iter()
+        .filter(|pkg| new_deps.contains(&pkg.name()))
+    {
+        manifest
+            .manifest_data_mut()
+            .add_project_optional_dependency(&pkg.to_string(), "dev");
+    }
+
+    manifest.manifest_data_mut().formatted();
+    manifest.write_file()?;
+
+    update_lock_file(manifest, python_env, options, config)
+}
+
 /// Create a workspace directory on the system.
 fn create_workspace<T: Into<PathBuf>>(path: T) -> HuakResult<()> {
     let root = path.into();
@@ -100,3 +212,57 @@ fn init_git<T: Into<PathBuf>>(path: T) -> HuakResult<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{collections::HashMap, ffi::OsStr};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_add_venv_to_command() {
+        let dir = tempdir().unwrap();
+        let venv_root = dir.path().join(".venv");
+        std::fs::create_dir_all(&venv_root).unwrap();
+        std::fs::write(venv_root.join("pyvenv.cfg"), "version = 3.11.7\n").unwrap();
+        let conda_root = dir.path().join("my-env");
+        std::fs::create_dir_all(conda_root.join("conda-meta")).unwrap();
+        std::fs::write(
+            conda_root
+                .join("conda-meta")
+                .join("python-3.11.7-h955ad1f_0.json"),
+            "{}",
+        )
+        .unwrap();
+        #[cfg(unix)]
+        let python_path = conda_root.join("bin").join("python");
+        #[cfg(windows)]
+        let python_path = conda_root.join("python.exe");
+        std::fs::create_dir_all(python_path.parent().unwrap()).unwrap();
+        std::fs::write(&python_path, "").unwrap();
+        let command_env = |venv: &PythonEnvironment| {
+            let mut cmd = Command::new("python");
+            add_venv_to_command(&mut cmd, venv).unwrap();
+            cmd.get_envs()
+                .filter_map(|(key, value)| Some((key.to_owned(), value?.to_owned())))
+                .collect::<HashMap<_, _>>()
+        };
+
+        let venv = command_env(&PythonEnvironment::new(&venv_root).unwrap());
+        let conda = command_env(&PythonEnvironment::new(&conda_root).unwrap());
+
+        assert_eq!(
+            venv.get(OsStr::new("VIRTUAL_ENV")),
+            Some(&venv_root.into_os_string())
+        );
+        assert!(!venv.contains_key(OsStr::new("CONDA_PREFIX")));
+        assert_eq!(
+            conda.get(OsStr::new("CONDA_PREFIX")),
+            Some(&conda_root.into_os_string())
+        );
+        assert!(!conda.contains_key(OsStr::new("VIRTUAL_ENV")));
+        assert!(std::env::split_paths(&conda[OsStr::new("PATH")])
+            .next()
+            .is_some_and(|it| it == python_path.parent().unwrap()));
+    }
+}
@@ -1,6 +1,6 @@
 use toml_edit::{Item, Table};
 
-use super::{create_workspace, init_git};
+use super::{create_workspace, ensure_manifest_writable, init_git};
 use crate::{
     default_package_test_file_contents, importable_package_name, last_path_component, Config,
     Dependency, Error, HuakResult, LocalManifest, WorkspaceOptions,
@@ -47,6 +47,8 @@ pub fn new_lib_project(config: &Config, options: &WorkspaceOptions) -> HuakResul
         Err(_) => LocalManifest::template(workspace.root().join("pyproject.toml")),
     };
 
+    ensure_manifest_writable(config)?;
+
     create_workspace(workspace.root())?;
 
     if options.uses_git {
@@ -0,0 +1,287 @@
+use super::lock::manifest_root;
+use crate::{
+    dependency::requirement_string,
+    lock::{manifest_dependency_groups, normalized_name, REQUIRED_GROUP_NAME},
+    Config, Error, HuakResult, InstalledDistribution, OutputFormat, PackageSources, Resolver,
+};
+use pep440_rs::{Version, VersionSpecifier};
+use pep508_rs::{Requirement, VersionOrUrl};
+use serde::Serialize;
+use std::{collections::BTreeMap, str::FromStr};
+use termcolor::Color;
+
+pub struct OutdatedOptions {
+    /// The format of the report.
+    pub format: OutputFormat,
+    /// The URL of the package index to check. Defaults to pypi.org.
+    pub index_url: Option<String>,
+}
+
+/// A dependency with a newer version available than the one installed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct OutdatedDependency {
+    /// The name of the dependency as it's listed in the manifest.
+    pub name: String,
+    /// The dependency group the dependency is listed in.
+    pub group: String,
+    /// The dependency's requirement without markers.
+    pub requirement: String,
+    /// The installed version, if the dependency is installed.
+    pub installed: Option<String>,
+    /// The latest version that satisfies the requirement.
+    pub compatible: Option<String>,
+    /// The latest version available. `None` if the dependency couldn't be found on the
+    /// package index.
+    pub latest: Option<String>,
+}
+
+/// Display the project's dependencies with newer versions available on the package index.
+///
+/// Every dependency listed in `[project.dependencies]`, `[project.optional-dependencies]` and
+/// `[dependency-groups]` is compared against the latest version satisfying its requirement and
+/// the latest version available for the current Python environment. Direct references are
+/// skipped. Dependencies that can't be found on the package index (for example local or
+/// private packages) are reported with an unknown latest version.
+pub fn display_outdated_dependencies(config: &Config, options: &OutdatedOptions) -> HuakResult<()> {
+    let workspace = config.workspace();
+    let manifest = workspace.current_local_manifest()?;
+    let python_env = workspace.current_python_environment()?;
+    let sources = PackageSources::from_config(config, options.index_url.as_deref())?;
+    let resolver = Resolver::new(sources.resolver_options(python_env.marker_environment()?))?;
+
+    let outdated = outdated_dependencies(
+        &manifest_dependency_groups(manifest.manifest_data(), manifest_root(&manifest))?,
+        &python_env.installed_distributions()?,
+        &resolver,
+    )?;
+    let mut terminal = config.terminal();
+
+    match options.format {
+        OutputFormat::Json => terminal.print_stdout(serde_json::to_string_pretty(&outdated)?),
+        OutputFormat::Table if outdated.is_empty() => {
+            terminal.print_custom("Success", "dependencies are up to date", Color::Green, true)
+        }
+        OutputFormat::Table => terminal.print_stdout(render_table(&outdated)),
+    }
+}
+
+/// Get the `OutdatedDependency`s for each group of requirements. Dependencies the index
+/// doesn't have are reported without a latest version.
+fn outdated_dependencies(
+    groups: &BTreeMap<String, Vec<Requirement>>,
+    installed: &[InstalledDistribution],
+    resolver: &Resolver,
+) -> HuakResult<Vec<OutdatedDependency>> {
+    let installed = installed
+        .iter()
+        .map(|it| (it.normalized_name(), it.version()))
+        .collect::<BTreeMap<_, _>>();
+    let mut outdated = Vec::new();
+
+    // Required dependencies are listed first.
+    let mut groups = groups.iter().collect::<Vec<_>>();
+    groups.sort_by_key(|(group, _)| (group.as_str() != REQUIRED_GROUP_NAME, group.as_str()));
+
+    for (group, reqs) in groups {
+        for req in reqs {
+            let specifiers = match req.version_or_url.as_ref() {
+                Some(VersionOrUrl::Url(_)) => continue,
+                Some(VersionOrUrl::VersionSpecifier(it)) => it.iter().collect::<Vec<_>>(),
+                None => Vec::new(),
+            };
+            let installed_version = installed
+                .get(&normalized_name(&req.name))
+                .and_then(|it| Version::from_str(it).ok());
+
+            // Pre-releases are only reported if they're requested or already installed.
+            let prereleases = specifiers.iter().any(|it| it.any_prerelease())
+                || installed_version
+                    .as_ref()
+                    .is_some_and(Version::any_prerelease);
+            let versions = match resolver.versions(&req.name) {
+                Ok(it) => it,
+                Err(Error::PackageNotFound(_)) => Vec::new(),
+                Err(e) => return Err(e),
+            };
+            let versions = versions
+                .into_iter()
+                .filter(|it| prereleases || !it.any_prerelease())
+                .collect::<Vec<_>>();
+
+            let Some(latest) = versions.first() else {
+                outdated.push(OutdatedDependency {
+                    name: req.name.clone(),
+                    group: group.clone(),
+                    requirement: requirement_string(req),
+                    installed: installed_version.map(|it| it.to_string()),
+                    compatible: None,
+                    latest: None,
+                });
+                continue;
+            };
+            if installed_version.as_ref().is_some_and(|it| it >= latest) {
+                continue;
+            }
+            let compatible = versions.iter().find(|version| {
+                specifiers
+                    .iter()
+                    .all(|it| VersionSpecifier::contains(it, version))
+            });
+
+            outdated.push(OutdatedDependency {
+                name: req.name.clone(),
+                group: group.clone(),
+                requirement: requirement_string(req),
+                installed: installed_version.map(|it| it.to_string()),
+                compatible: compatible.map(ToString::to_string),
+                latest: Some(latest.to_string()),
+            });
+        }
+    }
+
+    Ok(outdated)
+}
+
+fn render_table(outdated: &[OutdatedDependency]) -> String {
+    let mut rows = vec![[
+        "Package".to_string(),
+        "Group".to_string(),
+        "Requirement".to_string(),
+        "Installed".to_string(),
+        "Compatible".to_string(),
+        "Latest".to_string(),
+    ]];
+    for it in outdated {
+        rows.push([
+            it.name.clone(),
+            it.group.clone(),
+            it.requirement.clone(),
+            it.installed.clone().unwrap_or_else(|| "-".to_string()),
+            it.compatible.clone().unwrap_or_else(|| "-".to_string()),
+            it.latest.clone().unwrap_or_else(|| "unknown".to_string()),
+        ]);
+    }
+
+    let mut widths = [0; 6];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{cell:width$}"))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        installed::write_mock_distributions, installed_distributions,
+        python_environment::mock_marker_environment, resolve::write_mock_index, CredentialProvider,
+        ResolverOptions,
+    };
+    use std::collections::HashMap;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_outdated_dependencies() {
+        let dir = tempdir().unwrap();
+        write_mock_index(
+            dir.path(),
+            &[
+                (
+                    "click",
+                    &[("7.1.2", &[]), ("8.1.7", &[]), ("9.0.0a1", &[])],
+                    true,
+                ),
+                ("pytest", &[("7.4.3", &[]), ("8.0.0", &[])], false),
+                ("ruff", &[("0.1.5", &[])], true),
+                ("xlcsv", &[("0.1.0", &[]), ("0.2.0", &[])], true),
+            ],
+        );
+        let site_packages = dir.path().join("site-packages");
+        write_mock_distributions(
+            &site_packages,
+            &[
+                ("click", "7.1.2", &[]),
+                ("pytest", "7.4.3", &[]),
+                ("ruff", "0.1.5", &[]),
+            ],
+        );
+        let groups = [
+            ("required", vec!["click", "xlcsv", "private-pkg"]),
+            ("dev", vec!["pytest<8", "ruff"]),
+        ]
+        .into_iter()
+        .map(|(group, reqs)| {
+            (
+                group.to_string(),
+                reqs.iter()
+                    .map(|it| Requirement::from_str(it).unwrap())
+                    .collect(),
+            )
+        })
+        .collect();
+        let resolver = Resolver::new(ResolverOptions {
+            index_urls: vec![dir.path().join("simple").display().to_string()],
+            find_links: Vec::new(),
+            package_indexes: HashMap::new(),
+            credentials: CredentialProvider::default(),
+            marker_environment: mock_marker_environment(),
+            prereleases: false,
+            offline: false,
+        })
+        .unwrap();
+
+        let outdated = outdated_dependencies(
+            &groups,
+            &installed_distributions(&site_packages).unwrap(),
+            &resolver,
+        )
+        .unwrap();
+
+        assert_eq!(
+            render_table(&outdated),
+            r"Package      Group     Requirement  Installed  Compatible  Latest
+click        required  click        7.1.2      8.1.7       8.1.7
+xlcsv        required  xlcsv        -          0.2.0       0.2.0
+private-pkg  required  private-pkg  -          -           unknown
+pytest       dev       pytest<8     7.4.3      7.4.3       8.0.0"
+        );
+    }
+
+    #[test]
+    fn test_outdated_dependencies_index_error() {
+        let dir = tempdir().unwrap();
+        let project = dir.path().join("simple").join("click");
+        std::fs::create_dir_all(&project).unwrap();
+        std::fs::write(project.join("index.json"), "not json").unwrap();
+        let groups = BTreeMap::from([(
+            REQUIRED_GROUP_NAME.to_string(),
+            vec![Requirement::from_str("click").unwrap()],
+        )]);
+        let resolver = Resolver::new(ResolverOptions {
+            index_urls: vec![dir.path().join("simple").display().to_string()],
+            find_links: Vec::new(),
+            package_indexes: HashMap::new(),
+            credentials: CredentialProvider::default(),
+            marker_environment: mock_marker_environment(),
+            prereleases: false,
+            offline: false,
+        })
+        .unwrap();
+
+        assert!(outdated_dependencies(&groups, &[], &resolver).is_err());
+    }
+}
@@ -1,6 +1,11 @@
-use super::add_venv_to_command;
-use crate::{Config, Dependency, HuakResult, InstallOptions};
+use super::{add_tools_to_manifest, add_venv_to_command, install_tools};
+use crate::{Config, Credentials, Dependency, Error, HuakResult, InstallOptions, PackageSources};
 use std::{process::Command, str::FromStr};
+use url::Url;
+
+/// The name credentials for the default repository are looked up with.
+const PYPI_REPOSITORY_NAME: &str = "pypi";
+const PYPI_UPLOAD_URL: &str = "https://upload.pypi.org/legacy/";
 
 pub struct PublishOptions {
     /// A values vector of publish options typically used for passing on arguments.
@@ -9,6 +14,10 @@ pub struct PublishOptions {
 }
 
 pub fn publish_project(config: &Config, options: &PublishOptions) -> HuakResult<()> {
+    if config.offline {
+        return Err(Error::OfflineNetworkRequest("publishing".to_string()));
+    }
+
     let workspace = config.workspace();
     let mut manifest = workspace.current_local_manifest()?;
     let python_env = workspace.resolve_python_environment()?;
@@ -16,27 +25,23 @@ pub fn publish_project(config: &Config, options: &PublishOptions) -> HuakResult<
     // Install `twine` if it isn't already installed.
     let pub_dep = Dependency::from_str("twine")?;
     if !python_env.contains_module(pub_dep.name())? {
-        python_env.install_packages(&[&pub_dep], &options.install_options, config)?;
+        install_tools(
+            &[&pub_dep],
+            &manifest,
+            &python_env,
+            &options.install_options,
+            config,
+        )?;
     }
 
     // Add the installed `twine` package to the manifest file if it isn't already there.
-    if !manifest
-        .manifest_data()
-        .contains_project_dependency_any(pub_dep.name())
-    {
-        for pkg in python_env
-            .installed_packages()?
-            .iter()
-            .filter(|pkg| pkg.name() == pub_dep.name())
-        {
-            manifest
-                .manifest_data_mut()
-                .add_project_optional_dependency(&pkg.to_string(), "dev");
-        }
-    }
-
-    manifest.manifest_data_mut().formatted();
-    manifest.write_file()?;
+    add_tools_to_manifest(
+        &[&pub_dep],
+        &mut manifest,
+        &python_env,
+        &options.install_options,
+        config,
+    )?;
 
     // Run `twine`.
     let mut cmd = Command::new(python_env.python_path());
@@ -45,6 +50,54 @@ pub fn publish_project(config: &Config, options: &PublishOptions) -> HuakResult<
         args.extend(v.iter().map(String::as_str));
     }
     add_venv_to_command(&mut cmd, &python_env)?;
+
+    // Credentials are passed to `twine` with its environment variables unless they're
+    // already provided.
+    let has_username = std::env::var_os("TWINE_USERNAME").is_some()
+        || args
+            .iter()
+            .any(|it| *it == "-u" || it.starts_with("--username"));
+    if !has_username {
+        let sources = PackageSources::from_config(config, None)?;
+        if let Some(it) = upload_credentials(options.values.as_ref(), &sources) {
+            cmd.env("TWINE_USERNAME", it.username)
+                .env("TWINE_PASSWORD", it.password);
+        }
+    }
+
     cmd.args(args).current_dir(workspace.root());
     config.terminal().run_command(&mut cmd)
 }
+
+/// Get the `Credentials` for the repository `twine` uploads to. The repository is either
+/// passed with `--repository-url`, named with `--repository`, or the default repository.
+fn upload_credentials(
+    values: Option<&Vec<String>>,
+    sources: &PackageSources,
+) -> Option<Credentials> {
+    let mut name = PYPI_REPOSITORY_NAME;
+    let mut url = None;
+    let mut iter = values.into_iter().flatten();
+
+    while let Some(it) = iter.next() {
+        if let Some(value) = it.strip_prefix("--repository-url=") {
+            url = Some(value);
+        } else if it == "--repository-url" {
+            url = iter.next().map(String::as_str);
+        } else if let Some(value) = it.strip_prefix("--repository=") {
+            name = value;
+        } else if it == "--repository" || it == "-r" {
+            name = iter.next().map_or(name, String::as_str);
+        }
+    }
+
+    match url.and_then(|it| Url::parse(it).ok()) {
+        Some(url) => sources.credentials.credentials(&url),
+        None => sources.credentials.named(name).or_else(|| {
+            (name == PYPI_REPOSITORY_NAME)
+                .then(|| Url::parse(PYPI_UPLOAD_URL).ok())
+                .flatten()
+                .and_then(|it| sources.credentials.credentials(&it))
+        }),
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    active_python_env_path, directory_is_venv, venv_executables_dir_path, Config, Environment,
-    Error, HuakResult, PythonEnvironment,
+    active_python_env_path, create_venv, directory_is_venv, venv_executables_dir_path, Config,
+    Environment, Error, HuakResult, PythonEnvironment, VenvOptions,
 };
 use huak_home::huak_home_dir;
 use huak_python_manager::{
@@ -8,7 +8,7 @@ use huak_python_manager::{
     Strategy,
 };
 use huak_toolchain::Channel;
-use std::{process::Command, str::FromStr};
+use std::str::FromStr;
 use termcolor::Color;
 
 pub fn list_python(config: &Config) -> HuakResult<()> {
@@ -64,10 +64,14 @@ pub fn use_python(version: &RequestedVersion, config: &Config) -> HuakResult<()>
     };
 
     // Create a new Python environment using the interpreter matching the version provided.
-    let mut cmd = Command::new(path);
-    cmd.args(["-m", "venv", ".venv"])
-        .current_dir(&config.workspace_root);
-    config.terminal().run_command(&mut cmd)
+    create_venv(
+        config.workspace_root.join(".venv"),
+        &path,
+        &VenvOptions::default(),
+        config,
+    )?;
+
+    Ok(())
 }
 
 pub fn install_python(version: RequestedVersion) -> HuakResult<()> {
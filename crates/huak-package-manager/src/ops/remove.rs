@@ -1,9 +1,21 @@
-use crate::{dependency_iter, Config, Error, HuakResult, InstallOptions};
+use super::{
+    ensure_manifest_writable,
+    lock::{manifest_root, update_lock_file},
+};
+use crate::{
+    dependency_iter, lock::lock_file_path, Config, Error, HuakResult, InstallOptions, ManifestGroup,
+};
 
 pub struct RemoveOptions {
+    /// Only remove dependencies from this group. Dependencies are removed from every group
+    /// if `None`.
+    pub group: Option<ManifestGroup>,
     pub install_options: InstallOptions,
 }
 
+/// Remove dependencies from the manifest and uninstall them.
+///
+/// Dependencies are only uninstalled once they aren't listed in any group.
 pub fn remove_project_dependencies(
     dependencies: &[String],
     config: &Config,
@@ -14,10 +26,11 @@ pub fn remove_project_dependencies(
 
     // Collect any dependencies to remove from the manifest file.
     let deps = dependency_iter(dependencies)
-        .filter(|dep| {
-            manifest
+        .filter(|dep| match options.group.as_ref() {
+            Some(it) => it.contains(manifest.manifest_data(), dep.name()),
+            None => manifest
                 .manifest_data()
-                .contains_project_dependency_any(dep.name())
+                .contains_project_dependency_any(dep.name()),
         })
         .collect::<Vec<_>>();
 
@@ -25,33 +38,69 @@ pub fn remove_project_dependencies(
         return Ok(());
     }
 
-    let optional_groups = manifest
-        .manifest_data()
-        .project_optional_dependency_groups();
+    ensure_manifest_writable(config)?;
+
+    let groups = if let Some(it) = options.group.as_ref() {
+        vec![it.clone()]
+    } else {
+        let data = manifest.manifest_data();
+        data.project_optional_dependency_groups()
+            .unwrap_or_default()
+            .into_iter()
+            .map(ManifestGroup::Optional)
+            .chain(
+                data.dependency_group_names()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(ManifestGroup::Dependency),
+            )
+            .collect()
+    };
 
     for dep in &deps {
-        manifest
-            .manifest_data_mut()
-            .remove_project_dependency(dep.name());
-
-        if let Some(groups) = optional_groups.as_ref() {
-            for g in groups {
-                manifest
-                    .manifest_data_mut()
-                    .remove_project_optional_dependency(dep.name(), g);
-            }
+        if options.group.is_none() {
+            manifest
+                .manifest_data_mut()
+                .remove_project_dependency(dep.name());
+        }
+
+        for g in &groups {
+            g.remove(manifest.manifest_data_mut(), dep.name());
         }
     }
 
     manifest.manifest_data_mut().formatted();
     manifest.write_file()?;
 
+    let unlisted = deps
+        .into_iter()
+        .filter(|dep| {
+            !manifest
+                .manifest_data()
+                .contains_project_dependency_any(dep.name())
+        })
+        .collect::<Vec<_>>();
+
     // Uninstall the dependencies from the Python environment if an environment is found.
-    match workspace.current_python_environment() {
-        Ok(it) => it.uninstall_packages(&deps, &options.install_options, config),
-        Err(Error::PythonEnvironmentNotFound) => Ok(()),
-        Err(e) => Err(e),
+    // Environments aren't created just to update the lock file.
+    let python_env = match workspace.current_python_environment() {
+        Ok(it) => it,
+        Err(Error::PythonEnvironmentNotFound) => {
+            if lock_file_path(manifest_root(&manifest)).exists() {
+                config.terminal().print_warning(
+                    "huak.lock wasn't updated because no Python environment was found",
+                )?;
+            }
+            return Ok(());
+        }
+        Err(e) => return Err(e),
+    };
+
+    if !unlisted.is_empty() {
+        python_env.uninstall_packages(&unlisted, &options.install_options, config)?;
     }
+
+    update_lock_file(&manifest, &python_env, &options.install_options, config)
 }
 
 #[cfg(test)]
@@ -86,6 +135,7 @@ mod tests {
             ..Default::default()
         };
         let options = RemoveOptions {
+            group: None,
             install_options: InstallOptions { values: None },
         };
         let ws = config.workspace();
@@ -138,6 +188,7 @@ mod tests {
             ..Default::default()
         };
         let options = RemoveOptions {
+            group: None,
             install_options: InstallOptions { values: None },
         };
         let ws = config.workspace();
@@ -168,4 +219,42 @@ mod tests {
         assert!(!venv_contains_package);
         assert!(!toml_contains_package);
     }
+
+    #[test]
+    fn test_remove_project_dependencies_without_python_environment() {
+        let dir = tempdir().unwrap();
+        copy_dir(
+            &dev_resources_dir().join("mock-project"),
+            &dir.path().join("mock-project"),
+            &CopyDirOptions::default(),
+        )
+        .unwrap();
+        let workspace_root = dir.path().join("mock-project");
+        let cwd = workspace_root.clone();
+        let terminal_options = TerminalOptions {
+            verbosity: Verbosity::Quiet,
+            ..Default::default()
+        };
+        let config = Config {
+            workspace_root,
+            cwd,
+            terminal_options,
+            ..Default::default()
+        };
+        let options = RemoveOptions {
+            group: None,
+            install_options: InstallOptions { values: None },
+        };
+
+        remove_project_dependencies(&["click".to_string()], &config, &options).unwrap();
+
+        let ws = config.workspace();
+        let manifest = ws.current_local_manifest().unwrap();
+
+        assert!(!manifest
+            .manifest_data()
+            .contains_project_dependency("click"));
+        assert!(!ws.root().join(".venv").exists());
+        assert!(!ws.root().join("huak.lock").exists());
+    }
 }
@@ -489,6 +489,7 @@ mod tests {
         // For some reason this test fails with multiple threads used. Workspace.resolve_python_environment()
         // ends up updating the PATH environment variable causing subsequent Python searches using PATH to fail.
         // TODO
+        let _path = crate::python_environment::lock_path_env();
         let env_path = env_path_string().unwrap();
         let venv = ws.resolve_python_environment().unwrap();
         std::env::set_var("PATH", env_path);
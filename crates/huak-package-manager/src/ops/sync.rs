@@ -0,0 +1,305 @@
+use super::lock::{install_locked_packages, resolve_lock_file};
+use crate::{
+    lock::{normalized_name, REQUIRED_GROUP_NAME},
+    Config, HuakResult, InstallOptions, InstalledDistribution, LockedPackage,
+};
+use pep440_rs::Version;
+use std::str::FromStr;
+use termcolor::Color;
+
+/// Packages that are never removed from a Python environment when syncing.
+const PROTECTED_PACKAGE_NAMES: [&str; 3] = ["pip", "setuptools", "wheel"];
+
+pub struct SyncOptions {
+    /// The optional dependency groups and dependency groups to sync. All groups are synced if `None`.
+    pub groups: Option<Vec<String>>,
+    pub install_options: InstallOptions,
+}
+
+/// Sync the project's Python environment with its locked dependencies.
+///
+/// - Installs any locked packages that are missing or installed with a different version.
+/// - Uninstalls any packages that aren't locked for the selected groups.
+pub fn sync_project(config: &Config, options: &SyncOptions) -> HuakResult<()> {
+    let workspace = config.workspace();
+    let manifest = workspace.current_local_manifest()?;
+    let python_env = workspace.resolve_python_environment()?;
+    let lockfile = resolve_lock_file(&manifest, &python_env, &options.install_options, config)?;
+    let marker_env = python_env.marker_environment()?;
+    let mut terminal = config.terminal();
+
+    let groups = match options.groups.as_ref() {
+        Some(gs) => {
+            let mut available = manifest
+                .manifest_data()
+                .project_optional_dependency_groups()
+                .unwrap_or_default();
+            available.extend(
+                manifest
+                    .manifest_data()
+                    .dependency_group_names()
+                    .unwrap_or_default(),
+            );
+
+            for g in gs.iter().filter(|g| !available.contains(g)) {
+                terminal.print_warning(format!("dependency group '{g}' not found"))?;
+            }
+
+            let mut groups = vec![REQUIRED_GROUP_NAME.to_string()];
+            groups.extend(gs.iter().cloned());
+            groups
+        }
+        None => lockfile
+            .packages()
+            .iter()
+            .flat_map(|pkg| pkg.groups.iter().cloned())
+            .collect(),
+    };
+
+    let locked = lockfile
+        .packages_in_groups(&groups)
+        .filter(|pkg| {
+            pkg.markers
+                .as_ref()
+                .and_then(|it| pep508_rs::MarkerTree::from_str(it).ok())
+                .map_or(true, |it| it.evaluate(&marker_env, &[]))
+        })
+        .collect::<Vec<_>>();
+    let installed = python_env.installed_distributions()?;
+
+    let mut protected = PROTECTED_PACKAGE_NAMES
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    if let Some(name) = manifest.manifest_data().project_name() {
+        protected.push(normalized_name(&name));
+    }
+
+    let plan = SyncPlan::new(&locked, &installed, &protected);
+
+    if plan.is_empty() {
+        return terminal.print_custom(
+            "Success",
+            "the environment is already synced",
+            Color::Green,
+            true,
+        );
+    }
+
+    if !plan.uninstall.is_empty() {
+        let names = plan
+            .uninstall
+            .iter()
+            .map(|it| it.name())
+            .collect::<Vec<_>>();
+        python_env.uninstall_packages(&names, &options.install_options, config)?;
+    }
+
+    let mut packages = plan.install.clone();
+    packages.extend(plan.update.iter().map(|(_, pkg)| *pkg));
+
+    install_locked_packages(&packages, &python_env, &options.install_options, config)
+}
+
+/// The changes required to sync a Python environment with a set of `LockedPackage`s.
+struct SyncPlan<'a> {
+    /// Locked packages that aren't installed.
+    install: Vec<&'a LockedPackage>,
+    /// Installed distributions with a version or direct reference different from the
+    /// locked package.
+    update: Vec<(&'a InstalledDistribution, &'a LockedPackage)>,
+    /// Installed distributions that aren't locked.
+    uninstall: Vec<&'a InstalledDistribution>,
+}
+
+impl<'a> SyncPlan<'a> {
+    fn new(
+        locked: &[&'a LockedPackage],
+        installed: &'a [InstalledDistribution],
+        protected: &[String],
+    ) -> Self {
+        let mut install = Vec::new();
+        let mut update = Vec::new();
+
+        for pkg in locked {
+            match installed.iter().find(|it| it.normalized_name() == pkg.name) {
+                Some(it) if !is_synced(it, pkg) => update.push((it, *pkg)),
+                Some(_) => (),
+                None => install.push(*pkg),
+            }
+        }
+
+        let uninstall = installed
+            .iter()
+            .filter(|it| {
+                let name = it.normalized_name();
+                !protected.contains(&name) && !locked.iter().any(|pkg| pkg.name == name)
+            })
+            .collect();
+
+        Self {
+            install,
+            update,
+            uninstall,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.install.is_empty() && self.update.is_empty() && self.uninstall.is_empty()
+    }
+}
+
+/// Check if an `InstalledDistribution` matches a `LockedPackage`. Direct references must be
+/// installed from the locked URL (including its commit).
+fn is_synced(installed: &InstalledDistribution, pkg: &LockedPackage) -> bool {
+    let version = Version::from_str(&pkg.version).ok();
+    let url = installed.direct_url().map(|it| it.to_string());
+
+    version.is_some_and(|it| Version::from_str(installed.version()).ok() == Some(it))
+        && url == pkg.url
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::installed_distributions;
+    use tempfile::tempdir;
+
+    fn locked(name: &str, version: &str) -> LockedPackage {
+        LockedPackage {
+            name: name.to_string(),
+            version: version.to_string(),
+            index: None,
+            url: None,
+            hashes: Vec::new(),
+            markers: None,
+            groups: vec![REQUIRED_GROUP_NAME.to_string()],
+            dependencies: Vec::new(),
+            files: Vec::new(),
+        }
+    }
+
+    /// Write *.dist-info directories for "name==version" strings with an optional
+    /// `direct_url.json`.
+    fn installed(
+        dir: &std::path::Path,
+        packages: &[(&str, Option<&str>)],
+    ) -> Vec<InstalledDistribution> {
+        for (package, direct_url) in packages {
+            let (name, version) = package.split_once("==").unwrap();
+            let path = dir.join(format!("{name}-{version}.dist-info"));
+            std::fs::create_dir_all(&path).unwrap();
+            std::fs::write(
+                path.join("METADATA"),
+                format!("Metadata-Version: 2.1\nName: {name}\nVersion: {version}\n"),
+            )
+            .unwrap();
+            if let Some(it) = direct_url {
+                std::fs::write(path.join("direct_url.json"), it).unwrap();
+            }
+        }
+
+        installed_distributions(dir).unwrap()
+    }
+
+    #[test]
+    fn test_sync_plan() {
+        let locked = [
+            locked("click", "8.1.7"),
+            locked("typing-extensions", "4.8.0"),
+            locked("pytest", "7.4.3"),
+        ];
+        let locked = locked.iter().collect::<Vec<_>>();
+        let dir = tempdir().unwrap();
+        let installed = installed(
+            dir.path(),
+            &[
+                ("click==8.1.3", None),
+                ("Typing_Extensions==4.8.0", None),
+                ("ruff==0.1.5", None),
+                ("pip==23.3.1", None),
+            ],
+        );
+        let protected = vec!["pip".to_string()];
+
+        let plan = SyncPlan::new(&locked, &installed, &protected);
+
+        assert_eq!(
+            plan.install.iter().map(|it| &it.name).collect::<Vec<_>>(),
+            vec!["pytest"]
+        );
+        assert_eq!(
+            plan.update
+                .iter()
+                .map(|(from, to)| (from.version(), to.version.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("8.1.3", "8.1.7")]
+        );
+        assert_eq!(
+            plan.uninstall
+                .iter()
+                .map(|it| it.name())
+                .collect::<Vec<_>>(),
+            vec!["ruff"]
+        );
+    }
+
+    #[test]
+    fn test_sync_plan_empty() {
+        let locked = [locked("click", "8.1.7")];
+        let locked = locked.iter().collect::<Vec<_>>();
+        let dir = tempdir().unwrap();
+        let installed = installed(dir.path(), &[("click==8.1.7", None)]);
+
+        assert!(SyncPlan::new(&locked, &installed, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_sync_plan_direct_references() {
+        let mut lib = locked("lib", "1.0.0");
+        lib.url = Some("git+https://github.com/org/lib@def456".to_string());
+        let locked = [lib, locked("click", "8.1.7")];
+        let locked = locked.iter().collect::<Vec<_>>();
+        let dir = tempdir().unwrap();
+        let installed = installed(
+            dir.path(),
+            &[
+                (
+                    "lib==1.0.0",
+                    Some(
+                        r#"{"url": "https://github.com/org/lib", "vcs_info": {"vcs": "git", "commit_id": "abc123"}}"#,
+                    ),
+                ),
+                (
+                    "click==8.1.7",
+                    Some(
+                        r#"{"url": "https://example.com/click-8.1.7-py3-none-any.whl", "archive_info": {}}"#,
+                    ),
+                ),
+            ],
+        );
+
+        let plan = SyncPlan::new(&locked, &installed, &[]);
+
+        assert_eq!(
+            plan.update
+                .iter()
+                .map(|(_, to)| to.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["lib", "click"]
+        );
+
+        let synced = installed_distributions(dir.path())
+            .unwrap()
+            .into_iter()
+            .filter(|it| it.name() == "lib")
+            .collect::<Vec<_>>();
+        std::fs::write(
+            synced[0].path().join("direct_url.json"),
+            r#"{"url": "https://github.com/org/lib", "vcs_info": {"vcs": "git", "commit_id": "def456"}}"#,
+        )
+        .unwrap();
+
+        assert!(SyncPlan::new(&locked[..1], &synced, &[]).is_empty());
+    }
+}
@@ -1,4 +1,4 @@
-use super::add_venv_to_command;
+use super::{add_tools_to_manifest, add_venv_to_command, install_tools};
 use crate::{Config, Dependency, HuakResult, InstallOptions};
 use std::{process::Command, str::FromStr};
 
@@ -16,27 +16,23 @@ pub fn test_project(config: &Config, options: &TestOptions) -> HuakResult<()> {
     // Install `pytest` if it isn't already installed.
     let test_dep = Dependency::from_str("pytest")?;
     if !python_env.contains_module(test_dep.name())? {
-        python_env.install_packages(&[&test_dep], &options.install_options, config)?;
+        install_tools(
+            &[&test_dep],
+            &manifest,
+            &python_env,
+            &options.install_options,
+            config,
+        )?;
     }
 
     // Add the installed `pytest` package to the manifest file if it isn't already there.
-    if !manifest
-        .manifest_data()
-        .contains_project_dependency_any(test_dep.name())
-    {
-        for pkg in python_env
-            .installed_packages()?
-            .iter()
-            .filter(|pkg| pkg.name() == test_dep.name())
-        {
-            manifest
-                .manifest_data_mut()
-                .add_project_optional_dependency(&pkg.to_string(), "dev");
-        }
-    }
-
-    manifest.manifest_data_mut().formatted();
-    manifest.write_file()?;
+    add_tools_to_manifest(
+        &[&test_dep],
+        &mut manifest,
+        &python_env,
+        &options.install_options,
+        config,
+    )?;
 
     // Run `pytest` with the package directory added to the command's `PYTHONPATH`.
     let mut cmd = Command::new(python_env.python_path());
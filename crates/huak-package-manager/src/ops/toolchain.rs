@@ -1,5 +1,6 @@
 use crate::{
-    fs::maybe_exe, sys::symlink_supported, Config, Error, HuakResult, PythonEnvironment, Verbosity,
+    create_venv, fs::maybe_exe, Config, Error, HuakResult, InstallOptions, PythonEnvironment,
+    VenvOptions, Verbosity,
 };
 use huak_home::huak_home_dir;
 use huak_python_manager::{
@@ -26,23 +27,20 @@ pub fn add_tool(tool: &LocalTool, channel: Option<&Channel>, config: &Config) ->
     // Resolve a toolchain if a channel is provided. Otherwise resolve the current.
     let toolchain = config.workspace().resolve_local_toolchain(channel)?;
 
-    add_tool_to_toolchain(tool, &toolchain, config)
+    add_tool_to_toolchain(tool, &toolchain, &InstallOptions { values: None }, config)
 }
 
 // TODO(cnpryer): Refactor
 pub(crate) fn add_tool_to_toolchain(
     tool: &LocalTool,
     toolchain: &LocalToolchain,
+    options: &InstallOptions,
     config: &Config,
 ) -> HuakResult<()> {
-    let args = ["-m", "pip", "install", tool.spec().unwrap_or(&tool.name)];
     let venv = PythonEnvironment::new(toolchain.root().join(".venv"))?;
 
     let mut terminal = config.terminal();
 
-    let mut cmd = Command::new(venv.python_path());
-    let cmd = cmd.args(args).current_dir(&config.cwd);
-
     terminal.print_custom(
         "Updating",
         format!("adding {} to {}", &tool.name, toolchain.name()),
@@ -52,7 +50,7 @@ pub(crate) fn add_tool_to_toolchain(
 
     // TODO(cnpryer): Terminal work
     // terminal.set_verbosity(Verbosity::Quiet);
-    terminal.run_command(cmd)?;
+    venv.install_packages(&[tool.spec().unwrap_or(&tool.name)], options, config)?;
 
     let Some(source) = venv.executable_module_path(&tool.name) else {
         return Err(Error::InternalError(format!(
@@ -150,11 +148,7 @@ pub(crate) fn install(path: &PathBuf, channel: Channel, config: &Config) -> Huak
     for name in ["ruff", "mypy", "pytest"] {
         terminal.print_custom("Installing", name, Color::Green, true)?;
 
-        let mut cmd: Command = Command::new(venv.python_path());
-        cmd.current_dir(&config.cwd)
-            .args(["-m", "pip", "install", name]);
-
-        terminal.run_command(&mut cmd)?;
+        venv.install_packages(&[name], &InstallOptions { values: None }, config)?;
 
         let Some(p) = venv.executable_module_path(name) else {
             return Err(Error::PythonModuleNotFound(name.to_string()));
@@ -198,6 +192,14 @@ pub(crate) fn install_minimal_toolchain(
         return Err(Error::LocalToolchainExists(path.clone()));
     }
 
+    // Python releases are always downloaded.
+    if config.offline {
+        return Err(Error::OfflineNetworkRequest(format!(
+            "downloading python for toolchain '{}'",
+            toolchain.name()
+        )));
+    }
+
     for p in [toolchain.bin(), toolchain.downloads()] {
         std::fs::create_dir_all(p)?;
     }
@@ -272,27 +274,20 @@ pub(crate) fn install_minimal_toolchain(
         return Err(Error::PythonNotFound);
     }
 
-    // Create a virtual environment for the toolchain.
-    let mut cmd: Command = Command::new(&py_path);
-
-    cmd.current_dir(toolchain.root());
-
-    if symlink_supported() {
-        cmd.args(["-m", "venv", ".venv", "--symlinks"]);
-    } else {
-        cmd.args(["-m", "venv", ".venv"]);
-    }
-
     terminal.print_custom(
         "Updating",
         "setting up virtual environment",
         Color::Green,
         true,
     )?;
-    terminal.run_command(&mut cmd)?;
 
-    // Use the installed python
-    let venv = PythonEnvironment::new(toolchain.root().join(".venv"))?;
+    // Create a virtual environment for the toolchain and use its python.
+    let venv = create_venv(
+        toolchain.root().join(".venv"),
+        &py_path,
+        &VenvOptions::default(),
+        config,
+    )?;
 
     // With the release unpacked to the downloads directory, we need to install the release
     // to the toolchain we're setting up. In order to complete the setup the following steps
@@ -365,10 +360,6 @@ pub fn remove_tool(tool: &LocalTool, channel: Option<&Channel>, config: &Config)
     let venv = PythonEnvironment::new(toolchain.root().join(".venv"))?;
 
     let mut terminal = config.terminal();
-    let args = ["-m", "pip", "uninstall", &tool.name, "-y"];
-
-    let mut cmd = Command::new(venv.python_path());
-    let cmd = cmd.args(args).current_dir(&config.cwd);
 
     let tool = toolchain.tool(&tool.name);
     let Some(path) = tool.path.as_ref() else {
@@ -383,12 +374,9 @@ pub fn remove_tool(tool: &LocalTool, channel: Option<&Channel>, config: &Config)
         true,
     )?;
 
-    terminal.set_verbosity(Verbosity::Quiet);
-    terminal.run_command(cmd)?;
+    venv.uninstall_packages(&[&tool.name], &InstallOptions { values: None }, config)?;
 
     remove_path_with_scope(path, toolchain.root())?;
-    terminal.set_verbosity(Verbosity::Normal);
-
     terminal.print_custom(
         "Success",
         format!("{} was uninstalled", &tool.name),
@@ -507,25 +495,12 @@ pub fn update_toolchain(
             .collect()
     };
 
-    let py = toolchain.tool("python");
+    let venv = PythonEnvironment::new(toolchain.root().join(".venv"))?;
+    let options = InstallOptions { values: None };
 
-    let args = ["-m", "pip", "install", "--upgrade"];
     for tool in tools {
-        let Some(p) = py.path.as_ref() else {
-            return Err(Error::PythonNotFound);
-        };
-
-        let mut cmd = Command::new(p);
-
         terminal.print_custom("Updating", &tool.name, Color::Green, true)?;
-        terminal.set_verbosity(Verbosity::Quiet);
-
-        cmd.args(args.iter().chain([&tool.name.as_str()]))
-            .current_dir(&config.cwd);
-
-        terminal.run_command(&mut cmd)?;
-
-        terminal.set_verbosity(Verbosity::Normal);
+        venv.update_packages(&[&tool.name], &options, config)?;
     }
 
     terminal.print_custom("Success", "finished updating", Color::Green, true)
@@ -0,0 +1,400 @@
+use super::lock::manifest_root;
+use crate::{
+    lock::{manifest_dependency_groups, normalized_name, REQUIRED_GROUP_NAME},
+    Config, DependencyGraph, Error, GraphRoot, HuakResult, LocalManifest, PythonEnvironment,
+};
+use std::collections::HashSet;
+
+pub struct TreeOptions {
+    /// The maximum depth of the tree. Direct dependencies have a depth of 1.
+    pub depth: Option<usize>,
+    /// Invert the tree for a package to display what depends on it.
+    pub invert: Option<String>,
+    /// Only display packages depended on more than once (inverted).
+    pub duplicates: bool,
+}
+
+/// Display the tree of the project's installed dependencies.
+///
+/// The tree is rooted at the project's dependencies and its optional dependency groups.
+/// Packages that were already displayed are marked with (*) and aren't expanded again.
+pub fn display_dependency_tree(config: &Config, options: &TreeOptions) -> HuakResult<()> {
+    let workspace = config.workspace();
+    let manifest = workspace.current_local_manifest()?;
+    let python_env = workspace.current_python_environment()?;
+    let graph = dependency_graph(&manifest, &python_env)?;
+    let project = project_label(&manifest);
+
+    let tree = if let Some(name) = options.invert.as_ref() {
+        if graph.get(name).is_none() {
+            return Err(Error::PackageNotFound(name.clone()));
+        }
+        render_inverted_tree(&graph, name, &project, options.depth)
+    } else if options.duplicates {
+        render_duplicates(&graph, &project, options.depth)
+    } else {
+        render_tree(&graph, &project, options.depth)
+    };
+
+    if tree.is_empty() {
+        return Ok(());
+    }
+
+    config.terminal().print_stdout(tree.trim_end())
+}
+
+/// Get the `DependencyGraph` of a `PythonEnvironment` rooted at a `LocalManifest`'s
+/// dependencies.
+pub(crate) fn dependency_graph(
+    manifest: &LocalManifest,
+    python_env: &PythonEnvironment,
+) -> HuakResult<DependencyGraph> {
+    let roots = manifest_dependency_groups(manifest.manifest_data(), manifest_root(manifest))?
+        .into_iter()
+        .flat_map(|(group, reqs)| {
+            reqs.into_iter().map(move |requirement| GraphRoot {
+                group: group.clone(),
+                requirement,
+            })
+        })
+        .collect();
+
+    Ok(DependencyGraph::new(
+        &python_env.installed_distributions()?,
+        roots,
+        &python_env.marker_environment()?,
+    ))
+}
+
+/// Get the label used for the project in trees.
+pub(crate) fn project_label(manifest: &LocalManifest) -> String {
+    let data = manifest.manifest_data();
+    let name = data.project_name().unwrap_or_else(|| "project".to_string());
+
+    match data.project_version() {
+        Some(version) => format!("{name} v{version}"),
+        None => name,
+    }
+}
+
+/// Get the label used for a root's group. The required group isn't labeled.
+pub(crate) fn group_label(project: &str, group: &str) -> String {
+    if group == REQUIRED_GROUP_NAME {
+        project.to_owned()
+    } else {
+        format!("{project} ({group})")
+    }
+}
+
+fn render_tree(graph: &DependencyGraph, project: &str, depth: Option<usize>) -> String {
+    let mut out = vec![project.to_string()];
+    let mut seen = HashSet::new();
+
+    let mut groups = graph
+        .roots()
+        .iter()
+        .map(|it| it.group.as_str())
+        .collect::<Vec<_>>();
+    groups.sort_by_key(|it| (*it != REQUIRED_GROUP_NAME, *it));
+    groups.dedup();
+
+    for group in groups {
+        let mut names = graph
+            .roots()
+            .iter()
+            .filter(|it| it.group == group)
+            .map(|it| normalized_name(&it.requirement.name))
+            .collect::<Vec<_>>();
+        names.sort();
+        names.dedup();
+
+        if group != REQUIRED_GROUP_NAME {
+            out.push(format!("[{group}]"));
+        }
+
+        for (i, name) in names.iter().enumerate() {
+            render_node(
+                &mut out,
+                graph,
+                name,
+                "",
+                i + 1 == names.len(),
+                1,
+                depth,
+                &mut seen,
+            );
+        }
+    }
+
+    out.join("\n")
+}
+
+#[allow(clippy::too_many_arguments)]
+fn render_node(
+    out: &mut Vec<String>,
+    graph: &DependencyGraph,
+    name: &str,
+    prefix: &str,
+    last: bool,
+    depth: usize,
+    max_depth: Option<usize>,
+    seen: &mut HashSet<String>,
+) {
+    let connector = if last { "└── " } else { "├── " };
+    let Some(pkg) = graph.get(name) else {
+        out.push(format!("{prefix}{connector}{name} (not installed)"));
+        return;
+    };
+
+    let expand = max_depth.map_or(true, |it| depth < it) && !pkg.dependencies.is_empty();
+    let repeated = expand && !seen.insert(name.to_string());
+    out.push(format!(
+        "{prefix}{connector}{} v{}{}",
+        pkg.name,
+        pkg.version,
+        if repeated { " (*)" } else { "" }
+    ));
+
+    if !expand || repeated {
+        return;
+    }
+
+    let prefix = format!("{prefix}{}", if last { "    " } else { "│   " });
+    let mut names = pkg
+        .dependencies
+        .iter()
+        .map(|it| it.name.as_str())
+        .collect::<Vec<_>>();
+    names.dedup();
+
+    for (i, dep) in names.iter().enumerate() {
+        render_node(
+            out,
+            graph,
+            dep,
+            &prefix,
+            i + 1 == names.len(),
+            depth + 1,
+            max_depth,
+            seen,
+        );
+    }
+}
+
+fn render_inverted_tree(
+    graph: &DependencyGraph,
+    name: &str,
+    project: &str,
+    depth: Option<usize>,
+) -> String {
+    let Some(pkg) = graph.get(name) else {
+        return String::new();
+    };
+    let mut out = vec![format!("{} v{}", pkg.name, pkg.version)];
+    let mut seen = HashSet::new();
+    seen.insert(normalized_name(name));
+
+    render_dependents(
+        &mut out,
+        graph,
+        &normalized_name(name),
+        project,
+        "",
+        1,
+        depth,
+        &mut seen,
+    );
+
+    out.join("\n")
+}
+
+#[allow(clippy::too_many_arguments)]
+fn render_dependents(
+    out: &mut Vec<String>,
+    graph: &DependencyGraph,
+    name: &str,
+    project: &str,
+    prefix: &str,
+    depth: usize,
+    max_depth: Option<usize>,
+    seen: &mut HashSet<String>,
+) {
+    if max_depth.is_some_and(|it| depth > it) {
+        return;
+    }
+
+    let dependents = graph.dependents(name);
+    let mut groups = graph
+        .root_requirements(name)
+        .iter()
+        .map(|it| it.group.as_str())
+        .collect::<Vec<_>>();
+    groups.sort_by_key(|it| (*it != REQUIRED_GROUP_NAME, *it));
+    groups.dedup();
+    let count = dependents.len() + groups.len();
+
+    for (i, (pkg, _)) in dependents.iter().enumerate() {
+        let last = i + 1 == count;
+        let connector = if last { "└── " } else { "├── " };
+        let dependent = normalized_name(&pkg.name);
+        let expand = max_depth.map_or(true, |it| depth < it);
+        let repeated = expand && !seen.insert(dependent.clone());
+        out.push(format!(
+            "{prefix}{connector}{} v{}{}",
+            pkg.name,
+            pkg.version,
+            if repeated { " (*)" } else { "" }
+        ));
+
+        if expand && !repeated {
+            let prefix = format!("{prefix}{}", if last { "    " } else { "│   " });
+            render_dependents(
+                out,
+                graph,
+                &dependent,
+                project,
+                &prefix,
+                depth + 1,
+                max_depth,
+                seen,
+            );
+        }
+    }
+
+    for (i, group) in groups.iter().enumerate() {
+        let connector = if dependents.len() + i + 1 == count {
+            "└── "
+        } else {
+            "├── "
+        };
+        out.push(format!(
+            "{prefix}{connector}{}",
+            group_label(project, group)
+        ));
+    }
+}
+
+/// Render inverted trees for each package that's depended on more than once.
+fn render_duplicates(graph: &DependencyGraph, project: &str, depth: Option<usize>) -> String {
+    graph
+        .packages()
+        .filter(|pkg| {
+            let name = normalized_name(&pkg.name);
+            let mut groups = graph
+                .root_requirements(&name)
+                .iter()
+                .map(|it| it.group.as_str())
+                .collect::<Vec<_>>();
+            groups.dedup();
+            graph.dependents(&name).len() + groups.len() > 1
+        })
+        .map(|pkg| render_inverted_tree(graph, &pkg.name, project, depth))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        installed::write_mock_distributions, installed_distributions,
+        python_environment::mock_marker_environment,
+    };
+    use pep508_rs::Requirement;
+    use std::str::FromStr;
+    use tempfile::tempdir;
+
+    fn graph() -> DependencyGraph {
+        let dir = tempdir().unwrap();
+        write_mock_distributions(
+            dir.path(),
+            &[
+                (
+                    "click",
+                    "8.1.7",
+                    &["colorama; platform_system == 'Windows'"],
+                ),
+                (
+                    "pytest",
+                    "7.4.3",
+                    &["iniconfig", "packaging", "pluggy<2.0,>=0.12"],
+                ),
+                ("iniconfig", "2.0.0", &[]),
+                ("packaging", "23.2", &[]),
+                ("pluggy", "1.3.0", &[]),
+                ("build", "1.0.3", &["packaging>=19.0", "pyproject_hooks"]),
+            ],
+        );
+
+        let roots = [
+            ("required", "click"),
+            ("dev", "pytest"),
+            ("dev", "build"),
+            ("docs", "packaging"),
+        ]
+        .iter()
+        .map(|(group, req)| GraphRoot {
+            group: (*group).to_string(),
+            requirement: Requirement::from_str(req).unwrap(),
+        })
+        .collect();
+        DependencyGraph::new(
+            &installed_distributions(dir.path()).unwrap(),
+            roots,
+            &mock_marker_environment(),
+        )
+    }
+
+    #[test]
+    fn test_render_tree() {
+        let graph = graph();
+
+        assert_eq!(
+            render_tree(&graph, "mock-project v0.0.1", None),
+            r"mock-project v0.0.1
+└── click v8.1.7
+[dev]
+├── build v1.0.3
+│   ├── packaging v23.2
+│   └── pyproject-hooks (not installed)
+└── pytest v7.4.3
+    ├── iniconfig v2.0.0
+    ├── packaging v23.2
+    └── pluggy v1.3.0
+[docs]
+└── packaging v23.2"
+        );
+        assert_eq!(
+            render_tree(&graph, "mock-project v0.0.1", Some(1)),
+            r"mock-project v0.0.1
+└── click v8.1.7
+[dev]
+├── build v1.0.3
+└── pytest v7.4.3
+[docs]
+└── packaging v23.2"
+        );
+    }
+
+    #[test]
+    fn test_render_inverted_tree() {
+        let graph = graph();
+
+        assert_eq!(
+            render_inverted_tree(&graph, "Packaging", "mock-project v0.0.1", None),
+            r"packaging v23.2
+├── build v1.0.3
+│   └── mock-project v0.0.1 (dev)
+├── pytest v7.4.3
+│   └── mock-project v0.0.1 (dev)
+└── mock-project v0.0.1 (docs)"
+        );
+        assert_eq!(
+            render_duplicates(&graph, "mock-project v0.0.1", Some(1)),
+            r"packaging v23.2
+├── build v1.0.3
+├── pytest v7.4.3
+└── mock-project v0.0.1 (docs)"
+        );
+    }
+}
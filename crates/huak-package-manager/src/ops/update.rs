@@ -1,10 +1,58 @@
-use crate::{dependency_iter, Config, Dependency, HuakResult, InstallOptions};
-use std::str::FromStr;
+use super::{
+    add::version_bound,
+    ensure_manifest_writable,
+    lock::{manifest_root, update_lock_file},
+};
+use crate::{
+    diff::unified_diff,
+    lock::{
+        index_url_from_args, manifest_dependency_groups, normalized_name, parse_requirements,
+        REQUIRED_GROUP_NAME,
+    },
+    Config, HuakResult, InstallOptions, LocalManifest, ManifestGroup, PackageSources,
+    PythonEnvironment, Resolver,
+};
+use huak_pyproject_toml::PyProjectToml;
+use pep440_rs::{Operator, Version, VersionSpecifier, VersionSpecifiers};
+use pep508_rs::{Requirement, VersionOrUrl};
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+    str::FromStr,
+};
+use termcolor::Color;
+use toml_edit::{Array, Table, Value};
+
+/// How `huak update` changes the version specifiers of the dependencies it updates.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateStrategy {
+    /// Keep the version specifiers and only update the environment and huak.lock.
+    #[default]
+    Keep,
+    /// Raise lower bounds (>= and ~=) to the newest version satisfying the specifiers.
+    Compatible,
+    /// Rewrite the version specifiers for the newest release using the project's
+    /// `add-bound`.
+    Latest,
+}
 
 pub struct UpdateOptions {
+    pub strategy: UpdateStrategy,
+    /// Only update the dependencies listed in this group. Every dependency is updated if `None`.
+    pub group: Option<ManifestGroup>,
+    /// Display the changes to pyproject.toml without updating anything.
+    pub dry_run: bool,
     pub install_options: InstallOptions,
 }
 
+/// A map of (group, normalized name) to the `VersionSpecifiers` to write for a dependency.
+type Rewrites = HashMap<(String, String), VersionSpecifiers>;
+
+/// Update the project's dependencies.
+///
+/// Dependencies are updated within their existing version specifiers and keep their markers
+/// and extras. `UpdateStrategy::Compatible` and `UpdateStrategy::Latest` also rewrite the
+/// version specifiers in pyproject.toml. Direct references are left as-is.
 #[allow(clippy::module_name_repetitions)]
 #[allow(clippy::needless_pass_by_value)]
 pub fn update_project_dependencies(
@@ -12,94 +60,259 @@ pub fn update_project_dependencies(
     config: &Config,
     options: &UpdateOptions,
 ) -> HuakResult<()> {
+    if !options.dry_run {
+        ensure_manifest_writable(config)?;
+    }
+
     let workspace = config.workspace();
     let mut manifest = workspace.current_local_manifest()?;
-    let python_env = workspace.resolve_python_environment()?;
 
     // Collect dependencies to update if they are listed in the manifest file.
-    if let Some(it) = dependencies.as_ref() {
-        let deps = dependency_iter(it)
-            .filter_map(|dep| {
-                if manifest
-                    .manifest_data()
-                    .contains_project_dependency_any(dep.name())
-                {
-                    Some(dep)
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<_>>();
+    let targets = dependencies.as_ref().map(|it| {
+        it.iter()
+            .map(|name| normalized_name(name))
+            .collect::<HashSet<_>>()
+    });
+    let requirements = group_requirements(
+        manifest.manifest_data(),
+        manifest_root(&manifest),
+        options.group.as_ref(),
+    )?
+    .into_iter()
+    .filter(|(_, req)| {
+        targets
+            .as_ref()
+            .map_or(true, |it| it.contains(&normalized_name(&req.name)))
+    })
+    .filter(|(_, req)| !matches!(req.version_or_url, Some(VersionOrUrl::Url(_))))
+    .collect::<Vec<_>>();
 
-        if deps.is_empty() {
-            return Ok(());
-        }
+    if requirements.is_empty() {
+        return Ok(());
+    }
 
-        python_env.update_packages(&deps, &options.install_options, config)?;
+    let python_env = if options.dry_run {
+        workspace.current_python_environment()?
     } else {
-        let mut deps = manifest
-            .manifest_data()
-            .project_dependencies()
-            .map_or(Vec::new(), |reqs| reqs.into_iter().collect::<Vec<_>>());
-
-        if let Some(gs) = manifest
-            .manifest_data()
-            .project_optional_dependency_groups()
-        {
-            if let Some(optional_deps) = manifest.manifest_data().project_optional_dependencies() {
-                for g in gs {
-                    // TODO(cnpryer): Perf
-                    if let Some(it) = optional_deps.get(&g.to_string()) {
-                        deps.extend(it.iter().cloned());
-                    }
-                }
-            }
+        workspace.resolve_python_environment()?
+    };
+
+    let rewrites = if options.strategy == UpdateStrategy::Keep {
+        Rewrites::new()
+    } else {
+        version_rewrites(&requirements, &manifest, &python_env, config, options)?
+    };
+
+    // pyproject.toml is only changed if version specifiers are rewritten.
+    let before = manifest.manifest_data().to_string();
+    if !rewrites.is_empty() {
+        rewrite_requirements(
+            manifest.manifest_data_mut(),
+            &rewrites,
+            options.group.as_ref(),
+        );
+        manifest.manifest_data_mut().formatted();
+    }
+    let after = manifest.manifest_data().to_string();
+
+    if options.dry_run {
+        let diff = unified_diff(&before, &after, "pyproject.toml");
+        let mut terminal = config.terminal();
+        if diff.is_empty() {
+            return terminal.print_custom(
+                "Success",
+                "pyproject.toml wouldn't change",
+                Color::Green,
+                true,
+            );
         }
+        return terminal.print_stdout(diff);
+    }
 
-        deps.dedup();
+    let packages = requirements
+        .iter()
+        .map(|(group, req)| {
+            let mut req = req.clone();
+            if let Some(it) = rewrites.get(&(group.clone(), normalized_name(&req.name))) {
+                req.version_or_url = Some(VersionOrUrl::VersionSpecifier(it.clone()));
+            }
+            req.to_string()
+        })
+        .collect::<Vec<_>>();
+    python_env.update_packages(&packages, &options.install_options, config)?;
 
-        python_env.update_packages(&deps, &options.install_options, config)?;
+    if before != after {
+        manifest.write_file()?;
     }
 
-    let groups = manifest
-        .manifest_data()
-        .project_optional_dependency_groups();
+    update_lock_file(&manifest, &python_env, &options.install_options, config)
+}
 
-    for pkg in python_env.installed_packages()? {
-        let dep = &Dependency::from_str(&pkg.to_string())?;
-        if manifest
-            .manifest_data()
-            .contains_project_dependency(dep.name())
-        {
-            manifest
-                .manifest_data_mut()
-                .remove_project_dependency(dep.name());
-            manifest
-                .manifest_data_mut()
-                .add_project_dependency(&dep.to_string());
+/// Get the `VersionSpecifiers` to write for each requirement with `options.strategy`.
+fn version_rewrites(
+    requirements: &[(String, Requirement)],
+    manifest: &LocalManifest,
+    python_env: &PythonEnvironment,
+    config: &Config,
+    options: &UpdateOptions,
+) -> HuakResult<Rewrites> {
+    let sources = PackageSources::from_config(
+        config,
+        index_url_from_args(options.install_options.values.as_ref()),
+    )?;
+    let resolver = Resolver::new(sources.resolver_options(python_env.marker_environment()?))?;
+    let bound = version_bound(manifest, None)?;
+    let mut rewrites = Rewrites::new();
+
+    for (group, req) in requirements {
+        let specifiers = match req.version_or_url.as_ref() {
+            Some(VersionOrUrl::VersionSpecifier(it)) => Some(it),
+            _ => None,
+        };
+        let prereleases =
+            specifiers.is_some_and(|it| it.iter().any(VersionSpecifier::any_prerelease));
+        let versions = resolver
+            .versions(&req.name)?
+            .into_iter()
+            .filter(|it| prereleases || !it.any_prerelease())
+            .collect::<Vec<_>>();
+
+        let rewrite = match options.strategy {
+            UpdateStrategy::Latest => versions
+                .first()
+                .map(|it| bound.version_specifiers(it))
+                .transpose()?,
+            _ => specifiers.and_then(|specifiers| {
+                versions
+                    .iter()
+                    .find(|it| specifiers.contains(it))
+                    .map(|it| raise_lower_bounds(specifiers, it))
+            }),
+        };
+
+        if let Some(it) = rewrite {
+            rewrites.insert((group.clone(), normalized_name(&req.name)), it);
         }
+    }
+
+    Ok(rewrites)
+}
+
+/// Get the requirements to update along with the name of the group they're listed in.
+///
+/// Without a `ManifestGroup` every group is included. Only the requirements listed directly
+/// in a `[dependency-groups]` group are included when it's selected.
+fn group_requirements(
+    manifest: &PyProjectToml,
+    root: &Path,
+    group: Option<&ManifestGroup>,
+) -> HuakResult<Vec<(String, Requirement)>> {
+    let groups = match group {
+        None => manifest_dependency_groups(manifest, root)?
+            .into_iter()
+            .collect::<Vec<_>>(),
+        Some(ManifestGroup::Optional(g)) => manifest
+            .project_optional_dependencies()
+            .and_then(|mut it| it.remove(g))
+            .map(|deps| vec![(g.clone(), parse_requirements(&deps, root))])
+            .unwrap_or_default(),
+        Some(ManifestGroup::Dependency(g)) => manifest
+            .dependency_groups()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(key, _)| normalized_name(key) == normalized_name(g))
+            .map(|(key, deps)| (key, parse_requirements(&deps, root)))
+            .collect(),
+    };
 
-        if let Some(gs) = groups.as_ref() {
-            for g in gs {
-                if manifest
-                    .manifest_data()
-                    .contains_project_optional_dependency(dep.name(), g)
-                {
-                    manifest
-                        .manifest_data_mut()
-                        .remove_project_optional_dependency(dep.name(), g);
-                    manifest
-                        .manifest_data_mut()
-                        .add_project_optional_dependency(&dep.to_string(), g);
-                }
+    Ok(groups
+        .into_iter()
+        .flat_map(|(group, reqs)| reqs.into_iter().map(move |req| (group.clone(), req)))
+        .collect())
+}
+
+/// Raise the versions of `>=` and `~=` specifiers to a `Version`. `~=` specifiers keep
+/// their number of release segments.
+fn raise_lower_bounds(specifiers: &VersionSpecifiers, version: &Version) -> VersionSpecifiers {
+    let raised = specifiers
+        .iter()
+        .map(|it| match it.operator() {
+            Operator::GreaterThanEqual => format!(">={version}"),
+            Operator::TildeEqual => {
+                let len = it.version().release.len().max(2);
+                let mut release = version.release.clone();
+                release.resize(len, 0);
+                let epoch = if version.epoch == 0 {
+                    String::new()
+                } else {
+                    format!("{}!", version.epoch)
+                };
+                format!(
+                    "~={epoch}{}",
+                    release
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(".")
+                )
             }
+            _ => it.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+
+    VersionSpecifiers::from_str(&raised).unwrap_or_else(|_| specifiers.clone())
+}
+
+/// Rewrite the version specifiers of the requirements listed in a manifest's dependency
+/// arrays. Markers, extras and comments are kept.
+fn rewrite_requirements(
+    manifest: &mut PyProjectToml,
+    rewrites: &Rewrites,
+    group: Option<&ManifestGroup>,
+) {
+    if group.is_none() {
+        if let Some(array) = manifest.project_dependencies_mut() {
+            rewrite_array(array, REQUIRED_GROUP_NAME, rewrites);
         }
     }
 
-    manifest.manifest_data_mut().formatted();
-    manifest.write_file()?;
+    if !matches!(group, Some(ManifestGroup::Dependency(_))) {
+        if let Some(table) = manifest.project_optional_dependencies_mut() {
+            rewrite_table(table, rewrites);
+        }
+    }
+
+    if !matches!(group, Some(ManifestGroup::Optional(_))) {
+        if let Some(table) = manifest.dependency_groups_table_mut() {
+            rewrite_table(table, rewrites);
+        }
+    }
+}
+
+fn rewrite_table(table: &mut Table, rewrites: &Rewrites) {
+    for (group, item) in table.iter_mut() {
+        if let Some(array) = item.as_array_mut() {
+            rewrite_array(array, &group, rewrites);
+        }
+    }
+}
 
-    Ok(())
+fn rewrite_array(array: &mut Array, group: &str, rewrites: &Rewrites) {
+    for value in array.iter_mut() {
+        let Some(mut req) = value.as_str().and_then(|it| Requirement::from_str(it).ok()) else {
+            continue;
+        };
+        let Some(specifiers) = rewrites.get(&(group.to_string(), normalized_name(&req.name)))
+        else {
+            continue;
+        };
+
+        req.version_or_url = Some(VersionOrUrl::VersionSpecifier(specifiers.clone()));
+        let decor = value.decor().clone();
+        *value = Value::from(req.to_string());
+        *value.decor_mut() = decor;
+    }
 }
 
 #[cfg(test)]
@@ -133,6 +346,9 @@ mod tests {
         let ws = config.workspace();
         initialize_venv(ws.root().join(".venv"), &ws.environment()).unwrap();
         let options = UpdateOptions {
+            strategy: UpdateStrategy::default(),
+            group: None,
+            dry_run: false,
             install_options: InstallOptions { values: None },
         };
 
@@ -163,9 +379,120 @@ mod tests {
         let ws = config.workspace();
         initialize_venv(ws.root().join(".venv"), &ws.environment()).unwrap();
         let options = UpdateOptions {
+            strategy: UpdateStrategy::default(),
+            group: None,
+            dry_run: false,
             install_options: InstallOptions { values: None },
         };
 
         update_project_dependencies(None, &config, &options).unwrap();
     }
+
+    #[test]
+    fn test_raise_lower_bounds() {
+        let cases = [
+            (">=1.0,<2", "1.4.2", ">=1.4.2, <2"),
+            ("~=1.0", "1.4.2", "~=1.4"),
+            ("~=1.0.0", "1.0.5", "~=1.0.5"),
+            ("==1.0.3", "1.0.3", "==1.0.3"),
+            ("<2", "1.4.2", "<2"),
+        ];
+
+        for (specifiers, version, expected) in cases {
+            assert_eq!(
+                raise_lower_bounds(
+                    &VersionSpecifiers::from_str(specifiers).unwrap(),
+                    &Version::from_str(version).unwrap()
+                )
+                .to_string(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_rewrite_requirements() {
+        let mut pyproject_toml = PyProjectToml::from_str(
+            r#"[project]
+name = "mock-project"
+dependencies = [
+    "click>=8.0",  # cli
+    "requests[socks]>=2.28; python_version >= '3.8'",
+]
+
+[project.optional-dependencies]
+dev = [
+    "click>=8.0",
+    "pytest",
+]
+"#,
+        )
+        .unwrap();
+        let rewrites = [
+            (REQUIRED_GROUP_NAME, "requests", ">=2.31"),
+            ("dev", "pytest", "==7.4.3"),
+        ]
+        .into_iter()
+        .map(|(group, name, specifiers)| {
+            (
+                (group.to_string(), name.to_string()),
+                VersionSpecifiers::from_str(specifiers).unwrap(),
+            )
+        })
+        .collect();
+
+        rewrite_requirements(&mut pyproject_toml, &rewrites, None);
+
+        assert_eq!(
+            pyproject_toml.to_string(),
+            r#"[project]
+name = "mock-project"
+dependencies = [
+    "click>=8.0",  # cli
+    "requests[socks] >=2.31 ; python_version >= '3.8'",
+]
+
+[project.optional-dependencies]
+dev = [
+    "click>=8.0",
+    "pytest ==7.4.3",
+]
+"#
+        );
+    }
+
+    #[test]
+    fn test_rewrite_requirements_in_dependency_group() {
+        let mut pyproject_toml = PyProjectToml::from_str(
+            r#"[project.optional-dependencies]
+dev = ["pytest"]
+
+[dependency-groups]
+dev = ["pytest", {include-group = "lint"}]
+"#,
+        )
+        .unwrap();
+        let rewrites = [(
+            ("dev".to_string(), "pytest".to_string()),
+            VersionSpecifiers::from_str("==7.4.3").unwrap(),
+        )]
+        .into_iter()
+        .collect();
+
+        rewrite_requirements(
+            &mut pyproject_toml,
+            &rewrites,
+            Some(&ManifestGroup::Dependency("dev".to_string())),
+        );
+
+        assert_eq!(
+            pyproject_toml.to_string(),
+            r#"[project.optional-dependencies]
+dev = ["pytest"]
+
+[dependency-groups]
+dev = ["pytest ==7.4.3", {include-group = "lint"}]
+"#
+        );
+    }
 }
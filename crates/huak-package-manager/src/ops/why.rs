@@ -0,0 +1,109 @@
+use super::tree::{dependency_graph, group_label, project_label};
+use crate::{Config, DependencyPath, Error, GraphPackage, HuakResult};
+
+/// Display every path from the project's dependencies to an installed package.
+///
+/// Each path starts at a dependency listed in `[project.dependencies]`,
+/// `[project.optional-dependencies]` or `[dependency-groups]` and lists the requirement
+/// matched at each hop along with the installed version.
+pub fn display_dependency_paths(name: &str, config: &Config) -> HuakResult<()> {
+    let workspace = config.workspace();
+    let manifest = workspace.current_local_manifest()?;
+    let python_env = workspace.current_python_environment()?;
+    let graph = dependency_graph(&manifest, &python_env)?;
+    let mut terminal = config.terminal();
+
+    let Some(pkg) = graph.get(name) else {
+        return Err(Error::PackageNotFound(name.to_string()));
+    };
+
+    let paths = graph.paths(name);
+    if paths.is_empty() {
+        return terminal.print_warning(format!(
+            "{} v{} isn't required by the project",
+            pkg.name, pkg.version
+        ));
+    }
+
+    terminal.print_stdout(render_paths(pkg, &paths, &project_label(&manifest)))
+}
+
+fn render_paths(pkg: &GraphPackage, paths: &[DependencyPath], project: &str) -> String {
+    let mut lines = vec![format!("{} v{}", pkg.name, pkg.version)];
+
+    for path in paths {
+        lines.push(format!(
+            "{} -> {path}",
+            group_label(project, &path.root.group)
+        ));
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        installed::write_mock_distributions, installed_distributions,
+        python_environment::mock_marker_environment, DependencyGraph, GraphRoot,
+    };
+    use pep508_rs::Requirement;
+    use std::str::FromStr;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_render_paths() {
+        let dir = tempdir().unwrap();
+        write_mock_distributions(
+            dir.path(),
+            &[
+                (
+                    "pytest",
+                    "7.4.3",
+                    &["iniconfig", "packaging", "pluggy<2.0,>=0.12"],
+                ),
+                ("build", "1.0.3", &["packaging>=19.0"]),
+                ("packaging", "23.2", &[]),
+                ("pluggy", "1.3.0", &[]),
+            ],
+        );
+        let roots = [
+            ("dev", "pytest>=7"),
+            ("dev", "build"),
+            ("required", "packaging"),
+        ]
+        .iter()
+        .map(|(group, req)| GraphRoot {
+            group: (*group).to_string(),
+            requirement: Requirement::from_str(req).unwrap(),
+        })
+        .collect();
+        let graph = DependencyGraph::new(
+            &installed_distributions(dir.path()).unwrap(),
+            roots,
+            &mock_marker_environment(),
+        );
+
+        assert_eq!(
+            render_paths(
+                graph.get("packaging").unwrap(),
+                &graph.paths("packaging"),
+                "mock-project v0.0.1"
+            ),
+            r"packaging v23.2
+mock-project v0.0.1 -> packaging (v23.2)
+mock-project v0.0.1 (dev) -> build (v1.0.3) -> packaging>=19.0 (v23.2)
+mock-project v0.0.1 (dev) -> pytest>=7 (v7.4.3) -> packaging (v23.2)"
+        );
+        assert_eq!(
+            render_paths(
+                graph.get("pluggy").unwrap(),
+                &graph.paths("pluggy"),
+                "mock-project v0.0.1"
+            ),
+            r"pluggy v1.3.0
+mock-project v0.0.1 (dev) -> pytest>=7 (v7.4.3) -> pluggy<2.0, >=0.12 (v1.3.0)"
+        );
+    }
+}
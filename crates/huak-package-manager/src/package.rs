@@ -167,6 +167,6 @@ pub fn importable_package_name(name: &str) -> HuakResult<String> {
 }
 
 /// Normalize a name to a distributable and packagable name.
-fn canonical_package_name(name: &str) -> Cow<str> {
+fn canonical_package_name(name: &str) -> Cow<'_, str> {
     PACKAGE_REGEX.replace_all(name, "-")
 }
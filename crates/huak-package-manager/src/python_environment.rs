@@ -1,12 +1,20 @@
 use crate::{
     environment::env_path_values,
     fs::{self, maybe_exe},
+    installed::{installed_distributions, InstalledDistribution},
+    lock::{index_url_from_args, normalized_name, InstallationReport},
     package::Package,
-    sys, Config, Environment, Error, HuakResult,
+    sys,
+    wheel::{add_dist_info_file, remove_dist_info_file},
+    Cache, Config, Dependency, Environment, Error, HuakResult, InstallScheme, PackageSources,
+    Resolver, UnpackedWheel, Wheel,
 };
 use huak_python_manager::Version;
+use pep508_rs::{MarkerEnvironment, Requirement};
+use serde::Deserialize;
 use std::{
     cmp::Ordering,
+    collections::HashMap,
     env::consts::OS,
     fmt::Display,
     fs::File,
@@ -15,17 +23,66 @@ use std::{
     process::Command,
     str::FromStr,
 };
+use termcolor::Color;
 
 const DEFAULT_VENV_NAME: &str = ".venv";
 const VENV_CONFIG_FILE_NAME: &str = "pyvenv.cfg";
-const VIRTUAL_ENV_ENV_VAR: &str = "VIRTUAL_ENV";
-const CONDA_ENV_ENV_VAR: &str = "CONDA_PREFIX";
+pub(crate) const VIRTUAL_ENV_ENV_VAR: &str = "VIRTUAL_ENV";
+pub(crate) const CONDA_ENV_ENV_VAR: &str = "CONDA_PREFIX";
+pub(crate) const CONDA_DEFAULT_ENV_ENV_VAR: &str = "CONDA_DEFAULT_ENV";
+const CONDA_META_DIR_NAME: &str = "conda-meta";
+const PIP_BREAK_SYSTEM_PACKAGES_ENV_VAR: &str = "PIP_BREAK_SYSTEM_PACKAGES";
+const MARKER_ENVIRONMENT_SCRIPT: &str = r#"import json, os, platform, sys
+
+def version(info):
+    v = f"{info.major}.{info.minor}.{info.micro}"
+    if info.releaselevel != "final":
+        v += info.releaselevel[0] + str(info.serial)
+    return v
+
+print(json.dumps({
+    "implementation_name": sys.implementation.name,
+    "implementation_version": version(sys.implementation.version),
+    "os_name": os.name,
+    "platform_machine": platform.machine(),
+    "platform_python_implementation": platform.python_implementation(),
+    "platform_release": platform.release(),
+    "platform_system": platform.system(),
+    "platform_version": platform.version(),
+    "python_full_version": platform.python_version(),
+    "python_version": ".".join(platform.python_version_tuple()[:2]),
+    "sys_platform": sys.platform,
+}))
+"#;
+/// Get the installation paths of a Python installation's own environment. The
+/// EXTERNALLY-MANAGED marker is ignored in virtual environments.
+const SYSTEM_ENVIRONMENT_SCRIPT: &str = r#"import json, os, sys, sysconfig
+
+paths = sysconfig.get_paths()
+marker = os.path.join(paths["stdlib"], "EXTERNALLY-MANAGED")
+
+print(json.dumps({
+    "prefix": sys.prefix,
+    "base_prefix": sys.base_prefix,
+    "executable": sys.executable,
+    "version": "%d.%d.%d" % sys.version_info[:3],
+    "purelib": paths["purelib"],
+    "platlib": paths["platlib"],
+    "scripts": paths["scripts"],
+    "include": paths["include"],
+    "data": paths["data"],
+    "externally_managed": marker
+    if sys.prefix == sys.base_prefix and os.path.isfile(marker)
+    else None,
+}))
+"#;
 
 /// The `PythonEnvironment` is a struct used to intereact with an environment
 /// containing an installed Python `Interpreter` and `Package`s.
 ///
 /// An example of a valid `PythonEnvironment` would be a Virtual environment.
-/// See <https://peps.python.org/pep-0405/>.
+/// See <https://peps.python.org/pep-0405/>. conda environments (directories containing
+/// conda-meta) are supported too.
 ///
 /// The structure of a `Venv` on a system depends on if it is Windows or not.
 ///
@@ -54,6 +111,14 @@ const CONDA_ENV_ENV_VAR: &str = "CONDA_PREFIX";
 /// `Interpreter`, *potentially* the "home" path to the Python `Interpreter` that
 /// generated the `PythonEnvironment`, etc.
 ///
+/// conda environments use the same layout except that on Windows python.exe is located
+/// at the root of the environment.
+///
+/// A Python installation's own environment can be used with `PythonEnvironment::system`.
+/// Its paths are the installation's `sysconfig` paths. These environments can be marked
+/// as externally managed (see <https://peps.python.org/pep-0668/>). Huak refuses to
+/// modify them unless `Config::break_system_packages` is set.
+///
 /// ```
 /// use huak_package_manager::PythonEnvironment;
 ///
@@ -71,6 +136,20 @@ pub struct PythonEnvironment {
     executables_dir_path: PathBuf,
     /// The site-packages directory contains all of the `PythonEnvironment`'s installed Python packages.
     site_packages_path: PathBuf,
+    /// The kind of the `PythonEnvironment`.
+    kind: EnvironmentKind,
+}
+
+/// The kinds of `PythonEnvironment`s.
+enum EnvironmentKind {
+    Venv,
+    Conda,
+    /// A Python installation's own environment with its `InstallScheme` and the path to its
+    /// EXTERNALLY-MANAGED marker if it has one.
+    System {
+        scheme: InstallScheme,
+        externally_managed: Option<PathBuf>,
+    },
 }
 
 impl PythonEnvironment {
@@ -78,14 +157,56 @@ impl PythonEnvironment {
     pub fn new<T: Into<PathBuf>>(path: T) -> HuakResult<Self> {
         let path = path.into();
 
-        // TODO(cnpryer): `PythonEnvironment` shouldn't be exclusive to virtual environments.
-        if !directory_is_venv(&path) {
-            return Err(Error::UnsupportedPythonEnvironment(path));
+        if directory_is_venv(&path) {
+            new_venv(path)
+        } else if directory_is_conda_env(&path) {
+            new_conda_env(path)
+        } else {
+            Err(Error::UnsupportedPythonEnvironment(path))
         }
+    }
 
-        let env = new_venv(path)?;
+    /// Initialize the `PythonEnvironment` of a Python installation from its `Interpreter`
+    /// instead of a virtual environment. Interpreters that belong to a virtual environment
+    /// are unsupported.
+    pub fn system<T: Into<PathBuf>>(python_path: T) -> HuakResult<Self> {
+        let mut cmd = Command::new(python_path.into());
+        cmd.args(["-c", SYSTEM_ENVIRONMENT_SCRIPT]);
+
+        let output = cmd.output()?;
+        if !output.status.success() {
+            return Err(Error::SubprocessFailure(sys::SubprocessError::new(
+                output.status,
+            )));
+        }
+
+        let paths: SystemPaths = serde_json::from_slice(&output.stdout)?;
+        if paths.prefix != paths.base_prefix {
+            return Err(Error::UnsupportedPythonEnvironment(paths.prefix));
+        }
+        let interpreter = Interpreter {
+            version: Version::from_str(&paths.version)?,
+            path: paths.executable.clone(),
+        };
+        let scheme = InstallScheme {
+            purelib: paths.purelib.clone(),
+            platlib: paths.platlib,
+            scripts: paths.scripts.clone(),
+            headers: paths.include,
+            data: paths.data,
+            python_path: paths.executable,
+        };
 
-        Ok(env)
+        Ok(PythonEnvironment {
+            root: paths.prefix,
+            interpreter,
+            executables_dir_path: paths.scripts,
+            site_packages_path: paths.purelib,
+            kind: EnvironmentKind::System {
+                scheme,
+                externally_managed: paths.externally_managed,
+            },
+        })
     }
 
     /// Get a reference to the path to the `PythonEnvironment`.
@@ -105,6 +226,12 @@ impl PythonEnvironment {
         self.interpreter.path()
     }
 
+    /// Get a reference to the `Version` of the `PythonEnvironment`'s Python `Interpreter`.
+    #[must_use]
+    pub fn python_version(&self) -> &Version {
+        self.interpreter.version()
+    }
+
     /// Get a reference to the `PythonEnvironment`'s executables directory path.
     #[must_use]
     pub fn executables_dir_path(&self) -> &PathBuf {
@@ -129,7 +256,33 @@ impl PythonEnvironment {
         &self.site_packages_path
     }
 
+    /// Get the `InstallScheme` used to install wheels to the `PythonEnvironment`.
+    #[must_use]
+    pub fn install_scheme(&self) -> InstallScheme {
+        if let EnvironmentKind::System { scheme, .. } = &self.kind {
+            return scheme.clone();
+        }
+
+        let version = self.python_version();
+
+        InstallScheme {
+            purelib: self.site_packages_path.clone(),
+            platlib: self.site_packages_path.clone(),
+            scripts: self.executables_dir_path.clone(),
+            headers: self
+                .root
+                .join("include")
+                .join("site")
+                .join(format!("python{}.{}", version.major, version.minor)),
+            data: self.root.clone(),
+            python_path: self.python_path().clone(),
+        }
+    }
+
     /// Install Python `Package`s to the `PythonEnvironment`.
+    ///
+    /// Packages are resolved with pip's installation report. Wheels are installed by huak
+    /// and any other distributions (like source distributions) are installed with pip.
     pub fn install_packages<T>(
         &self,
         packages: &[T],
@@ -139,9 +292,77 @@ impl PythonEnvironment {
     where
         T: Display,
     {
-        let mut cmd = Command::new(self.python_path());
-        cmd.args(["-m", "pip", "install"])
-            .args(packages.iter().map(ToString::to_string));
+        self.ensure_modifiable(config)?;
+
+        if options.relocates() {
+            return self.pip_install(packages, &[], options, config);
+        }
+
+        let report = self.resolve(packages, options, config, true)?;
+        self.install_report(&report, options, config)
+    }
+
+    /// Install pinned requirements, like the packages of a lock file, to the
+    /// `PythonEnvironment`.
+    ///
+    /// `hashes` are the hashes locked for each package by normalized name. Distributions
+    /// resolved for a package with locked hashes must match one of them.
+    pub(crate) fn install_locked<T>(
+        &self,
+        requirements: &[T],
+        hashes: &HashMap<String, Vec<String>>,
+        options: &InstallOptions,
+        config: &Config,
+    ) -> HuakResult<()>
+    where
+        T: Display,
+    {
+        self.ensure_modifiable(config)?;
+
+        if options.relocates() {
+            return self.pip_install(requirements, &["--no-deps"], options, config);
+        }
+
+        let report = self.resolve(requirements, options, config, true)?;
+        report.verify_hashes(hashes)?;
+        self.install_report(&report, options, config)
+    }
+
+    /// Install wheel files to the `PythonEnvironment`. Installed distributions with the same
+    /// names are replaced.
+    pub fn install_wheels<T: AsRef<Path>>(&self, wheels: &[T], config: &Config) -> HuakResult<()> {
+        self.ensure_modifiable(config)?;
+
+        let temp_cache = tempfile::tempdir()?;
+        let cache = config
+            .cache()
+            .unwrap_or_else(|| Cache::new(temp_cache.path()));
+
+        for path in wheels {
+            let wheel = cache.add_wheel(&Wheel::new(path.as_ref())?)?;
+            self.install_wheel(&wheel, None, config)?;
+        }
+
+        Ok(())
+    }
+
+    /// Install local Python projects to the `PythonEnvironment` in editable mode.
+    pub fn install_editable_packages<T: AsRef<Path>>(
+        &self,
+        paths: &[T],
+        options: &InstallOptions,
+        config: &Config,
+    ) -> HuakResult<()> {
+        self.ensure_modifiable(config)?;
+
+        let sources = PackageSources::from_config(config, None)?;
+        let dir = tempfile::tempdir()?;
+        let mut cmd = self.pip_command(&sources, dir.path(), config)?;
+        cmd.arg("install").args(sources.pip_args());
+
+        for path in paths {
+            cmd.arg("--editable").arg(path.as_ref());
+        }
 
         if let Some(v) = options.values.as_ref() {
             cmd.args(v.iter().map(String::as_str));
@@ -151,6 +372,8 @@ impl PythonEnvironment {
     }
 
     /// Uninstall Python `Package`s from the `PythonEnvironment`.
+    ///
+    /// Distributions are uninstalled by removing the files listed in their RECORD.
     pub fn uninstall_packages<T>(
         &self,
         packages: &[T],
@@ -160,16 +383,47 @@ impl PythonEnvironment {
     where
         T: Display,
     {
-        let mut cmd = Command::new(self.python_path());
-        cmd.args(["-m", "pip", "uninstall"])
-            .args(packages.iter().map(ToString::to_string))
-            .arg("-y");
+        self.ensure_modifiable(config)?;
 
-        if let Some(v) = options.values.as_ref() {
-            cmd.args(v.iter().map(String::as_str));
+        if options.relocates() {
+            let mut cmd = Command::new(self.python_path());
+            cmd.args(["-m", "pip", "uninstall"])
+                .args(packages.iter().map(ToString::to_string))
+                .arg("-y");
+
+            if let Some(v) = options.values.as_ref() {
+                cmd.args(v.iter().map(String::as_str));
+            }
+
+            return config.terminal().run_command(&mut cmd);
         }
 
-        config.terminal().run_command(&mut cmd)
+        let installed = self.installed_distributions()?;
+
+        for package in packages {
+            let package = package.to_string();
+            let name = Requirement::from_str(&package).map_or(package, |it| it.name);
+
+            match installed
+                .iter()
+                .find(|it| it.normalized_name() == normalized_name(&name))
+            {
+                Some(distribution) => {
+                    distribution.uninstall()?;
+                    config.terminal().print_custom(
+                        "Uninstalled",
+                        format!("{} v{}", distribution.name(), distribution.version()),
+                        Color::Green,
+                        true,
+                    )?;
+                }
+                None => config
+                    .terminal()
+                    .print_warning(format!("{name} is not installed"))?,
+            }
+        }
+
+        Ok(())
     }
 
     /// Update Python `Package`s installed in the `PythonEnvironment`.
@@ -182,8 +436,259 @@ impl PythonEnvironment {
     where
         T: Display,
     {
-        let mut cmd = Command::new(self.python_path());
-        cmd.args(["-m", "pip", "install", "--upgrade"])
+        self.ensure_modifiable(config)?;
+
+        if options.relocates() {
+            return self.pip_install(packages, &["--upgrade"], options, config);
+        }
+
+        let report = self.resolve(packages, options, config, false)?;
+        self.install_report(&report, options, config)
+    }
+
+    /// Resolve Python requirements for the `PythonEnvironment` without installing them.
+    ///
+    /// Packages already installed to the `PythonEnvironment` are preferred. If they conflict
+    /// with the requirements then the requirements are resolved without them.
+    pub fn installation_report<T>(
+        &self,
+        requirements: &[T],
+        options: &InstallOptions,
+        config: &Config,
+    ) -> HuakResult<InstallationReport>
+    where
+        T: Display,
+    {
+        self.resolve(requirements, options, config, true)
+    }
+
+    /// Resolve requirements with the `Resolver`. Installed versions are preferred if
+    /// `prefer_installed` is set.
+    ///
+    /// pip resolves the requirements instead if a distribution has to be built to read its
+    /// metadata (like a VCS reference or a source distribution without static metadata) or
+    /// if the `InstallOptions` include pip arguments that change how packages are resolved.
+    fn resolve<T>(
+        &self,
+        requirements: &[T],
+        options: &InstallOptions,
+        config: &Config,
+        prefer_installed: bool,
+    ) -> HuakResult<InstallationReport>
+    where
+        T: Display,
+    {
+        if !options.changes_resolution() {
+            let sources =
+                PackageSources::from_config(config, index_url_from_args(options.values.as_ref()))?;
+            let dependencies = requirements
+                .iter()
+                .map(|it| Dependency::from_str(&it.to_string()))
+                .collect::<HuakResult<Vec<_>>>()?;
+            let mut resolver = Resolver::new(sources.resolver_options(self.marker_environment()?))?;
+            if prefer_installed {
+                resolver = resolver.with_preferences(self.installed_versions()?);
+            }
+
+            match resolver.resolve(&dependencies) {
+                Ok(it) => return Ok(InstallationReport::from_resolution(&it)),
+                Err(Error::BuildRequired(_)) => (),
+                Err(e) => return Err(e),
+            }
+        }
+
+        self.pip_report(requirements, options, config, prefer_installed)
+    }
+
+    /// Resolve requirements with `pip install --dry-run --report`.
+    fn pip_report<T>(
+        &self,
+        requirements: &[T],
+        options: &InstallOptions,
+        config: &Config,
+        prefer_installed: bool,
+    ) -> HuakResult<InstallationReport>
+    where
+        T: Display,
+    {
+        let sources = PackageSources::from_config(config, None)?;
+        let dir = tempfile::tempdir()?;
+        let report_path = dir.path().join("report.json");
+        let constraints_path = dir.path().join("constraints.txt");
+
+        if prefer_installed {
+            let mut constraints = String::new();
+            for (name, version) in self.installed_versions()? {
+                constraints.push_str(&name);
+                constraints.push_str("==");
+                constraints.push_str(&version.to_string());
+                constraints.push('\n');
+            }
+            std::fs::write(&constraints_path, constraints)?;
+        }
+
+        let report_cmd = |constraints: Option<&Path>| -> HuakResult<Command> {
+            let mut cmd = self.pip_command(&sources, dir.path(), config)?;
+            cmd.args(["install", "--dry-run", "--ignore-installed", "--quiet"])
+                .arg("--report")
+                .arg(&report_path)
+                .args(sources.pip_args())
+                .args(requirements.iter().map(ToString::to_string));
+
+            if let Some(path) = constraints {
+                cmd.arg("-c").arg(path);
+            }
+
+            if let Some(v) = options.values.as_ref() {
+                cmd.args(v.iter().map(String::as_str));
+            }
+
+            Ok(cmd)
+        };
+
+        if !prefer_installed
+            || !report_cmd(Some(&constraints_path))?
+                .output()?
+                .status
+                .success()
+        {
+            config.terminal().run_command(&mut report_cmd(None)?)?;
+        }
+
+        let report = InstallationReport::from_str(&std::fs::read_to_string(report_path)?)?;
+        sources.verify_report(&report)?;
+
+        Ok(report)
+    }
+
+    /// Get the versions of the distributions installed to the `PythonEnvironment` by
+    /// normalized name. Editable installs aren't included.
+    fn installed_versions(&self) -> HuakResult<HashMap<String, pep440_rs::Version>> {
+        Ok(self
+            .installed_distributions()?
+            .into_iter()
+            .filter(|it| !it.direct_url().is_some_and(|url| url.editable))
+            .filter_map(|it| {
+                let version = pep440_rs::Version::from_str(it.version()).ok()?;
+                Some((it.normalized_name(), version))
+            })
+            .collect())
+    }
+
+    /// Install the distributions in an `InstallationReport`.
+    ///
+    /// Distributions already installed with the same version are skipped unless they're
+    /// direct references or --force-reinstall is used. Distributions installed from a direct
+    /// reference are replaced by the same version from an index.
+    fn install_report(
+        &self,
+        report: &InstallationReport,
+        options: &InstallOptions,
+        config: &Config,
+    ) -> HuakResult<()> {
+        let installed = self
+            .installed_distributions()?
+            .into_iter()
+            .filter(|it| !it.direct_url().is_some_and(|url| !url.editable))
+            .map(|it| (it.normalized_name(), it.version().to_string()))
+            .collect::<HashMap<_, _>>();
+        let reinstall = options.has_value("--force-reinstall");
+        let sources = PackageSources::from_config(config, None)?;
+        // Wheels are unpacked to a temporary cache if Huak's home directory can't be found.
+        let temp_cache = tempfile::tempdir()?;
+        let cache = config
+            .cache()
+            .unwrap_or_else(|| Cache::new(temp_cache.path()));
+        let mut requirements = Vec::new();
+        // Non-wheel distributions resolved from an index are installed by pip from their URL.
+        let mut indexed = Vec::new();
+
+        for item in report.items() {
+            let is_installed = installed
+                .get(&normalized_name(item.name()))
+                .is_some_and(|it| it == item.version());
+            if is_installed && !reinstall && !item.is_direct() {
+                continue;
+            }
+
+            let Some(file) = item.wheel_file() else {
+                if !item.is_direct() {
+                    indexed.push(normalized_name(item.name()));
+                }
+                requirements.push(item.file_requirement());
+                continue;
+            };
+
+            if config.offline && file.url.scheme() != "file" && !cache.contains(&file) {
+                return Err(Error::OfflineNetworkRequest(file.url.to_string()));
+            }
+
+            let credentials = sources.credentials.credentials(&file.url);
+            self.install_wheel(
+                &cache.wheel(&file, credentials.as_ref())?,
+                item.direct_url_json(),
+                config,
+            )?;
+        }
+
+        if requirements.is_empty() {
+            return Ok(());
+        }
+
+        self.pip_install(&requirements, &["--no-deps"], options, config)?;
+
+        // pip records distributions installed from a URL as direct references.
+        for distribution in self.installed_distributions()? {
+            if indexed.contains(&distribution.normalized_name()) {
+                remove_dist_info_file(&distribution, "direct_url.json")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Install an `UnpackedWheel` by linking its files, replacing an installed distribution
+    /// with the same name.
+    fn install_wheel(
+        &self,
+        wheel: &UnpackedWheel,
+        direct_url: Option<String>,
+        config: &Config,
+    ) -> HuakResult<()> {
+        let name = normalized_name(wheel.name());
+
+        for distribution in self.installed_distributions()? {
+            if distribution.normalized_name() == name {
+                distribution.uninstall()?;
+            }
+        }
+
+        let distribution = wheel.link(&self.install_scheme())?;
+        if let Some(it) = direct_url {
+            add_dist_info_file(&distribution, "direct_url.json", it.as_bytes())?;
+        }
+
+        config.terminal().print_custom(
+            "Installed",
+            format!("{} v{}", distribution.name(), distribution.version()),
+            Color::Green,
+            true,
+        )
+    }
+
+    fn pip_install<T: Display>(
+        &self,
+        packages: &[T],
+        args: &[&str],
+        options: &InstallOptions,
+        config: &Config,
+    ) -> HuakResult<()> {
+        let sources = PackageSources::from_config(config, None)?;
+        let dir = tempfile::tempdir()?;
+        let mut cmd = self.pip_command(&sources, dir.path(), config)?;
+        cmd.arg("install")
+            .args(args)
+            .args(sources.pip_args())
             .args(packages.iter().map(ToString::to_string));
 
         if let Some(v) = options.values.as_ref() {
@@ -193,6 +698,70 @@ impl PythonEnvironment {
         config.terminal().run_command(&mut cmd)
     }
 
+    /// Get a pip `Command` for the `PythonEnvironment`. Credentials for the `PackageSources`
+    /// are passed to pip with a netrc file written to `dir`.
+    ///
+    /// Virtual environments without pip get it from ensurepip first. Any other
+    /// `PythonEnvironment` without pip is left alone and an error is returned instead.
+    fn pip_command(
+        &self,
+        sources: &PackageSources,
+        dir: &Path,
+        config: &Config,
+    ) -> HuakResult<Command> {
+        if !self
+            .installed_distributions()?
+            .iter()
+            .any(|it| it.normalized_name() == "pip")
+        {
+            if !matches!(self.kind, EnvironmentKind::Venv) {
+                return Err(Error::PipNotFound(self.root.clone()));
+            }
+
+            let mut terminal = config.terminal();
+            terminal.print_custom(
+                "Installing",
+                format!("pip to {} with ensurepip", self.root.display()),
+                Color::Green,
+                true,
+            )?;
+            let mut cmd = Command::new(self.python_path());
+            cmd.args(["-Im", "ensurepip", "--default-pip"]);
+            terminal.run_command(&mut cmd)?;
+        }
+
+        let mut cmd = Command::new(self.python_path());
+        cmd.args(["-m", "pip"]);
+
+        // pip refuses to modify externally managed environments. Huak checks if they can
+        // be modified before running pip.
+        if self.externally_managed().is_some() {
+            cmd.env(PIP_BREAK_SYSTEM_PACKAGES_ENV_VAR, "1");
+        }
+
+        let netrc = dir.join("netrc");
+        if sources.credentials.write_netrc(&netrc)? {
+            cmd.env("NETRC", netrc);
+        }
+
+        Ok(cmd)
+    }
+
+    /// Get the `MarkerEnvironment` used to evaluate PEP 508 markers for the `PythonEnvironment`.
+    pub fn marker_environment(&self) -> HuakResult<MarkerEnvironment> {
+        let mut cmd = Command::new(self.python_path());
+        cmd.args(["-c", MARKER_ENVIRONMENT_SCRIPT]);
+
+        let output = cmd.output()?;
+        if !output.status.success() {
+            return Err(Error::SubprocessFailure(sys::SubprocessError::new(
+                output.status,
+            )));
+        }
+
+        Ok(serde_json::from_slice(&output.stdout)?)
+    }
+
     /// Check if the `PythonEnvironment` has a module installed in the executables directory.
     pub fn contains_module(&self, module_name: &str) -> HuakResult<bool> {
         let dir = self.executables_dir_path();
@@ -214,24 +783,71 @@ impl PythonEnvironment {
     /// Check if the `PythonEnvironment` has a `Package` already installed.
     #[must_use]
     pub fn contains_package(&self, package: &Package) -> bool {
-        self.site_packages_dir_path().join(package.name()).exists()
+        let name = normalized_name(package.name());
+
+        self.installed_distributions()
+            .is_ok_and(|it| it.iter().any(|it| it.normalized_name() == name))
     }
 
     /// Get all of the `Package`s installed in the `PythonEnvironment`.
+    ///
+    /// Packages are read from the distributions' metadata in the site-packages directory.
+    /// Distributions with versions that aren't PEP 440 compliant are skipped.
     pub fn installed_packages(&self) -> HuakResult<Vec<Package>> {
-        let mut cmd = Command::new(self.python_path());
-        cmd.args(["-m", "pip", "freeze"]);
+        let packages = self
+            .installed_distributions()?
+            .iter()
+            .filter_map(|it| Package::from_str(format!("{}=={}", it.name(), it.version())).ok())
+            .collect();
 
-        let output = cmd.output()?;
-        let output = sys::parse_command_output(&output)?;
-        let mut packages = Vec::new();
-        for line in output.split('\n') {
-            if !line.is_empty() {
-                packages.push(Package::from_str(line)?);
+        Ok(packages)
+    }
+
+    /// Get the `InstalledDistribution`s in the `PythonEnvironment`'s site-packages directory.
+    pub fn installed_distributions(&self) -> HuakResult<Vec<InstalledDistribution>> {
+        installed_distributions(self.site_packages_dir_path())
+    }
+
+    /// Check if the `PythonEnvironment` is a conda environment.
+    #[must_use]
+    pub fn is_conda(&self) -> bool {
+        matches!(self.kind, EnvironmentKind::Conda)
+    }
+
+    /// Check if the `PythonEnvironment` is a Python installation's own environment.
+    #[must_use]
+    pub fn is_system(&self) -> bool {
+        matches!(self.kind, EnvironmentKind::System { .. })
+    }
+
+    /// Get the error message of the `PythonEnvironment`'s EXTERNALLY-MANAGED marker if the
+    /// environment is externally managed.
+    #[must_use]
+    pub fn externally_managed(&self) -> Option<String> {
+        let EnvironmentKind::System {
+            externally_managed: Some(path),
+            ..
+        } = &self.kind
+        else {
+            return None;
+        };
+
+        let message = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|it| externally_managed_message(&it))
+            .unwrap_or_else(|| format!("see {}", path.display()));
+
+        Some(message)
+    }
+
+    /// Check if packages can be installed to or uninstalled from the `PythonEnvironment`.
+    fn ensure_modifiable(&self, config: &Config) -> HuakResult<()> {
+        match self.externally_managed() {
+            Some(message) if !config.break_system_packages => {
+                Err(Error::ExternallyManagedEnvironment(message))
             }
+            _ => Ok(()),
         }
-
-        Ok(packages)
     }
 
     /// Check if the `PythonEnvironment` is already activated.
@@ -256,16 +872,7 @@ fn new_venv<T: Into<PathBuf>>(path: T) -> HuakResult<PythonEnvironment> {
 
     let config = VenvConfig::new(root.join(VENV_CONFIG_FILE_NAME))?;
     let version = config.version;
-
-    // On Unix systems the Venv's site-package directory depends on the Python version.
-    // The path is root/lib/pythonX.X/site-packages.
-    #[cfg(unix)]
-    let site_packages_path = root
-        .join("lib")
-        .join(format!("python{}.{}", version.major, version.minor))
-        .join("site-packages");
-    #[cfg(windows)]
-    let site_packages_path = root.join("Lib").join("site-packages");
+    let site_packages_path = site_packages_dir_path(&root, version);
 
     let interpreter = Interpreter {
         version,
@@ -277,16 +884,144 @@ fn new_venv<T: Into<PathBuf>>(path: T) -> HuakResult<PythonEnvironment> {
         interpreter,
         executables_dir_path,
         site_packages_path,
+        kind: EnvironmentKind::Venv,
     };
 
     Ok(venv)
 }
 
+/// Helper function for creating a conda environment as a `PythonEnvironment`.
+fn new_conda_env<T: Into<PathBuf>>(path: T) -> HuakResult<PythonEnvironment> {
+    let root = path.into();
+    let executables_dir_path = venv_executables_dir_path(&root);
+
+    #[cfg(unix)]
+    let python_path = executables_dir_path.join("python");
+    #[cfg(windows)]
+    let python_path = root.join("python.exe");
+
+    if !python_path.exists() {
+        return Err(Error::UnsupportedPythonEnvironment(root));
+    }
+
+    // conda records the installed Python package in conda-meta. Python is asked for its
+    // version if the record isn't found.
+    let version = match conda_python_version(&root) {
+        Some(it) => it,
+        None => parse_python_version_from_command(&python_path)?
+            .ok_or_else(|| Error::UnsupportedPythonEnvironment(root.clone()))?,
+    };
+    let site_packages_path = site_packages_dir_path(&root, version);
+
+    let interpreter = Interpreter {
+        version,
+        path: python_path,
+    };
+
+    Ok(PythonEnvironment {
+        root,
+        interpreter,
+        executables_dir_path,
+        site_packages_path,
+        kind: EnvironmentKind::Conda,
+    })
+}
+
+/// The `sysconfig` paths of a Python installation (see `SYSTEM_ENVIRONMENT_SCRIPT`).
+#[derive(Deserialize)]
+struct SystemPaths {
+    prefix: PathBuf,
+    base_prefix: PathBuf,
+    executable: PathBuf,
+    version: String,
+    purelib: PathBuf,
+    platlib: PathBuf,
+    scripts: PathBuf,
+    include: PathBuf,
+    data: PathBuf,
+    externally_managed: Option<PathBuf>,
+}
+
+/// Get the `Error` message of an EXTERNALLY-MANAGED file's `[externally-managed]` section.
+///
+/// See <https://packaging.python.org/en/latest/specifications/externally-managed-environments/>.
+fn externally_managed_message(contents: &str) -> Option<String> {
+    let mut in_section = false;
+    let mut message: Option<String> = None;
+
+    for line in contents.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with('[') {
+            if message.is_some() {
+                break;
+            }
+            in_section = trimmed == "[externally-managed]";
+            continue;
+        }
+        if !in_section || trimmed.starts_with(['#', ';']) {
+            continue;
+        }
+
+        // Continuation lines of the message are indented.
+        if let Some(it) = message.as_mut() {
+            if trimmed.is_empty() || line.starts_with([' ', '\t']) {
+                it.push('\n');
+                it.push_str(trimmed);
+                continue;
+            }
+            break;
+        }
+
+        if let Some((key, value)) = trimmed.split_once(['=', ':']) {
+            if key.trim() == "Error" {
+                message = Some(value.trim().to_string());
+            }
+        }
+    }
+
+    message.map(|it| it.trim().to_string())
+}
+
+/// Get the `Version` of Python from a conda environment's conda-meta record of the
+/// python package (python-X.Y.Z-build.json).
+fn conda_python_version(root: &Path) -> Option<Version> {
+    std::fs::read_dir(root.join(CONDA_META_DIR_NAME))
+        .ok()?
+        .filter_map(Result::ok)
+        .find_map(|entry| {
+            let file_name = entry.file_name();
+            let record = file_name.to_str()?.strip_prefix("python-")?;
+            Version::from_str(record.split('-').next()?).ok()
+        })
+}
+
+/// Get the site-packages directory path of a `PythonEnvironment`. On Unix systems the
+/// directory depends on the Python version. The path is root/lib/pythonX.X/site-packages.
+pub(crate) fn site_packages_dir_path(root: &Path, version: Version) -> PathBuf {
+    #[cfg(unix)]
+    let path = root
+        .join("lib")
+        .join(format!("python{}.{}", version.major, version.minor))
+        .join("site-packages");
+    #[cfg(windows)]
+    let path = {
+        let _ = version;
+        root.join("Lib").join("site-packages")
+    };
+    path
+}
+
 /// Helper for detecting virtual environment directories.
 pub fn directory_is_venv<T: Into<PathBuf>>(path: T) -> bool {
     path.into().join(VENV_CONFIG_FILE_NAME).exists()
 }
 
+/// Helper for detecting conda environment directories.
+pub fn directory_is_conda_env<T: Into<PathBuf>>(path: T) -> bool {
+    path.into().join(CONDA_META_DIR_NAME).is_dir()
+}
+
 /// Helper for constructing OS-dependent venv executables directory path.
 pub fn venv_executables_dir_path<T: Into<PathBuf>>(root: T) -> PathBuf {
     let root = root.into();
@@ -305,17 +1040,59 @@ pub struct InstallOptions {
     pub values: Option<Vec<String>>,
 }
 
+impl InstallOptions {
+    fn has_value(&self, value: &str) -> bool {
+        self.values.as_ref().is_some_and(|it| {
+            it.iter()
+                .any(|v| v == value || v.starts_with(&format!("{value}=")))
+        })
+    }
+
+    /// Check if the options include pip arguments that change how packages are resolved.
+    /// --force-reinstall and --index-url are supported without pip.
+    fn changes_resolution(&self) -> bool {
+        let mut values = self.values.iter().flatten();
+
+        while let Some(it) = values.next() {
+            if it == "--index-url" || it == "-i" {
+                values.next();
+            } else if it != "--force-reinstall" && !it.starts_with("--index-url=") {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Check if the options install packages outside of the environment. pip handles
+    /// those installs.
+    fn relocates(&self) -> bool {
+        ["--target", "-t", "--prefix", "--root", "--user"]
+            .iter()
+            .any(|it| self.has_value(it))
+    }
+}
+
 /// Python virtual environment configuration data (pyvenv.cfg).
 ///
 /// See <https://docs.python.org/3/library/venv.html>.
-struct VenvConfig {
+pub(crate) struct VenvConfig {
+    /// The directory containing the Python `Interpreter` the virtual environment was
+    /// created from.
+    pub(crate) home: Option<PathBuf>,
+    /// Whether the base Python's site-packages are available in the virtual environment.
+    pub(crate) include_system_site_packages: bool,
     /// The `Version` of the virtual environment's Python `Interpreter`.
-    version: Version,
+    pub(crate) version: Version,
+    /// The path to the Python `Interpreter` the virtual environment was created from.
+    pub(crate) executable: Option<PathBuf>,
+    /// The prompt activation scripts display.
+    pub(crate) prompt: Option<String>,
 }
 
 impl VenvConfig {
     /// Initialize a new `VenvConfig` from the pvenv.cfg path.
-    fn new<T: Into<PathBuf>>(path: T) -> HuakResult<Self> {
+    pub(crate) fn new<T: Into<PathBuf>>(path: T) -> HuakResult<Self> {
         let path = path.into();
 
         // Read the file and flatten the lines for parsing.
@@ -327,21 +1104,65 @@ impl VenvConfig {
             .map_while(Result::ok)
             .collect::<Vec<String>>();
 
-        // Search for version = "X.X.X"
-        let mut version = Version::from_str("0.0.0")?;
+        let mut cfg = VenvConfig {
+            home: None,
+            include_system_site_packages: false,
+            version: Version::from_str("0.0.0")?,
+            executable: None,
+            prompt: None,
+        };
+
         for item in lines {
             let mut split = item.splitn(2, '=');
             let key = split.next().unwrap_or_default().trim();
             let val = split.next().unwrap_or_default().trim();
-            if key == "version" {
-                version = Version::from_str(val)?;
+            match key {
+                "home" => cfg.home = Some(PathBuf::from(val)),
+                "include-system-site-packages" => {
+                    cfg.include_system_site_packages = val.eq_ignore_ascii_case("true");
+                }
+                "version" => cfg.version = Version::from_str(val)?,
+                "executable" => cfg.executable = Some(PathBuf::from(val)),
+                "prompt" => {
+                    let prompt = val
+                        .strip_prefix('\'')
+                        .and_then(|it| it.strip_suffix('\''))
+                        .unwrap_or(val);
+                    cfg.prompt = Some(prompt.to_string());
+                }
+                _ => (),
             }
         }
 
-        let cfg = VenvConfig { version };
-
         Ok(cfg)
     }
+
+    /// Write the `VenvConfig` to a pyvenv.cfg path.
+    pub(crate) fn write<T: AsRef<Path>>(&self, path: T) -> HuakResult<()> {
+        Ok(std::fs::write(path, self.to_string())?)
+    }
+}
+
+impl Display for VenvConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(home) = self.home.as_ref() {
+            writeln!(f, "home = {}", home.display())?;
+        }
+        writeln!(
+            f,
+            "include-system-site-packages = {}",
+            self.include_system_site_packages
+        )?;
+        writeln!(f, "version = {}", self.version)?;
+        if let Some(executable) = self.executable.as_ref() {
+            writeln!(f, "executable = {}", executable.display())?;
+        }
+        if let Some(prompt) = self.prompt.as_ref() {
+            writeln!(f, "prompt = '{prompt}'")?;
+        }
+
+        Ok(())
+    }
 }
 
 /// A wrapper for a collection of `Interpreter`s.
@@ -607,6 +1428,35 @@ pub fn initialize_venv<T: AsRef<Path>>(path: T, env: &Environment) -> HuakResult
     Ok(())
 }
 
+/// A `MarkerEnvironment` for `CPython` 3.11 on x86-64 Linux used by tests.
+#[cfg(test)]
+pub(crate) fn mock_marker_environment() -> MarkerEnvironment {
+    let version = |s: &str| pep508_rs::StringVersion::from_str(s).unwrap();
+    MarkerEnvironment {
+        implementation_name: "cpython".to_string(),
+        implementation_version: version("3.11.7"),
+        os_name: "posix".to_string(),
+        platform_machine: "x86_64".to_string(),
+        platform_python_implementation: "CPython".to_string(),
+        platform_release: String::new(),
+        platform_system: "Linux".to_string(),
+        platform_version: String::new(),
+        python_full_version: version("3.11.7"),
+        python_version: version("3.11"),
+        sys_platform: "linux".to_string(),
+    }
+}
+
+/// Serialize tests that search `PATH` for interpreters with the tests that change it. The
+/// guard is still returned if a test panicked while holding it.
+#[cfg(test)]
+pub(crate) fn lock_path_env() -> std::sync::MutexGuard<'static, ()> {
+    static PATH_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    PATH_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{TerminalOptions, Verbosity};
@@ -650,19 +1500,209 @@ mod tests {
         assert!(path.exists());
     }
 
+    #[test]
+    fn conda_python_environment() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("my-env");
+        let conda_meta = root.join("conda-meta");
+        std::fs::create_dir_all(&conda_meta).unwrap();
+        for record in [
+            "python-dateutil-2.8.2-pyhd3eb1b0_0.json",
+            "python-3.11.7-h955ad1f_0.json",
+            "python_abi-3.11-2_cp311.json",
+        ] {
+            std::fs::write(conda_meta.join(record), "{}").unwrap();
+        }
+        #[cfg(unix)]
+        let python_path = root.join("bin").join("python");
+        #[cfg(windows)]
+        let python_path = root.join("python.exe");
+        std::fs::create_dir_all(python_path.parent().unwrap()).unwrap();
+        std::fs::write(&python_path, "").unwrap();
+
+        let env = PythonEnvironment::new(&root).unwrap();
+
+        assert!(env.is_conda());
+        assert_eq!(env.python_path(), &python_path);
+        assert_eq!(env.python_version(), &Version::new(3, 11, 7));
+        assert_eq!(
+            env.site_packages_dir_path(),
+            &site_packages_dir_path(&root, Version::new(3, 11, 7))
+        );
+        assert!(matches!(
+            PythonEnvironment::new(dir.path()),
+            Err(Error::UnsupportedPythonEnvironment(_))
+        ));
+    }
+
+    #[test]
+    fn system_python_environment() {
+        let dir = tempdir().unwrap();
+        let env = {
+            let _path = lock_path_env();
+            python_paths()
+                .find_map(|(_, path)| PythonEnvironment::system(path).ok())
+                .unwrap()
+        };
+        let venv_root = dir.path().join(".venv");
+        let status = Command::new(env.python_path())
+            .args(["-m", "venv", "--without-pip"])
+            .arg(&venv_root)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        let venv = PythonEnvironment::new(&venv_root).unwrap();
+
+        assert!(env.is_system());
+        assert!(matches!(
+            PythonEnvironment::system(venv.python_path()),
+            Err(Error::UnsupportedPythonEnvironment(_))
+        ));
+    }
+
+    #[test]
+    fn install_packages_without_pip() {
+        let dir = tempdir().unwrap();
+        let env = {
+            let _path = lock_path_env();
+            python_paths()
+                .find_map(|(_, path)| PythonEnvironment::system(path).ok())
+                .unwrap()
+        };
+        let venv_root = dir.path().join(".venv");
+        let status = Command::new(env.python_path())
+            .args(["-m", "venv", "--without-pip"])
+            .arg(&venv_root)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        let venv = PythonEnvironment::new(&venv_root).unwrap();
+        let index = dir.path().join("index");
+        crate::resolve::write_mock_index(
+            &index,
+            &[
+                ("app", &[("1.0.0", &["lib>=1"])], true),
+                ("lib", &[("1.0.0", &[]), ("2.0.0", &[])], false),
+            ],
+        );
+        let url = url::Url::from_directory_path(index.join("simple")).unwrap();
+        let options = InstallOptions {
+            values: Some(vec!["--index-url".to_string(), url.to_string()]),
+        };
+        let config = Config {
+            workspace_root: dir.path().to_path_buf(),
+            cwd: dir.path().to_path_buf(),
+            terminal_options: TerminalOptions {
+                verbosity: Verbosity::Quiet,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        venv.install_packages(&["app"], &options, &config).unwrap();
+
+        let mut installed = venv
+            .installed_distributions()
+            .unwrap()
+            .iter()
+            .map(|it| format!("{}=={}", it.normalized_name(), it.version()))
+            .collect::<Vec<_>>();
+        installed.sort();
+        assert_eq!(installed, ["app==1.0.0", "lib==2.0.0"]);
+    }
+
+    #[test]
+    fn externally_managed_python_environment() {
+        let dir = tempdir().unwrap();
+        let marker = dir.path().join("EXTERNALLY-MANAGED");
+        std::fs::write(
+            &marker,
+            "[externally-managed]\nError=To install Python packages system-wide, try apt install\n python3-xyz.\n \n See /usr/share/doc/python3.11/README.venv.\n",
+        )
+        .unwrap();
+        let scheme = InstallScheme {
+            purelib: dir.path().join("site-packages"),
+            platlib: dir.path().join("site-packages"),
+            scripts: dir.path().join("bin"),
+            headers: dir.path().join("include"),
+            data: dir.path().to_path_buf(),
+            python_path: dir.path().join("bin").join("python3"),
+        };
+        let env = PythonEnvironment {
+            root: dir.path().to_path_buf(),
+            interpreter: Interpreter::new(&scheme.python_path, Version::new(3, 11, 7)),
+            executables_dir_path: scheme.scripts.clone(),
+            site_packages_path: scheme.purelib.clone(),
+            kind: EnvironmentKind::System {
+                scheme,
+                externally_managed: Some(marker),
+            },
+        };
+        let mut config = Config::default();
+        let wheels: [&Path; 0] = [];
+
+        assert!(env.is_system());
+        assert_eq!(
+            env.externally_managed().unwrap(),
+            "To install Python packages system-wide, try apt install\npython3-xyz.\n\nSee /usr/share/doc/python3.11/README.venv."
+        );
+        assert!(matches!(
+            env.install_wheels(&wheels, &config),
+            Err(Error::ExternallyManagedEnvironment(_))
+        ));
+
+        config.break_system_packages = true;
+
+        assert!(env.install_wheels(&wheels, &config).is_ok());
+    }
+
+    #[test]
+    fn system_python_environment_without_pip() {
+        let dir = tempdir().unwrap();
+        let scheme = InstallScheme {
+            purelib: dir.path().join("site-packages"),
+            platlib: dir.path().join("site-packages"),
+            scripts: dir.path().join("bin"),
+            headers: dir.path().join("include"),
+            data: dir.path().to_path_buf(),
+            python_path: dir.path().join("bin").join("python3"),
+        };
+        std::fs::create_dir_all(&scheme.purelib).unwrap();
+        let env = PythonEnvironment {
+            root: dir.path().to_path_buf(),
+            interpreter: Interpreter::new(&scheme.python_path, Version::new(3, 11, 7)),
+            executables_dir_path: scheme.scripts.clone(),
+            site_packages_path: scheme.purelib.clone(),
+            kind: EnvironmentKind::System {
+                scheme,
+                externally_managed: None,
+            },
+        };
+        let sources = PackageSources::default();
+
+        assert!(matches!(
+            env.pip_command(&sources, dir.path(), &Config::default()),
+            Err(Error::PipNotFound(path)) if path == dir.path()
+        ));
+    }
+
     #[cfg(unix)]
     #[test]
     fn python_search() {
         let dir = tempdir().unwrap();
         std::fs::write(dir.path().join("python3.11"), "").unwrap();
+        let _path = lock_path_env();
+        let original = std::env::var_os("PATH");
         let path_vals = [dir.path().to_str().unwrap().to_string()];
         std::env::set_var("PATH", path_vals.join(":"));
         let mut interpreter_paths = python_paths();
 
-        assert_eq!(
-            interpreter_paths.next().unwrap().1,
-            dir.path().join("python3.11")
-        );
+        let first = interpreter_paths.next().unwrap().1;
+        if let Some(it) = original {
+            std::env::set_var("PATH", it);
+        }
+
+        assert_eq!(first, dir.path().join("python3.11"));
     }
 
     #[cfg(windows)]
@@ -670,13 +1710,17 @@ mod tests {
     fn python_search() {
         let dir = tempdir().unwrap();
         std::fs::write(dir.path().join("python.exe"), "").unwrap();
+        let _path = lock_path_env();
+        let original = std::env::var_os("PATH");
         let path_vals = vec![dir.path().to_str().unwrap().to_string()];
         std::env::set_var("PATH", path_vals.join(":"));
         let mut interpreter_paths = python_paths();
 
-        assert_eq!(
-            interpreter_paths.next().unwrap().1,
-            dir.path().join("python.exe")
-        );
+        let first = interpreter_paths.next().unwrap().1;
+        if let Some(it) = original {
+            std::env::set_var("PATH", it);
+        }
+
+        assert_eq!(first, dir.path().join("python.exe"));
     }
 }
@@ -0,0 +1,406 @@
+use crate::{lock::normalized_name, CoreMetadata, Error, HuakResult};
+use pep440_rs::Version;
+use pep508_rs::MarkerEnvironment;
+use std::{
+    io::{Cursor, Read},
+    str::FromStr,
+};
+
+/// The parsed file name of a wheel.
+///
+/// See <https://packaging.python.org/en/latest/specifications/binary-distribution-format/>.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WheelFilename {
+    /// The normalized name of the distribution.
+    pub name: String,
+    /// The PEP 440 `Version` of the distribution.
+    pub version: Version,
+    /// The Python tags the wheel supports (for example `py3` or `cp311`).
+    pub python_tags: Vec<String>,
+    /// The ABI tags the wheel supports (for example `none`, `abi3` or `cp311`).
+    pub abi_tags: Vec<String>,
+    /// The platform tags the wheel supports (for example `any` or `win_amd64`).
+    pub platform_tags: Vec<String>,
+}
+
+impl FromStr for WheelFilename {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidMetadata(format!("invalid wheel file name: {s}"));
+        let stem = s.strip_suffix(".whl").ok_or_else(invalid)?;
+        let parts = stem.split('-').collect::<Vec<_>>();
+
+        // {name}-{version}(-{build})?-{python}-{abi}-{platform}
+        let (name, version, tags) = match parts.as_slice() {
+            [name, version, tags @ ..] if tags.len() == 3 || tags.len() == 4 => {
+                (name, version, &tags[tags.len() - 3..])
+            }
+            _ => return Err(invalid()),
+        };
+        let split = |tag: &str| tag.split('.').map(ToString::to_string).collect();
+
+        Ok(Self {
+            name: normalized_name(name),
+            version: Version::from_str(version).map_err(|_| invalid())?,
+            python_tags: split(tags[0]),
+            abi_tags: split(tags[1]),
+            platform_tags: split(tags[2]),
+        })
+    }
+}
+
+/// Get the `Version` of a source distribution from its file name.
+pub(crate) fn sdist_version(filename: &str, name: &str) -> Option<Version> {
+    let stem = [".tar.gz", ".zip"]
+        .iter()
+        .find_map(|ext| filename.strip_suffix(ext))?;
+    let name = normalized_name(name);
+
+    // Names in older sdists aren't normalized and can contain '-' so each split is tried.
+    stem.match_indices('-').find_map(|(i, _)| {
+        if normalized_name(&stem[..i]) == name {
+            Version::from_str(&stem[i + 1..]).ok()
+        } else {
+            None
+        }
+    })
+}
+
+/// The wheel `Tags` supported by a target environment.
+///
+/// See <https://packaging.python.org/en/latest/specifications/platform-compatibility-tags/>.
+#[derive(Clone, Debug)]
+pub struct Tags {
+    /// The interpreter abbreviation (for example "cp" for `CPython`).
+    implementation: String,
+    /// The major version of Python.
+    major: u64,
+    /// The minor version of Python.
+    minor: u64,
+    /// The platform tags supported by the environment. "any" is always supported.
+    platforms: Vec<String>,
+}
+
+impl Tags {
+    /// Initialize `Tags` for a specific Python version and platforms.
+    #[must_use]
+    pub fn new(implementation: &str, major: u64, minor: u64, platforms: Vec<String>) -> Self {
+        Self {
+            implementation: implementation.to_string(),
+            major,
+            minor,
+            platforms,
+        }
+    }
+
+    /// Initialize the `Tags` supported by a `MarkerEnvironment`.
+    #[must_use]
+    pub fn from_marker_environment(env: &MarkerEnvironment) -> Self {
+        let implementation = match env.implementation_name.as_str() {
+            "cpython" => "cp",
+            "pypy" => "pp",
+            "ironpython" => "ip",
+            "jython" => "jy",
+            _ => "py",
+        };
+        let release = &env.python_version.version.release;
+        let major = release.first().copied().unwrap_or(3);
+        let minor = release.get(1).copied().unwrap_or_default();
+
+        Self::new(
+            implementation,
+            major,
+            minor,
+            platform_tags(&env.sys_platform, &env.platform_machine),
+        )
+    }
+
+    /// Check if a wheel is compatible with the `Tags`.
+    #[must_use]
+    pub fn is_compatible(&self, wheel: &WheelFilename) -> bool {
+        self.priority(wheel).is_some()
+    }
+
+    /// Get the priority of a compatible wheel. Platform-specific wheels are preferred.
+    pub(crate) fn priority(&self, wheel: &WheelFilename) -> Option<usize> {
+        let mut best = None;
+
+        for py in &wheel.python_tags {
+            for abi in &wheel.abi_tags {
+                for platform in &wheel.platform_tags {
+                    if !self.supports(py, abi, platform) {
+                        continue;
+                    }
+                    let priority = if platform == "any" {
+                        0
+                    } else {
+                        self.platforms.len()
+                            - self
+                                .platforms
+                                .iter()
+                                .position(|it| it == platform)
+                                .unwrap_or(0)
+                    };
+                    best = best.max(Some(priority));
+                }
+            }
+        }
+
+        best
+    }
+
+    fn supports(&self, python: &str, abi: &str, platform: &str) -> bool {
+        if platform != "any" && !self.platforms.iter().any(|it| it == platform) {
+            return false;
+        }
+
+        let prefix = python.trim_end_matches(|c: char| c.is_ascii_digit());
+        let digits = &python[prefix.len()..];
+        let (major, minor) = if digits.len() > 1 {
+            (
+                digits[..1].parse::<u64>().ok(),
+                digits[1..].parse::<u64>().ok(),
+            )
+        } else {
+            (digits.parse::<u64>().ok(), None)
+        };
+
+        if major != Some(self.major) {
+            return false;
+        }
+
+        let is_implementation = prefix == self.implementation;
+        match abi {
+            "none" => match minor {
+                None => prefix == "py" || is_implementation,
+                Some(minor) if prefix == "py" => minor <= self.minor,
+                Some(minor) => is_implementation && minor == self.minor,
+            },
+            "abi3" => is_implementation && minor.is_some_and(|it| it <= self.minor),
+            _ => {
+                is_implementation
+                    && minor == Some(self.minor)
+                    && abi.trim_end_matches(['m', 'd', 'u'])
+                        == format!("{}{}{}", self.implementation, self.major, self.minor)
+            }
+        }
+    }
+}
+
+/// Get the platform tags for a platform (`sys.platform`) and machine.
+fn platform_tags(sys_platform: &str, machine: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+
+    if sys_platform.starts_with("linux") {
+        let arch = machine.to_lowercase();
+        for minor in (17..=39).rev() {
+            tags.push(format!("manylinux_2_{minor}_{arch}"));
+        }
+        tags.push(format!("manylinux2014_{arch}"));
+        if arch == "x86_64" || arch == "i686" {
+            tags.push(format!("manylinux2010_{arch}"));
+            tags.push(format!("manylinux1_{arch}"));
+        }
+        tags.push(format!("linux_{arch}"));
+    } else if sys_platform == "darwin" {
+        let arches: &[&str] = if machine == "arm64" {
+            &["arm64", "universal2"]
+        } else {
+            &["x86_64", "universal2", "intel", "universal"]
+        };
+        for major in (11..=14).rev() {
+            for arch in arches {
+                tags.push(format!("macosx_{major}_0_{arch}"));
+            }
+        }
+        for minor in (9..=16).rev() {
+            for arch in arches {
+                tags.push(format!("macosx_10_{minor}_{arch}"));
+            }
+        }
+    } else if sys_platform == "win32" {
+        match machine.to_lowercase().as_str() {
+            "amd64" | "x86_64" => tags.push("win_amd64".to_string()),
+            "arm64" => tags.push("win_arm64".to_string()),
+            _ => tags.push("win32".to_string()),
+        }
+    }
+
+    tags
+}
+
+/// Check if a file name has an extension. Extensions are compared case-insensitively.
+pub(crate) fn has_extension(filename: &str, extension: &str) -> bool {
+    std::path::Path::new(filename)
+        .extension()
+        .is_some_and(|it| it.eq_ignore_ascii_case(extension))
+}
+
+/// Read the `CoreMetadata` from a wheel's *.dist-info/METADATA file.
+pub(crate) fn wheel_metadata(bytes: &[u8]) -> HuakResult<CoreMetadata> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))
+        .map_err(|e| Error::InvalidMetadata(e.to_string()))?;
+    let name = archive
+        .file_names()
+        .find(|it| {
+            let mut parts = it.split('/');
+            parts.next().is_some_and(|it| it.ends_with(".dist-info"))
+                && parts.next() == Some("METADATA")
+                && parts.next().is_none()
+        })
+        .map(ToString::to_string)
+        .ok_or_else(|| Error::InvalidMetadata("a wheel is missing METADATA".to_string()))?;
+
+    let mut contents = String::new();
+    archive
+        .by_name(&name)
+        .map_err(|e| Error::InvalidMetadata(e.to_string()))?
+        .read_to_string(&mut contents)?;
+
+    CoreMetadata::from_str(&contents)
+}
+
+/// Read the `CoreMetadata` from a source distribution's PKG-INFO file.
+pub(crate) fn sdist_metadata(filename: &str, bytes: &[u8]) -> HuakResult<CoreMetadata> {
+    let is_pkg_info = |path: &str| {
+        let parts = path.trim_end_matches('/').split('/').collect::<Vec<_>>();
+        parts.len() == 2 && parts[1] == "PKG-INFO"
+    };
+    let mut contents = None;
+
+    if has_extension(filename, "zip") {
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes))
+            .map_err(|e| Error::InvalidMetadata(e.to_string()))?;
+        let name = archive
+            .file_names()
+            .find(|it| is_pkg_info(it))
+            .map(ToString::to_string);
+        if let Some(name) = name {
+            let mut s = String::new();
+            archive
+                .by_name(&name)
+                .map_err(|e| Error::InvalidMetadata(e.to_string()))?
+                .read_to_string(&mut s)?;
+            contents = Some(s);
+        }
+    } else {
+        let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(bytes));
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            if is_pkg_info(&entry.path()?.to_string_lossy()) {
+                let mut s = String::new();
+                entry.read_to_string(&mut s)?;
+                contents = Some(s);
+                break;
+            }
+        }
+    }
+
+    let Some(contents) = contents else {
+        return Err(Error::BuildRequired(filename.to_string()));
+    };
+
+    static_sdist_metadata(filename, CoreMetadata::from_str(&contents)?)
+}
+
+/// Check that a source distribution's `CoreMetadata` can be used without building it.
+///
+/// Only metadata version 2.2 and later marks the fields a build can change as `Dynamic`
+/// (see <https://peps.python.org/pep-0643/>). Returns `Error::BuildRequired` for older
+/// metadata and for dynamic versions, dependencies or Python requirements.
+pub(crate) fn static_sdist_metadata(
+    filename: &str,
+    metadata: CoreMetadata,
+) -> HuakResult<CoreMetadata> {
+    let is_static = metadata
+        .get("Metadata-Version")
+        .and_then(|it| Version::from_str(it).ok())
+        .is_some_and(|it| it >= Version::from_release(vec![2, 2]))
+        && !metadata.get_all("Dynamic").any(|it| {
+            ["Version", "Requires-Dist", "Requires-Python"]
+                .iter()
+                .any(|field| it.eq_ignore_ascii_case(field))
+        });
+
+    if is_static {
+        Ok(metadata)
+    } else {
+        Err(Error::BuildRequired(filename.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wheel_filename() {
+        let wheel = WheelFilename::from_str("Typing_Extensions-4.8.0-1-py3-none-any.whl").unwrap();
+
+        assert_eq!(wheel.name, "typing-extensions");
+        assert_eq!(wheel.version, Version::from_str("4.8.0").unwrap());
+        assert_eq!(wheel.python_tags, vec!["py3"]);
+        assert_eq!(wheel.platform_tags, vec!["any"]);
+        assert!(WheelFilename::from_str("click-8.1.7.tar.gz").is_err());
+    }
+
+    #[test]
+    fn test_static_sdist_metadata() {
+        let metadata = |contents: &str| CoreMetadata::from_str(contents).unwrap();
+
+        assert!(static_sdist_metadata(
+            "a-1.0.0.tar.gz",
+            metadata("Metadata-Version: 2.2\nName: a\nVersion: 1.0.0\nDynamic: Classifier\n")
+        )
+        .is_ok());
+        assert!(matches!(
+            static_sdist_metadata(
+                "a-1.0.0.tar.gz",
+                metadata("Metadata-Version: 2.1\nName: a\nVersion: 1.0.0\n")
+            ),
+            Err(Error::BuildRequired(_))
+        ));
+        assert!(matches!(
+            static_sdist_metadata(
+                "a-1.0.0.tar.gz",
+                metadata(
+                    "Metadata-Version: 2.3\nName: a\nVersion: 1.0.0\nDynamic: Requires-Dist\n"
+                )
+            ),
+            Err(Error::BuildRequired(_))
+        ));
+    }
+
+    #[test]
+    fn test_sdist_version() {
+        assert_eq!(
+            sdist_version("zope.interface-6.1.tar.gz", "zope-interface"),
+            Some(Version::from_str("6.1").unwrap())
+        );
+        assert_eq!(
+            sdist_version("python-dateutil-2.8.2.zip", "python-dateutil"),
+            Some(Version::from_str("2.8.2").unwrap())
+        );
+        assert_eq!(sdist_version("click-8.1.7.whl", "click"), None);
+    }
+
+    #[test]
+    fn test_tags() {
+        let tags = Tags::new("cp", 3, 11, platform_tags("linux", "x86_64"));
+        let compatible = |s: &str| tags.is_compatible(&WheelFilename::from_str(s).unwrap());
+
+        assert!(compatible("a-1.0-py3-none-any.whl"));
+        assert!(compatible("a-1.0-py2.py3-none-any.whl"));
+        assert!(compatible("a-1.0-cp311-cp311-manylinux_2_17_x86_64.whl"));
+        assert!(compatible("a-1.0-cp37-abi3-manylinux2014_x86_64.whl"));
+        assert!(!compatible("a-1.0-cp312-cp312-manylinux_2_17_x86_64.whl"));
+        assert!(!compatible("a-1.0-cp311-cp311-win_amd64.whl"));
+        assert!(!compatible("a-1.0-py2-none-any.whl"));
+        assert!(
+            tags.priority(
+                &WheelFilename::from_str("a-1.0-cp311-cp311-manylinux_2_17_x86_64.whl").unwrap()
+            ) > tags.priority(&WheelFilename::from_str("a-1.0-py3-none-any.whl").unwrap())
+        );
+    }
+}
@@ -0,0 +1,730 @@
+use super::distribution::{
+    has_extension, sdist_metadata, sdist_version, static_sdist_metadata, wheel_metadata,
+    WheelFilename,
+};
+use crate::{CoreMetadata, Credentials, Error, HuakResult};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeMap, str::FromStr};
+use url::Url;
+
+/// The Accept header used to request PEP 691 JSON pages with a fallback to HTML.
+const SIMPLE_API_ACCEPT: &str =
+    "application/vnd.pypi.simple.v1+json, application/vnd.pypi.simple.v1+html;q=0.2, text/html;q=0.01";
+
+/// The content type of PEP 691 JSON pages.
+const SIMPLE_API_JSON: &str = "application/vnd.pypi.simple.v1+json";
+
+lazy_static! {
+    static ref ANCHOR_REGEX: Regex =
+        Regex::new(r"(?is)<a\s+([^>]*)>(.*?)</a\s*>").expect("anchor regex");
+    static ref ATTRIBUTE_REGEX: Regex =
+        Regex::new(r#"([A-Za-z0-9_:-]+)(?:\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+)))?"#)
+            .expect("attribute regex");
+    static ref ANCHOR_BASE_REGEX: Regex =
+        Regex::new(r#"(?is)<base\s+[^>]*href\s*=\s*["']([^"']*)["']"#).expect("base regex");
+}
+
+/// A Python package index implementing the simple repository API.
+///
+/// Both HTML (PEP 503) and JSON (PEP 691) pages are supported. Indexes can be served
+/// over http(s) or from a local directory with a file:// URL. Local indexes use
+/// {index}/{project}/index.json or {index}/{project}/index.html for each project page.
+///
+/// `find-links` locations are used as flat indexes. They're either a local directory of
+/// distributions or the URL of an HTML page linking to them.
+///
+/// ```no_run
+/// use huak_package_manager::PackageIndex;
+///
+/// let index = PackageIndex::new("https://pypi.org/simple").unwrap();
+/// let page = index.project("click").unwrap();
+/// ```
+pub struct PackageIndex {
+    url: Url,
+    client: reqwest::blocking::Client,
+    /// Whether the index is a flat `find-links` location.
+    flat: bool,
+    /// The `Credentials` used for requests to the index's host.
+    credentials: Option<Credentials>,
+}
+
+impl PackageIndex {
+    /// Initialize a `PackageIndex` from its URL.
+    pub fn new(url: &str) -> HuakResult<PackageIndex> {
+        let mut url = url_from_str(url)?;
+
+        // Project pages are joined relative to the index.
+        if !url.path().ends_with('/') {
+            url.set_path(&format!("{}/", url.path()));
+        }
+
+        Ok(PackageIndex {
+            url,
+            client: http_client()?,
+            flat: false,
+            credentials: None,
+        })
+    }
+
+    /// Initialize a flat `PackageIndex` from a `find-links` location.
+    pub fn find_links(location: &str) -> HuakResult<PackageIndex> {
+        Ok(PackageIndex {
+            url: url_from_str(location)?,
+            client: http_client()?,
+            flat: true,
+            credentials: None,
+        })
+    }
+
+    /// Use `Credentials` for requests to the `PackageIndex`. They're only sent to URLs on
+    /// the same host as the index.
+    #[must_use]
+    pub fn with_credentials(mut self, credentials: Option<Credentials>) -> PackageIndex {
+        self.credentials = credentials;
+        self
+    }
+
+    /// Get the URL of the `PackageIndex`.
+    #[must_use]
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Get the `ProjectPage` for a project. Returns `None` if the index doesn't have
+    /// the project.
+    pub fn project(&self, name: &str) -> HuakResult<Option<ProjectPage>> {
+        if self.flat {
+            return self.flat_project(name);
+        }
+
+        let url = self
+            .url
+            .join(&format!("{}/", crate::lock::normalized_name(name)))?;
+
+        let (bytes, content_type, url) = if url.scheme() == "file" {
+            let Ok(dir) = url.to_file_path() else {
+                return Err(Error::PackageIndexError(format!("invalid file url: {url}")));
+            };
+            let json = dir.join("index.json");
+            let html = dir.join("index.html");
+
+            if json.exists() {
+                (std::fs::read(&json)?, SIMPLE_API_JSON.to_string(), url)
+            } else if html.exists() {
+                (std::fs::read(&html)?, "text/html".to_string(), url)
+            } else {
+                return Ok(None);
+            }
+        } else {
+            let response = request(&self.client, &url, self.credentials_for(&url))
+                .header(reqwest::header::ACCEPT, SIMPLE_API_ACCEPT)
+                .send()?;
+
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                return Ok(None);
+            }
+
+            let response = response.error_for_status()?;
+            let url = response.url().clone();
+            let content_type = response
+                .headers()
+                .get(reqwest::header::CONTENT_TYPE)
+                .and_then(|it| it.to_str().ok())
+                .unwrap_or_default()
+                .to_string();
+
+            (response.bytes()?.to_vec(), content_type, url)
+        };
+
+        let page = if content_type.starts_with(SIMPLE_API_JSON) {
+            ProjectPage::from_json(&bytes, &url)?
+        } else {
+            ProjectPage::from_html(&String::from_utf8_lossy(&bytes), &url)?
+        };
+
+        Ok(Some(page))
+    }
+
+    /// Get the files for a project from a flat `find-links` location.
+    fn flat_project(&self, name: &str) -> HuakResult<Option<ProjectPage>> {
+        let page = if self.url.scheme() == "file" {
+            let Ok(dir) = self.url.to_file_path() else {
+                return Err(Error::PackageIndexError(format!(
+                    "invalid file url: {}",
+                    self.url
+                )));
+            };
+            if !dir.is_dir() {
+                return Ok(None);
+            }
+
+            let mut files = Vec::new();
+            for entry in std::fs::read_dir(dir)? {
+                let path = entry?.path();
+                if !path.is_file() {
+                    continue;
+                }
+                let Ok(url) = Url::from_file_path(&path) else {
+                    continue;
+                };
+                files.push(DistributionFile {
+                    filename: path
+                        .file_name()
+                        .map(|it| it.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                    url,
+                    hashes: BTreeMap::new(),
+                    requires_python: None,
+                    yanked: false,
+                    core_metadata: None,
+                });
+            }
+
+            ProjectPage { files }
+        } else {
+            let html = String::from_utf8_lossy(&self.get(&self.url)?).to_string();
+            ProjectPage::from_html(&html, &self.url)?
+        };
+
+        let name = crate::lock::normalized_name(name);
+        let files = page
+            .files
+            .into_iter()
+            .filter(|it| match WheelFilename::from_str(&it.filename) {
+                Ok(wheel) => wheel.name == name,
+                Err(_) => sdist_version(&it.filename, &name).is_some(),
+            })
+            .collect::<Vec<_>>();
+
+        Ok((!files.is_empty()).then_some(ProjectPage { files }))
+    }
+
+    /// Download a `DistributionFile`. The file is verified against its sha256 hash if
+    /// the index provides one.
+    pub fn download(&self, file: &DistributionFile) -> HuakResult<Vec<u8>> {
+        download(&self.client, file, self.credentials_for(&file.url))
+    }
+
+    /// Get the `CoreMetadata` for a `DistributionFile`.
+    ///
+    /// PEP 658 metadata files are used when the index provides them and verified against
+    /// the sha256 hash the index advertises for them. Otherwise the distribution is
+    /// downloaded and its metadata is read from the archive. Returns `Error::BuildRequired`
+    /// for source distributions without static metadata.
+    pub fn metadata(&self, file: &DistributionFile) -> HuakResult<CoreMetadata> {
+        if let Some(hashes) = file.core_metadata.as_ref() {
+            let filename = format!("{}.metadata", file.filename);
+            let url = Url::parse(&format!("{}.metadata", file.url))?;
+            let bytes = self.get(&url)?;
+            verify_sha256(&bytes, hashes, &filename)?;
+
+            let metadata = CoreMetadata::from_str(&String::from_utf8_lossy(&bytes))?;
+            if file.is_wheel() {
+                return Ok(metadata);
+            }
+            return static_sdist_metadata(&file.filename, metadata);
+        }
+
+        let bytes = self.download(file)?;
+
+        if file.is_wheel() {
+            wheel_metadata(&bytes)
+        } else {
+            sdist_metadata(&file.filename, &bytes)
+        }
+    }
+
+    fn get(&self, url: &Url) -> HuakResult<Vec<u8>> {
+        get(&self.client, url, self.credentials_for(url))
+    }
+
+    fn credentials_for(&self, url: &Url) -> Option<&Credentials> {
+        self.credentials.as_ref().filter(|_| {
+            url.host_str() == self.url.host_str()
+                && url.port_or_known_default() == self.url.port_or_known_default()
+        })
+    }
+}
+
+/// A project's page on a `PackageIndex`.
+#[derive(Clone, Debug, Default)]
+pub struct ProjectPage {
+    /// The `DistributionFile`s available for the project.
+    pub files: Vec<DistributionFile>,
+}
+
+impl ProjectPage {
+    /// Parse a PEP 691 JSON project page.
+    fn from_json(bytes: &[u8], base: &Url) -> HuakResult<ProjectPage> {
+        let page: RawJsonPage = serde_json::from_slice(bytes)?;
+        let mut files = Vec::with_capacity(page.files.len());
+
+        for file in page.files {
+            let core_metadata = file.core_metadata.or(file.dist_info_metadata);
+            files.push(DistributionFile {
+                url: base.join(&file.url)?,
+                filename: file.filename,
+                hashes: file.hashes,
+                requires_python: file.requires_python.filter(|it| !it.trim().is_empty()),
+                yanked: file.yanked.is_some_and(|it| it.is_true()),
+                core_metadata: core_metadata.and_then(BoolOrValue::into_hashes),
+            });
+        }
+
+        Ok(ProjectPage { files })
+    }
+
+    /// Parse a PEP 503 HTML project page.
+    fn from_html(html: &str, base: &Url) -> HuakResult<ProjectPage> {
+        // A <base> element changes the URL hrefs are relative to.
+        let base = match ANCHOR_BASE_REGEX.captures(html) {
+            Some(caps) => base.join(&unescape_html(&caps[1]))?,
+            None => base.clone(),
+        };
+        let mut files = Vec::new();
+
+        for caps in ANCHOR_REGEX.captures_iter(html) {
+            let attributes = ATTRIBUTE_REGEX
+                .captures_iter(&caps[1])
+                .map(|it| {
+                    let value = it
+                        .get(2)
+                        .or(it.get(3))
+                        .or(it.get(4))
+                        .map(|v| unescape_html(v.as_str()));
+                    (it[1].to_lowercase(), value)
+                })
+                .collect::<BTreeMap<_, _>>();
+            let Some(Some(href)) = attributes.get("href") else {
+                continue;
+            };
+
+            let mut url = base.join(href)?;
+            let mut hashes = BTreeMap::new();
+            if let Some((algorithm, digest)) = url.fragment().and_then(|it| it.split_once('=')) {
+                hashes.insert(algorithm.to_string(), digest.to_string());
+            }
+            url.set_fragment(None);
+
+            let filename = match url.path_segments().and_then(Iterator::last) {
+                Some(it) if !it.is_empty() => percent_decode(it),
+                _ => unescape_html(caps[2].trim()),
+            };
+            let core_metadata = match attributes
+                .get("data-core-metadata")
+                .or(attributes.get("data-dist-info-metadata"))
+            {
+                Some(Some(it)) if it == "false" => None,
+                Some(it) => Some(
+                    it.as_deref()
+                        .and_then(|it| it.split_once('='))
+                        .map(|(algorithm, digest)| {
+                            BTreeMap::from([(algorithm.to_string(), digest.to_string())])
+                        })
+                        .unwrap_or_default(),
+                ),
+                None => None,
+            };
+
+            files.push(DistributionFile {
+                filename,
+                url,
+                hashes,
+                requires_python: attributes
+                    .get("data-requires-python")
+                    .cloned()
+                    .flatten()
+                    .filter(|it| !it.trim().is_empty()),
+                yanked: attributes.contains_key("data-yanked"),
+                core_metadata,
+            });
+        }
+
+        Ok(ProjectPage { files })
+    }
+}
+
+/// A file available for a project on a `PackageIndex`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DistributionFile {
+    /// The name of the file.
+    pub filename: String,
+    /// The absolute URL of the file.
+    pub url: Url,
+    /// The hashes of the file by algorithm (for example "sha256").
+    pub hashes: BTreeMap<String, String>,
+    /// The Requires-Python specifiers of the distribution.
+    pub requires_python: Option<String>,
+    /// Whether the file has been yanked.
+    pub yanked: bool,
+    /// The hashes of the file's metadata by algorithm if the index provides it (PEP 658).
+    /// The hashes are empty if the index doesn't advertise any.
+    pub core_metadata: Option<BTreeMap<String, String>>,
+}
+
+impl DistributionFile {
+    /// Initialize a `DistributionFile` from a direct URL.
+    pub fn from_url(url: &str) -> HuakResult<DistributionFile> {
+        let mut url = url_from_str(url)?;
+        let mut hashes = BTreeMap::new();
+        if let Some((algorithm, digest)) = url.fragment().and_then(|it| it.split_once('=')) {
+            hashes.insert(algorithm.to_string(), digest.to_string());
+        }
+        url.set_fragment(None);
+
+        let filename = url
+            .path_segments()
+            .and_then(Iterator::last)
+            .map(percent_decode)
+            .unwrap_or_default();
+
+        Ok(DistributionFile {
+            filename,
+            url,
+            hashes,
+            requires_python: None,
+            yanked: false,
+            core_metadata: None,
+        })
+    }
+
+    /// Download the file from its URL with optional `Credentials`. The file is verified
+    /// against its sha256 hash if it has one.
+    pub fn download(&self, credentials: Option<&Credentials>) -> HuakResult<Vec<u8>> {
+        download(&http_client()?, self, credentials)
+    }
+
+    /// Check if the file is a wheel.
+    #[must_use]
+    pub fn is_wheel(&self) -> bool {
+        has_extension(&self.filename, "whl")
+    }
+
+    /// Check if the file is a source distribution.
+    #[must_use]
+    pub fn is_sdist(&self) -> bool {
+        self.filename.ends_with(".tar.gz") || has_extension(&self.filename, "zip")
+    }
+
+    /// Get the file's hashes formatted as "{algorithm}:{digest}".
+    #[must_use]
+    pub fn hash_strings(&self) -> Vec<String> {
+        self.hashes
+            .iter()
+            .map(|(algorithm, digest)| format!("{algorithm}:{digest}"))
+            .collect()
+    }
+}
+
+#[derive(Deserialize)]
+struct RawJsonPage {
+    #[serde(default)]
+    files: Vec<RawJsonFile>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RawJsonFile {
+    filename: String,
+    url: String,
+    #[serde(default)]
+    hashes: BTreeMap<String, String>,
+    requires_python: Option<String>,
+    yanked: Option<BoolOrValue>,
+    core_metadata: Option<BoolOrValue>,
+    /// The PEP 691 key PEP 714 renamed to `core-metadata`.
+    dist_info_metadata: Option<BoolOrValue>,
+}
+
+/// Fields like "yanked" are either a bool or a value implying true (a reason or hashes).
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BoolOrValue {
+    Bool(bool),
+    Value(serde_json::Value),
+}
+
+impl BoolOrValue {
+    fn is_true(&self) -> bool {
+        match self {
+            BoolOrValue::Bool(it) => *it,
+            BoolOrValue::Value(it) => !it.is_null(),
+        }
+    }
+
+    /// Get the hashes of a value like "core-metadata". `None` if the value is false.
+    fn into_hashes(self) -> Option<BTreeMap<String, String>> {
+        if !self.is_true() {
+            return None;
+        }
+
+        match self {
+            BoolOrValue::Value(serde_json::Value::Object(it)) => Some(
+                it.into_iter()
+                    .filter_map(|(k, v)| Some((k, v.as_str()?.to_string())))
+                    .collect(),
+            ),
+            _ => Some(BTreeMap::new()),
+        }
+    }
+}
+
+/// Build the HTTP client used for index requests.
+fn http_client() -> HuakResult<reqwest::blocking::Client> {
+    Ok(reqwest::blocking::Client::builder()
+        .user_agent(concat!("huak/", env!("CARGO_PKG_VERSION")))
+        .build()?)
+}
+
+/// Build a GET request. `Credentials` are sent with basic auth unless the URL has its own.
+fn request(
+    client: &reqwest::blocking::Client,
+    url: &Url,
+    credentials: Option<&Credentials>,
+) -> reqwest::blocking::RequestBuilder {
+    let request = client.get(url.clone());
+
+    match credentials {
+        Some(it) if url.username().is_empty() => {
+            request.basic_auth(&it.username, Some(&it.password))
+        }
+        _ => request,
+    }
+}
+
+fn get(
+    client: &reqwest::blocking::Client,
+    url: &Url,
+    credentials: Option<&Credentials>,
+) -> HuakResult<Vec<u8>> {
+    if url.scheme() == "file" {
+        let path = url
+            .to_file_path()
+            .map_err(|()| Error::PackageIndexError(format!("invalid file url: {url}")))?;
+        return Ok(std::fs::read(path)?);
+    }
+
+    let response = request(client, url, credentials)
+        .send()?
+        .error_for_status()?;
+
+    Ok(response.bytes()?.to_vec())
+}
+
+fn download(
+    client: &reqwest::blocking::Client,
+    file: &DistributionFile,
+    credentials: Option<&Credentials>,
+) -> HuakResult<Vec<u8>> {
+    let bytes = get(client, &file.url, credentials)?;
+    verify_sha256(&bytes, &file.hashes, &file.filename)?;
+
+    Ok(bytes)
+}
+
+/// Verify bytes against the sha256 hash in `hashes` if there is one.
+fn verify_sha256(
+    bytes: &[u8],
+    hashes: &BTreeMap<String, String>,
+    filename: &str,
+) -> HuakResult<()> {
+    if let Some(expected) = hashes.get("sha256") {
+        let actual = hex::encode(Sha256::digest(bytes));
+        if !actual.eq_ignore_ascii_case(expected) {
+            return Err(Error::InvalidChecksum(filename.to_string()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse a `Url` from a URL or a local path.
+fn url_from_str(s: &str) -> HuakResult<Url> {
+    match Url::parse(s) {
+        // Windows paths like C:\ parse as a URL with a single letter scheme.
+        Ok(url) if url.scheme().len() > 1 => Ok(url),
+        _ => {
+            let path = std::path::Path::new(s);
+            let path = if path.is_absolute() {
+                path.to_path_buf()
+            } else {
+                std::env::current_dir()?.join(path)
+            };
+            Url::from_directory_path(&path)
+                .map_err(|()| Error::PackageIndexError(format!("invalid index: {s}")))
+        }
+    }
+}
+
+fn unescape_html(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&amp;", "&")
+}
+
+fn percent_decode(s: &str) -> String {
+    percent_encoding::percent_decode_str(s)
+        .decode_utf8_lossy()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_page_from_html() {
+        let base = Url::parse("https://example.com/simple/click/").unwrap();
+        let page = ProjectPage::from_html(
+            r#"<!DOCTYPE html>
+<html>
+  <body>
+    <a href="../../packages/click-8.1.6-py3-none-any.whl#sha256=abc" data-requires-python="&gt;=3.7" data-yanked>click-8.1.6-py3-none-any.whl</a>
+    <a href='https://files.example.com/click-8.1.7-py3-none-any.whl#sha256=def' data-dist-info-metadata="sha256=123">click-8.1.7-py3-none-any.whl</a><br/>
+    <a href="click-8.1.7.tar.gz">click-8.1.7.tar.gz</a>
+  </body>
+</html>"#,
+            &base,
+        )
+        .unwrap();
+
+        assert_eq!(page.files.len(), 3);
+        assert_eq!(
+            page.files[0].url.as_str(),
+            "https://example.com/packages/click-8.1.6-py3-none-any.whl"
+        );
+        assert_eq!(page.files[0].filename, "click-8.1.6-py3-none-any.whl");
+        assert_eq!(page.files[0].hashes.get("sha256").unwrap(), "abc");
+        assert_eq!(page.files[0].requires_python.as_deref(), Some(">=3.7"));
+        assert!(page.files[0].yanked);
+        assert!(page.files[0].core_metadata.is_none());
+        assert_eq!(
+            page.files[1].core_metadata,
+            Some(BTreeMap::from([("sha256".to_string(), "123".to_string())]))
+        );
+        assert!(!page.files[1].yanked);
+        assert_eq!(
+            page.files[2].url.as_str(),
+            "https://example.com/simple/click/click-8.1.7.tar.gz"
+        );
+        assert!(page.files[2].is_sdist());
+    }
+
+    #[test]
+    fn test_project_page_from_json() {
+        let base = Url::parse("https://example.com/simple/click/").unwrap();
+        let page = ProjectPage::from_json(
+            br#"{
+  "meta": {"api-version": "1.1"},
+  "name": "click",
+  "files": [
+    {
+      "filename": "click-8.1.6-py3-none-any.whl",
+      "url": "https://files.example.com/click-8.1.6-py3-none-any.whl",
+      "hashes": {"sha256": "abc"},
+      "requires-python": ">=3.7",
+      "yanked": "broken",
+      "core-metadata": {"sha256": "123"}
+    },
+    {
+      "filename": "click-8.1.7.tar.gz",
+      "url": "../../packages/click-8.1.7.tar.gz",
+      "hashes": {},
+      "yanked": false
+    }
+  ]
+}"#,
+            &base,
+        )
+        .unwrap();
+
+        assert_eq!(page.files.len(), 2);
+        assert!(page.files[0].yanked);
+        assert_eq!(
+            page.files[0].core_metadata,
+            Some(BTreeMap::from([("sha256".to_string(), "123".to_string())]))
+        );
+        assert_eq!(page.files[0].requires_python.as_deref(), Some(">=3.7"));
+        assert_eq!(page.files[0].hash_strings(), vec!["sha256:abc"]);
+        assert!(!page.files[1].yanked);
+        assert!(page.files[1].core_metadata.is_none());
+        assert_eq!(
+            page.files[1].url.as_str(),
+            "https://example.com/packages/click-8.1.7.tar.gz"
+        );
+    }
+
+    #[test]
+    fn test_metadata_verifies_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let metadata = "Metadata-Version: 2.1\nName: click\nVersion: 8.1.7\n";
+        std::fs::write(
+            dir.path().join("click-8.1.7-py3-none-any.whl.metadata"),
+            metadata,
+        )
+        .unwrap();
+        let index = PackageIndex::find_links(dir.path().to_str().unwrap()).unwrap();
+        let file = |filename: &str, digest: &str| DistributionFile {
+            filename: filename.to_string(),
+            url: Url::from_file_path(dir.path().join(filename)).unwrap(),
+            hashes: BTreeMap::new(),
+            requires_python: None,
+            yanked: false,
+            core_metadata: Some(BTreeMap::from([("sha256".to_string(), digest.to_string())])),
+        };
+        let digest = hex::encode(Sha256::digest(metadata.as_bytes()));
+
+        let it = index
+            .metadata(&file("click-8.1.7-py3-none-any.whl", &digest))
+            .unwrap();
+        assert_eq!(it.name(), Some("click"));
+        assert!(matches!(
+            index.metadata(&file("click-8.1.7-py3-none-any.whl", "abc")),
+            Err(Error::InvalidChecksum(_))
+        ));
+        // A missing metadata file isn't replaced by downloading the distribution.
+        assert!(matches!(
+            index.metadata(&file("click-8.1.6-py3-none-any.whl", &digest)),
+            Err(Error::IOError(_))
+        ));
+    }
+
+    #[test]
+    fn test_project_page_from_json_dist_info_metadata() {
+        let base = Url::parse("https://example.com/simple/click/").unwrap();
+        let page = ProjectPage::from_json(
+            br#"{
+  "meta": {"api-version": "1.0"},
+  "name": "click",
+  "files": [
+    {
+      "filename": "click-8.1.7-py3-none-any.whl",
+      "url": "https://files.example.com/click-8.1.7-py3-none-any.whl",
+      "hashes": {"sha256": "def"},
+      "dist-info-metadata": {"sha256": "123"}
+    },
+    {
+      "filename": "click-8.1.6-py3-none-any.whl",
+      "url": "https://files.example.com/click-8.1.6-py3-none-any.whl",
+      "hashes": {"sha256": "abc"},
+      "dist-info-metadata": false
+    }
+  ]
+}"#,
+            &base,
+        )
+        .unwrap();
+
+        assert_eq!(
+            page.files[0].core_metadata,
+            Some(BTreeMap::from([("sha256".to_string(), "123".to_string())]))
+        );
+        assert!(page.files[1].core_metadata.is_none());
+    }
+}
@@ -0,0 +1,1258 @@
+mod distribution;
+mod index;
+
+use crate::{
+    dependency::requirement_string, lock::normalized_name, CoreMetadata, CredentialProvider,
+    Dependency, Error, HuakResult,
+};
+use distribution::sdist_version;
+pub use distribution::{Tags, WheelFilename};
+pub use index::{DistributionFile, PackageIndex, ProjectPage};
+use indexmap::IndexMap;
+use pep440_rs::{Operator, Version, VersionSpecifiers};
+use pep508_rs::{MarkerEnvironment, Requirement, VersionOrUrl};
+use std::{
+    cell::RefCell,
+    collections::{BTreeSet, HashMap, VecDeque},
+    ops::Range,
+    rc::Rc,
+    str::FromStr,
+};
+
+/// The label used for requirements from the project in conflict messages.
+const PROJECT_LABEL: &str = "project";
+
+pub struct ResolverOptions {
+    /// The URLs of the package indexes to use. Earlier indexes are preferred.
+    pub index_urls: Vec<String>,
+    /// Local directories or URLs of HTML pages linking to distributions (`find-links`).
+    pub find_links: Vec<String>,
+    /// The URLs of the indexes packages are pinned to by normalized package name. Pinned
+    /// packages are only resolved from their index.
+    pub package_indexes: HashMap<String, String>,
+    /// The credentials for the indexes.
+    pub credentials: CredentialProvider,
+    /// The `MarkerEnvironment` to resolve for.
+    pub marker_environment: MarkerEnvironment,
+    /// Whether pre-releases can be selected when a requirement doesn't ask for one.
+    pub prereleases: bool,
+    /// Whether network access is disabled. Packages pinned to a remote index can't be
+    /// resolved offline.
+    pub offline: bool,
+}
+
+/// A `Resolver` for `Dependency` requirements using Python package indexes.
+///
+/// The `Resolver` selects the newest version of each package that's compatible with
+/// every requirement of it, backtracking when a selection leads to a conflict. Package
+/// metadata is read from PEP 658 metadata files or from the wheel and source distribution
+/// archives themselves.
+///
+/// ```no_run
+/// use huak_package_manager::{
+///     CredentialProvider, Dependency, PythonEnvironment, Resolver, ResolverOptions,
+/// };
+/// use std::{collections::HashMap, str::FromStr};
+///
+/// let python_env = PythonEnvironment::new(".venv").unwrap();
+/// let options = ResolverOptions {
+///     index_urls: vec!["https://pypi.org/simple".to_string()],
+///     find_links: Vec::new(),
+///     package_indexes: HashMap::new(),
+///     credentials: CredentialProvider::default(),
+///     marker_environment: python_env.marker_environment().unwrap(),
+///     prereleases: false,
+///     offline: false,
+/// };
+/// let resolver = Resolver::new(options).unwrap();
+/// let resolution = resolver.resolve(&[Dependency::from_str("click").unwrap()]).unwrap();
+/// ```
+pub struct Resolver {
+    indexes: Vec<PackageIndex>,
+    /// The number of indexes unpinned packages are resolved from. Indexes only used by
+    /// pinned packages follow them.
+    shared: usize,
+    /// The position of the index each pinned package is resolved from.
+    pins: HashMap<String, usize>,
+    /// The URLs of the remote indexes packages are pinned to in offline mode.
+    offline_pins: HashMap<String, String>,
+    marker_environment: MarkerEnvironment,
+    tags: Tags,
+    prereleases: bool,
+    /// The versions to try first for each project by name.
+    preferences: HashMap<String, Version>,
+    /// The `Candidate`s for each project by name.
+    candidates: RefCell<HashMap<String, Rc<Vec<Candidate>>>>,
+    /// The `CoreMetadata` for each distribution by URL.
+    metadata: RefCell<HashMap<String, Rc<CoreMetadata>>>,
+}
+
+impl Resolver {
+    pub fn new(options: ResolverOptions) -> HuakResult<Resolver> {
+        if options.index_urls.is_empty() && options.find_links.is_empty() {
+            return Err(Error::PackageIndexError(
+                "at least one package index is required".to_string(),
+            ));
+        }
+
+        let mut indexes = options
+            .index_urls
+            .iter()
+            .map(|it| PackageIndex::new(it))
+            .chain(
+                options
+                    .find_links
+                    .iter()
+                    .map(|it| PackageIndex::find_links(it)),
+            )
+            .map(|it| it.map(|index| with_credentials(index, &options.credentials)))
+            .collect::<HuakResult<Vec<_>>>()?;
+        let shared = indexes.len();
+
+        let mut positions = options
+            .index_urls
+            .iter()
+            .enumerate()
+            .map(|(i, it)| (it.clone(), i))
+            .collect::<HashMap<_, _>>();
+        let mut pins = HashMap::new();
+        let mut offline_pins = HashMap::new();
+        for (name, url) in options.package_indexes {
+            if options.offline && !url.starts_with("file:") {
+                offline_pins.insert(normalized_name(&name), url);
+                continue;
+            }
+
+            if let Some(&i) = positions.get(&url) {
+                pins.insert(normalized_name(&name), i);
+                continue;
+            }
+
+            indexes.push(with_credentials(
+                PackageIndex::new(&url)?,
+                &options.credentials,
+            ));
+            positions.insert(url, indexes.len() - 1);
+            pins.insert(normalized_name(&name), indexes.len() - 1);
+        }
+
+        Ok(Resolver {
+            indexes,
+            shared,
+            pins,
+            offline_pins,
+            tags: Tags::from_marker_environment(&options.marker_environment),
+            marker_environment: options.marker_environment,
+            prereleases: options.prereleases,
+            preferences: HashMap::new(),
+            candidates: RefCell::new(HashMap::new()),
+            metadata: RefCell::new(HashMap::new()),
+        })
+    }
+
+    /// Prefer versions of projects (like the installed versions) over newer ones. A preferred
+    /// version is still only selected if it's compatible with every requirement.
+    #[must_use]
+    pub fn with_preferences(mut self, preferences: HashMap<String, Version>) -> Resolver {
+        self.preferences = preferences
+            .into_iter()
+            .map(|(name, version)| (normalized_name(&name), version))
+            .collect();
+        self
+    }
+
+    /// Resolve the `Dependency`s and their dependencies to a `Resolution`.
+    ///
+    /// Returns `Error::DependencyConflict` if no set of versions satisfies every requirement
+    /// and `Error::PackageNotFound` if no index has a package. VCS references, local
+    /// project directories and source distributions without static metadata have to be
+    /// built to read their metadata, so they return `Error::BuildRequired`.
+    pub fn resolve(&self, dependencies: &[Dependency]) -> HuakResult<Resolution> {
+        let mut state = State::default();
+        for dep in dependencies {
+            state.pending.push_back(Pending {
+                parent: None,
+                extras: Vec::new(),
+                requirement: dep.requirement().clone(),
+            });
+        }
+
+        let state = self.solve(state)?;
+
+        Ok(state.into_resolution(&self.indexes))
+    }
+
+    /// Get the versions of a project that can be installed to the `MarkerEnvironment`
+    /// sorted from newest to oldest. Yanked versions aren't included.
+    ///
+    /// Returns `Error::PackageNotFound` if no index has the project.
+    pub fn versions(&self, name: &str) -> HuakResult<Vec<Version>> {
+        let candidates = self.candidates(&normalized_name(name))?;
+
+        if candidates.is_empty() {
+            return Err(Error::PackageNotFound(name.to_string()));
+        }
+
+        Ok(candidates
+            .iter()
+            .filter(|it| !it.yanked)
+            .map(|it| it.version.clone())
+            .collect())
+    }
+
+    /// Get every file released for a version of a project (wheels for any platform and
+    /// source distributions) from the index it's resolved from. Returns an empty list if no
+    /// index has the project.
+    pub fn files(&self, name: &str, version: &Version) -> HuakResult<Vec<DistributionFile>> {
+        let name = normalized_name(name);
+
+        for i in self.index_positions(&name)? {
+            let Some(page) = self.indexes[i].project(&name)? else {
+                continue;
+            };
+
+            return Ok(page
+                .files
+                .into_iter()
+                .filter(|file| match WheelFilename::from_str(&file.filename) {
+                    Ok(wheel) => wheel.name == name && &wheel.version == version,
+                    Err(_) => sdist_version(&file.filename, &name).as_ref() == Some(version),
+                })
+                .collect());
+        }
+
+        Ok(Vec::new())
+    }
+
+    /// Solve the pending requirements of a `State`. Each candidate is tried in order and
+    /// a conflict moves on to the next one.
+    fn solve(&self, mut state: State) -> HuakResult<State> {
+        while let Some(pending) = state.pending.pop_front() {
+            let extras = pending
+                .extras
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<_>>();
+            if let Some(marker) = pending.requirement.marker.as_ref() {
+                if !marker.evaluate(&self.marker_environment, &extras) {
+                    continue;
+                }
+            }
+
+            let name = normalized_name(&pending.requirement.name);
+            let requested_extras = pending.requirement.extras.clone().unwrap_or_default();
+            state
+                .edges
+                .push((pending.parent.clone(), pending.requirement.clone()));
+            state
+                .constraints
+                .entry(name.clone())
+                .or_default()
+                .push((pending.parent.clone(), pending.requirement.clone()));
+
+            if let Some(selected) = state.selected.get(&name) {
+                if !satisfies(&pending.requirement, &selected.version, &selected.file) {
+                    return Err(state.conflict(&name));
+                }
+
+                // Newly requested extras can add dependencies to a selected package.
+                let mut extras = selected.extras.clone();
+                let len = extras.len();
+                extras.extend(requested_extras.into_iter().map(|it| normalized_name(&it)));
+                if extras.len() > len {
+                    let metadata = self.metadata(selected.index, &selected.file)?;
+                    state.enqueue(&name, &metadata, &extras);
+                    if let Some(it) = state.selected.get_mut(&name) {
+                        it.extras = extras;
+                    }
+                }
+                continue;
+            }
+
+            let extras = requested_extras
+                .iter()
+                .map(|it| normalized_name(it))
+                .collect::<BTreeSet<_>>();
+
+            // Direct references only have one candidate.
+            if let Some(VersionOrUrl::Url(url)) = pending.requirement.version_or_url.as_ref() {
+                let file = DistributionFile::from_url(url.as_str())?;
+                if !file.is_wheel() && !file.is_sdist() {
+                    return Err(Error::BuildRequired(url.to_string()));
+                }
+                let metadata = self.metadata(None, &file)?;
+                let version = metadata.version().ok_or_else(|| {
+                    Error::InvalidMetadata(format!("{} is missing a version", file.filename))
+                })?;
+                state.select(&name, version, file, None, extras, &metadata);
+                continue;
+            }
+
+            let candidates = self.candidates(&name)?;
+            if candidates.is_empty() {
+                return Err(Error::PackageNotFound(name));
+            }
+            let compatible = self.compatible_candidates(&state, &name, &candidates);
+
+            if compatible.is_empty() {
+                return Err(state.conflict(&name));
+            }
+
+            let mut conflict = None;
+            for candidate in compatible {
+                let metadata = self.metadata(candidate.index, &candidate.file)?;
+                if let Some(requires_python) = metadata.requires_python() {
+                    if !requires_python
+                        .contains(&self.marker_environment.python_full_version.version)
+                    {
+                        continue;
+                    }
+                }
+
+                let mut next = state.clone();
+                next.select(
+                    &name,
+                    candidate.version.clone(),
+                    candidate.file.clone(),
+                    candidate.index,
+                    extras.clone(),
+                    &metadata,
+                );
+
+                match self.solve(next) {
+                    Ok(it) => return Ok(it),
+                    Err(e @ Error::DependencyConflict(_)) => {
+                        conflict.get_or_insert(e);
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+
+            return Err(conflict.unwrap_or_else(|| state.conflict(&name)));
+        }
+
+        Ok(state)
+    }
+
+    /// Get the `Candidate`s compatible with every constraint on a package.
+    ///
+    /// Pre-releases are only used if they're requested, allowed, or nothing else matches.
+    /// Yanked versions are only used if they're pinned. A preferred version is tried first.
+    fn compatible_candidates<'a>(
+        &self,
+        state: &State,
+        name: &str,
+        candidates: &'a [Candidate],
+    ) -> Vec<&'a Candidate> {
+        let constraints = state.constraints.get(name).map_or(&[][..], Vec::as_slice);
+        let requests_prerelease = constraints.iter().any(|(_, req)| {
+            version_specifiers(req)
+                .is_some_and(|it| it.iter().any(pep440_rs::VersionSpecifier::any_prerelease))
+        });
+        let compatible = candidates
+            .iter()
+            .filter(|c| {
+                constraints
+                    .iter()
+                    .all(|(_, req)| satisfies(req, &c.version, &c.file))
+            })
+            .filter(|c| {
+                !c.yanked
+                    || constraints.iter().any(|(_, req)| {
+                        version_specifiers(req).is_some_and(|it| {
+                            it.iter().any(|s| {
+                                matches!(s.operator(), Operator::Equal | Operator::ExactEqual)
+                                    && s.version() == &c.version
+                            })
+                        })
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        let releases = compatible
+            .iter()
+            .filter(|c| !c.version.any_prerelease())
+            .copied()
+            .collect::<Vec<_>>();
+        let mut candidates = if self.prereleases || requests_prerelease || releases.is_empty() {
+            compatible
+        } else {
+            releases
+        };
+
+        if let Some(version) = self.preferences.get(name) {
+            if let Some(i) = candidates.iter().position(|c| &c.version == version) {
+                let preferred = candidates.remove(i);
+                candidates.insert(0, preferred);
+            }
+        }
+
+        candidates
+    }
+
+    /// Get the positions of the indexes a project is resolved from. Pinned projects are only
+    /// resolved from their index.
+    ///
+    /// Returns `Error::OfflineNetworkRequest` if the project is pinned to a remote index in
+    /// offline mode.
+    fn index_positions(&self, name: &str) -> HuakResult<Range<usize>> {
+        if let Some(url) = self.offline_pins.get(name) {
+            return Err(Error::OfflineNetworkRequest(format!(
+                "{name} is pinned to {url}"
+            )));
+        }
+
+        Ok(match self.pins.get(name) {
+            Some(&i) => i..i + 1,
+            None => 0..self.shared,
+        })
+    }
+
+    /// Get the `Candidate`s for a project from the first index that has it, or from the
+    /// index it's pinned to. Candidates are sorted from newest to oldest.
+    fn candidates(&self, name: &str) -> HuakResult<Rc<Vec<Candidate>>> {
+        if let Some(it) = self.candidates.borrow().get(name) {
+            return Ok(it.clone());
+        }
+
+        let python_version = &self.marker_environment.python_full_version.version;
+        let mut candidates: Vec<Candidate> = Vec::new();
+
+        for i in self.index_positions(name)? {
+            let index = &self.indexes[i];
+            let Some(page) = index.project(name)? else {
+                continue;
+            };
+
+            // The best file for each version.
+            let mut files: HashMap<Version, (Option<usize>, &DistributionFile)> = HashMap::new();
+            let mut yanked: HashMap<Version, bool> = HashMap::new();
+
+            for file in &page.files {
+                let (version, priority) = if file.is_wheel() {
+                    let Ok(wheel) = WheelFilename::from_str(&file.filename) else {
+                        continue;
+                    };
+                    let Some(priority) = self.tags.priority(&wheel) else {
+                        continue;
+                    };
+                    if wheel.name != name {
+                        continue;
+                    }
+                    (wheel.version, Some(priority))
+                } else if let Some(version) = sdist_version(&file.filename, name) {
+                    (version, None)
+                } else {
+                    continue;
+                };
+
+                if let Some(requires_python) = file
+                    .requires_python
+                    .as_ref()
+                    .and_then(|it| VersionSpecifiers::from_str(it).ok())
+                {
+                    if !requires_python.contains(python_version) {
+                        continue;
+                    }
+                }
+
+                *yanked.entry(version.clone()).or_insert(true) &= file.yanked;
+
+                match files.get(&version) {
+                    Some((p, _)) if *p >= priority => (),
+                    _ => {
+                        files.insert(version, (priority, file));
+                    }
+                }
+            }
+
+            candidates = files
+                .into_iter()
+                .map(|(version, (_, file))| Candidate {
+                    yanked: yanked.get(&version).copied().unwrap_or_default(),
+                    version,
+                    file: file.clone(),
+                    index: Some(i),
+                })
+                .collect();
+            candidates.sort_by(|a, b| b.version.cmp(&a.version));
+
+            break;
+        }
+
+        let candidates = Rc::new(candidates);
+        self.candidates
+            .borrow_mut()
+            .insert(name.to_string(), candidates.clone());
+
+        Ok(candidates)
+    }
+
+    /// Get the `CoreMetadata` for a `DistributionFile`.
+    fn metadata(
+        &self,
+        index: Option<usize>,
+        file: &DistributionFile,
+    ) -> HuakResult<Rc<CoreMetadata>> {
+        let key = file.url.to_string();
+        if let Some(it) = self.metadata.borrow().get(&key) {
+            return Ok(it.clone());
+        }
+
+        // Direct references are fetched with the first index's client.
+        let index = &self.indexes[index.unwrap_or_default()];
+        let metadata = Rc::new(index.metadata(file)?);
+        self.metadata.borrow_mut().insert(key, metadata.clone());
+
+        Ok(metadata)
+    }
+}
+
+/// A `Resolution` of packages selected by a `Resolver`.
+#[derive(Clone, Debug, Default)]
+pub struct Resolution {
+    /// The resolved packages sorted by name.
+    packages: IndexMap<String, ResolvedPackage>,
+    /// The names of the packages required directly.
+    roots: Vec<String>,
+}
+
+impl Resolution {
+    /// Get the `ResolvedPackage`s sorted by name.
+    pub fn packages(&self) -> impl Iterator<Item = &ResolvedPackage> {
+        self.packages.values()
+    }
+
+    /// Get a `ResolvedPackage` by name.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&ResolvedPackage> {
+        self.packages.get(&normalized_name(name))
+    }
+
+    /// Get the names of the packages required directly.
+    #[must_use]
+    pub fn roots(&self) -> &[String] {
+        &self.roots
+    }
+}
+
+/// A package selected by a `Resolver`.
+#[derive(Clone, Debug)]
+pub struct ResolvedPackage {
+    /// The normalized name of the package.
+    pub name: String,
+    /// The selected `Version` of the package.
+    pub version: Version,
+    /// The selected `DistributionFile` of the package.
+    pub distribution: DistributionFile,
+    /// The URL of the index the package was found on. `None` for direct references.
+    pub index: Option<String>,
+    /// The extras of the package that are required.
+    pub extras: Vec<String>,
+    /// The package's dependencies that apply to the `MarkerEnvironment`.
+    pub dependencies: Vec<ResolvedDependency>,
+}
+
+/// A dependency of a `ResolvedPackage`.
+#[derive(Clone, Debug)]
+pub struct ResolvedDependency {
+    /// The normalized name of the dependency.
+    pub name: String,
+    /// The `Requirement` the dependency was resolved for.
+    pub requirement: Requirement,
+}
+
+/// A version of a project available on an index.
+#[derive(Clone, Debug)]
+struct Candidate {
+    version: Version,
+    file: DistributionFile,
+    index: Option<usize>,
+    yanked: bool,
+}
+
+/// A requirement waiting to be resolved.
+#[derive(Clone, Debug)]
+struct Pending {
+    /// The name of the package requiring it. `None` for the project.
+    parent: Option<String>,
+    /// The parent's extras used to evaluate markers.
+    extras: Vec<String>,
+    requirement: Requirement,
+}
+
+/// A package selected while solving.
+#[derive(Clone, Debug)]
+struct Selected {
+    version: Version,
+    file: DistributionFile,
+    index: Option<usize>,
+    extras: BTreeSet<String>,
+}
+
+/// The state of a `Resolver` while solving. The state is cloned at each choice so that
+/// a conflict can backtrack to it.
+#[derive(Clone, Debug, Default)]
+struct State {
+    selected: IndexMap<String, Selected>,
+    constraints: HashMap<String, Vec<(Option<String>, Requirement)>>,
+    edges: Vec<(Option<String>, Requirement)>,
+    pending: VecDeque<Pending>,
+}
+
+impl State {
+    fn select(
+        &mut self,
+        name: &str,
+        version: Version,
+        file: DistributionFile,
+        index: Option<usize>,
+        extras: BTreeSet<String>,
+        metadata: &CoreMetadata,
+    ) {
+        self.enqueue(name, metadata, &extras);
+        self.selected.insert(
+            name.to_string(),
+            Selected {
+                version,
+                file,
+                index,
+                extras,
+            },
+        );
+    }
+
+    fn enqueue(&mut self, name: &str, metadata: &CoreMetadata, extras: &BTreeSet<String>) {
+        for requirement in metadata.requires_dist() {
+            self.pending.push_back(Pending {
+                parent: Some(name.to_string()),
+                extras: extras.iter().cloned().collect(),
+                requirement,
+            });
+        }
+    }
+
+    /// Get an `Error::DependencyConflict` listing the constraints on a package.
+    fn conflict(&self, name: &str) -> Error {
+        let constraints = self
+            .constraints
+            .get(name)
+            .map(|it| {
+                it.iter()
+                    .map(|(parent, req)| {
+                        let source = match parent {
+                            Some(p) => self
+                                .selected
+                                .get(p)
+                                .map_or_else(|| p.clone(), |it| format!("{p} {}", it.version)),
+                            None => PROJECT_LABEL.to_string(),
+                        };
+                        format!("{} (from {source})", requirement_string(req))
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .unwrap_or_default();
+
+        Error::DependencyConflict(format!("no version of {name} satisfies {constraints}"))
+    }
+
+    fn into_resolution(mut self, indexes: &[PackageIndex]) -> Resolution {
+        let mut roots = Vec::new();
+        let mut dependencies: HashMap<String, Vec<ResolvedDependency>> = HashMap::new();
+
+        for (parent, requirement) in self.edges {
+            let name = normalized_name(&requirement.name);
+            match parent {
+                Some(p) => {
+                    let deps = dependencies.entry(p).or_default();
+                    if !deps
+                        .iter()
+                        .any(|it| it.name == name && it.requirement == requirement)
+                    {
+                        deps.push(ResolvedDependency { name, requirement });
+                    }
+                }
+                None if !roots.contains(&name) => roots.push(name),
+                None => (),
+            }
+        }
+
+        self.selected.sort_keys();
+
+        let packages = self
+            .selected
+            .into_iter()
+            .map(|(name, selected)| {
+                let pkg = ResolvedPackage {
+                    dependencies: dependencies.remove(&name).unwrap_or_default(),
+                    name: name.clone(),
+                    version: selected.version,
+                    distribution: selected.file,
+                    index: selected
+                        .index
+                        .and_then(|i| indexes.get(i))
+                        .map(|it| it.url().to_string()),
+                    extras: selected.extras.into_iter().collect(),
+                };
+                (name, pkg)
+            })
+            .collect();
+
+        Resolution { packages, roots }
+    }
+}
+
+fn with_credentials(index: PackageIndex, credentials: &CredentialProvider) -> PackageIndex {
+    let credentials = credentials.credentials(index.url());
+    index.with_credentials(credentials)
+}
+
+/// Check if a `Requirement` is satisfied by a version and its distribution.
+fn satisfies(requirement: &Requirement, version: &Version, file: &DistributionFile) -> bool {
+    match requirement.version_or_url.as_ref() {
+        Some(VersionOrUrl::VersionSpecifier(it)) => it.contains(version),
+        Some(VersionOrUrl::Url(url)) => {
+            DistributionFile::from_url(url.as_str()).is_ok_and(|it| it.url == file.url)
+        }
+        None => true,
+    }
+}
+
+fn version_specifiers(requirement: &Requirement) -> Option<&VersionSpecifiers> {
+    match requirement.version_or_url.as_ref() {
+        Some(VersionOrUrl::VersionSpecifier(it)) => Some(it),
+        _ => None,
+    }
+}
+
+/// Write a wheel with METADATA and WHEEL files to a directory.
+#[cfg(test)]
+fn write_mock_wheel(dir: &std::path::Path, filename: &str, metadata: &str) -> std::path::PathBuf {
+    let wheel = WheelFilename::from_str(filename).unwrap();
+    let path = dir.join(filename);
+    let dist_info = format!(
+        "{}-{}.dist-info",
+        wheel.name.replace('-', "_"),
+        wheel.version
+    );
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+    for (name, contents) in [
+        ("METADATA", metadata),
+        (
+            "WHEEL",
+            "Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n",
+        ),
+    ] {
+        zip.start_file(
+            format!("{dist_info}/{name}"),
+            zip::write::FileOptions::default(),
+        )
+        .unwrap();
+        std::io::Write::write_all(&mut zip, contents.as_bytes()).unwrap();
+    }
+    zip.finish().unwrap();
+    path
+}
+
+/// A project's name, its versions with their requirements, and whether to use JSON.
+#[cfg(test)]
+pub(crate) type MockProject<'a> = (&'a str, &'a [(&'a str, &'a [&'a str])], bool);
+
+/// Write a local simple index with a page for each `MockProject`.
+#[cfg(test)]
+pub(crate) fn write_mock_index(root: &std::path::Path, projects: &[MockProject]) {
+    let files = root.join("files");
+    std::fs::create_dir_all(&files).unwrap();
+
+    for (name, versions, json) in projects {
+        let dir = root.join("simple").join(name);
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut entries = Vec::new();
+
+        for (version, requires) in *versions {
+            let filename = format!("{}-{version}-py3-none-any.whl", name.replace('-', "_"));
+            let mut metadata = format!("Metadata-Version: 2.1\nName: {name}\nVersion: {version}\n");
+            for req in *requires {
+                metadata.push_str("Requires-Dist: ");
+                metadata.push_str(req);
+                metadata.push('\n');
+            }
+            let path = write_mock_wheel(&files, &filename, &metadata);
+            let digest = hex::encode(<sha2::Sha256 as sha2::Digest>::digest(
+                std::fs::read(path).unwrap(),
+            ));
+            entries.push((filename, digest));
+        }
+
+        if *json {
+            let files = entries
+                .iter()
+                .map(|(f, d)| {
+                    serde_json::json!({
+                        "filename": f,
+                        "url": format!("../../files/{f}"),
+                        "hashes": {"sha256": d},
+                    })
+                })
+                .collect::<Vec<_>>();
+            let page =
+                serde_json::json!({"meta": {"api-version": "1.0"}, "name": name, "files": files});
+            std::fs::write(dir.join("index.json"), page.to_string()).unwrap();
+        } else {
+            let anchors = entries
+                .iter()
+                .map(|(f, d)| format!("<a href=\"../../files/{f}#sha256={d}\">{f}</a>"))
+                .collect::<Vec<_>>()
+                .join("<br/>");
+            std::fs::write(
+                dir.join("index.html"),
+                format!("<!DOCTYPE html><html><body>{anchors}</body></html>"),
+            )
+            .unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::python_environment::mock_marker_environment;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+    };
+    use tempfile::tempdir;
+
+    fn resolver(index_url: &str) -> Resolver {
+        Resolver::new(ResolverOptions {
+            index_urls: vec![index_url.to_string()],
+            find_links: Vec::new(),
+            package_indexes: HashMap::new(),
+            credentials: CredentialProvider::default(),
+            marker_environment: mock_marker_environment(),
+            prereleases: false,
+            offline: false,
+        })
+        .unwrap()
+    }
+
+    /// Serve a directory over http on a local port. Requests must use basic auth if an
+    /// `authorization` ("user:password") is provided.
+    fn serve_mock_index(root: std::path::PathBuf, authorization: Option<&str>) -> u16 {
+        use base64::Engine;
+
+        let authorization =
+            authorization.map(|it| base64::engine::general_purpose::STANDARD.encode(it));
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        // A minimal file server for the local index.
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else {
+                    continue;
+                };
+                let mut line = String::new();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                reader.read_line(&mut line).unwrap();
+                let mut authorized = authorization.is_none();
+                loop {
+                    let mut header = String::new();
+                    if reader.read_line(&mut header).unwrap() == 0 || header.trim().is_empty() {
+                        break;
+                    }
+                    let Some((key, value)) = header.trim().split_once(':') else {
+                        continue;
+                    };
+                    let credentials = value.trim().split_once(' ').and_then(|(scheme, it)| {
+                        scheme.eq_ignore_ascii_case("basic").then_some(it)
+                    });
+                    if key.eq_ignore_ascii_case("authorization")
+                        && credentials.is_some_and(|it| authorization.as_deref() == Some(it))
+                    {
+                        authorized = true;
+                    }
+                }
+
+                if !authorized {
+                    stream
+                        .write_all(b"HTTP/1.1 401 Unauthorized\r\nWWW-Authenticate: Basic\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                        .unwrap();
+                    continue;
+                }
+
+                let path = line.split_whitespace().nth(1).unwrap_or("/");
+                let mut path = root.join(path.trim_start_matches('/'));
+                let mut content_type = "application/octet-stream";
+                if path.is_dir() {
+                    if path.join("index.json").exists() {
+                        path = path.join("index.json");
+                        content_type = "application/vnd.pypi.simple.v1+json";
+                    } else {
+                        path = path.join("index.html");
+                        content_type = "text/html";
+                    }
+                }
+
+                let response = match std::fs::read(&path) {
+                    Ok(body) => {
+                        let mut response = format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                            body.len()
+                        )
+                        .into_bytes();
+                        response.extend(body);
+                        response
+                    }
+                    Err(_) => {
+                        b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                            .to_vec()
+                    }
+                };
+                stream.write_all(&response).unwrap();
+            }
+        });
+
+        port
+    }
+
+    fn dependencies(reqs: &[&str]) -> Vec<Dependency> {
+        reqs.iter()
+            .map(|it| Dependency::from_str(it).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_resolve_local_index() {
+        let dir = tempdir().unwrap();
+        write_mock_index(
+            dir.path(),
+            &[
+                (
+                    "app",
+                    &[
+                        ("1.0.0", &["lib>=1"]),
+                        ("2.0.0", &["lib>=1,<2", "extra-lib; extra == 'all'"]),
+                    ],
+                    true,
+                ),
+                (
+                    "lib",
+                    &[
+                        ("1.0.0", &[]),
+                        ("1.5.0", &["win-only; sys_platform == 'win32'"]),
+                        ("2.0.0", &[]),
+                    ],
+                    false,
+                ),
+                ("extra-lib", &[("0.1.0", &[])], true),
+                ("other", &[("1.0.0", &[]), ("2.0.0b1", &[])], false),
+            ],
+        );
+        let url = url::Url::from_directory_path(dir.path().join("simple")).unwrap();
+        let resolution = resolver(url.as_str())
+            .resolve(&dependencies(&["app[all]", "other"]))
+            .unwrap();
+
+        let versions = resolution
+            .packages()
+            .map(|it| (it.name.as_str(), it.version.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            versions,
+            vec![
+                ("app", "2.0.0".to_string()),
+                ("extra-lib", "0.1.0".to_string()),
+                ("lib", "1.5.0".to_string()),
+                ("other", "1.0.0".to_string()),
+            ]
+        );
+        assert_eq!(resolution.roots(), ["app", "other"]);
+        let app = resolution.get("app").unwrap();
+        assert_eq!(app.extras, vec!["all"]);
+        assert_eq!(
+            app.dependencies
+                .iter()
+                .map(|it| it.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["lib", "extra-lib"]
+        );
+        assert!(resolution.get("lib").unwrap().dependencies.is_empty());
+        assert_eq!(
+            resolution.get("lib").unwrap().index.as_deref(),
+            Some(url.as_str())
+        );
+    }
+
+    #[test]
+    fn test_resolve_backtracking() {
+        let dir = tempdir().unwrap();
+        write_mock_index(
+            dir.path(),
+            &[
+                ("a", &[("1.0.0", &["c<2"]), ("2.0.0", &["c>=2"])], true),
+                ("b", &[("1.0.0", &["c<2"])], true),
+                ("c", &[("1.0.0", &[]), ("2.0.0", &[])], true),
+            ],
+        );
+        let url = url::Url::from_directory_path(dir.path().join("simple")).unwrap();
+        let resolution = resolver(url.as_str())
+            .resolve(&dependencies(&["a", "b"]))
+            .unwrap();
+
+        assert_eq!(resolution.get("a").unwrap().version.to_string(), "1.0.0");
+        assert_eq!(resolution.get("c").unwrap().version.to_string(), "1.0.0");
+    }
+
+    #[test]
+    fn test_resolve_preferences() {
+        let dir = tempdir().unwrap();
+        write_mock_index(
+            dir.path(),
+            &[
+                ("a", &[("1.0.0", &["c"]), ("2.0.0", &["c"])], true),
+                ("c", &[("1.0.0", &[]), ("2.0.0", &[])], true),
+            ],
+        );
+        let url = url::Url::from_directory_path(dir.path().join("simple")).unwrap();
+        let preferences = HashMap::from([
+            ("A".to_string(), Version::from_str("1.0.0").unwrap()),
+            ("c".to_string(), Version::from_str("1.0.0").unwrap()),
+        ]);
+        let resolution = resolver(url.as_str())
+            .with_preferences(preferences)
+            .resolve(&dependencies(&["a", "c>=2"]))
+            .unwrap();
+
+        assert_eq!(resolution.get("a").unwrap().version.to_string(), "1.0.0");
+        assert_eq!(resolution.get("c").unwrap().version.to_string(), "2.0.0");
+    }
+
+    #[test]
+    fn test_resolve_vcs_reference() {
+        let dir = tempdir().unwrap();
+        write_mock_index(dir.path(), &[("a", &[("1.0.0", &[])], true)]);
+        let url = url::Url::from_directory_path(dir.path().join("simple")).unwrap();
+
+        assert!(matches!(
+            resolver(url.as_str())
+                .resolve(&dependencies(&["b @ git+https://github.com/mock/b@1a2b3c"])),
+            Err(Error::BuildRequired(_))
+        ));
+    }
+
+    #[test]
+    fn test_resolve_conflict() {
+        let dir = tempdir().unwrap();
+        write_mock_index(
+            dir.path(),
+            &[
+                ("a", &[("1.0.0", &["c>=2"])], true),
+                ("c", &[("1.0.0", &[]), ("2.0.0", &[])], false),
+            ],
+        );
+        let url = url::Url::from_directory_path(dir.path().join("simple")).unwrap();
+        let err = resolver(url.as_str())
+            .resolve(&dependencies(&["a", "c<2"]))
+            .unwrap_err();
+
+        let Error::DependencyConflict(msg) = err else {
+            panic!("expected a dependency conflict, got {err}");
+        };
+        assert_eq!(
+            msg,
+            "no version of c satisfies c<2 (from project), c>=2 (from a 1.0.0)"
+        );
+    }
+
+    #[test]
+    fn test_resolve_not_found() {
+        let dir = tempdir().unwrap();
+        write_mock_index(dir.path(), &[("a", &[("1.0.0", &[])], true)]);
+        let url = url::Url::from_directory_path(dir.path().join("simple")).unwrap();
+
+        assert!(matches!(
+            resolver(url.as_str()).resolve(&dependencies(&["missing"])),
+            Err(Error::PackageNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_resolve_find_links() {
+        let dir = tempdir().unwrap();
+        let wheels = dir.path().join("wheels");
+        std::fs::create_dir_all(&wheels).unwrap();
+        crate::wheel::write_mock_wheel(&wheels, "mock_package", "0.1.0");
+        crate::wheel::write_mock_wheel(&wheels, "mock_package", "0.2.0");
+        crate::wheel::write_mock_wheel(&wheels, "other", "1.0.0");
+        let resolver = Resolver::new(ResolverOptions {
+            index_urls: Vec::new(),
+            find_links: vec![wheels.display().to_string()],
+            package_indexes: HashMap::new(),
+            credentials: CredentialProvider::default(),
+            marker_environment: mock_marker_environment(),
+            prereleases: false,
+            offline: false,
+        })
+        .unwrap();
+
+        let resolution = resolver.resolve(&dependencies(&["mock-package"])).unwrap();
+
+        assert_eq!(
+            resolution
+                .packages()
+                .map(|it| (it.name.as_str(), it.version.to_string()))
+                .collect::<Vec<_>>(),
+            vec![("mock-package", "0.2.0".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_resolve_pinned_package() {
+        let dir = tempdir().unwrap();
+        let public = dir.path().join("public");
+        let private = dir.path().join("private");
+        write_mock_index(
+            &public,
+            &[
+                ("app", &[("1.0.0", &["internal"])], true),
+                ("internal", &[("99.0.0", &[])], true),
+            ],
+        );
+        write_mock_index(&private, &[("internal", &[("1.0.0", &[])], true)]);
+        let public_url = url::Url::from_directory_path(public.join("simple")).unwrap();
+        let private_url = url::Url::from_directory_path(private.join("simple")).unwrap();
+        let resolver = Resolver::new(ResolverOptions {
+            index_urls: vec![public_url.to_string()],
+            find_links: Vec::new(),
+            package_indexes: HashMap::from([("Internal".to_string(), private_url.to_string())]),
+            credentials: CredentialProvider::default(),
+            marker_environment: mock_marker_environment(),
+            prereleases: false,
+            offline: false,
+        })
+        .unwrap();
+
+        let resolution = resolver.resolve(&dependencies(&["app"])).unwrap();
+
+        let internal = resolution.get("internal").unwrap();
+        assert_eq!(internal.version.to_string(), "1.0.0");
+        assert_eq!(internal.index.as_deref(), Some(private_url.as_str()));
+        assert_eq!(
+            resolution.get("app").unwrap().index.as_deref(),
+            Some(public_url.as_str())
+        );
+    }
+
+    #[test]
+    fn test_resolve_pinned_package_offline() {
+        let dir = tempdir().unwrap();
+        let find_links = dir.path().join("files");
+        write_mock_index(
+            dir.path(),
+            &[
+                ("app", &[("1.0.0", &["internal"])], true),
+                ("internal", &[("99.0.0", &[])], true),
+            ],
+        );
+        let options = |pinned_url: &str| ResolverOptions {
+            index_urls: Vec::new(),
+            find_links: vec![find_links.display().to_string()],
+            package_indexes: HashMap::from([("internal".to_string(), pinned_url.to_string())]),
+            credentials: CredentialProvider::default(),
+            marker_environment: mock_marker_environment(),
+            prereleases: false,
+            offline: true,
+        };
+
+        // The package can't be resolved from the find-links directory instead of its index.
+        let resolver = Resolver::new(options("https://pypi.example.com/simple")).unwrap();
+        assert!(matches!(
+            resolver.resolve(&dependencies(&["app"])),
+            Err(Error::OfflineNetworkRequest(_))
+        ));
+
+        let private = dir.path().join("private");
+        write_mock_index(&private, &[("internal", &[("1.0.0", &[])], true)]);
+        let private_url = url::Url::from_directory_path(private.join("simple")).unwrap();
+        let resolution = Resolver::new(options(private_url.as_str()))
+            .unwrap()
+            .resolve(&dependencies(&["app"]))
+            .unwrap();
+        assert_eq!(
+            resolution.get("internal").unwrap().version.to_string(),
+            "1.0.0"
+        );
+    }
+
+    #[test]
+    fn test_resolve_http_index() {
+        let dir = tempdir().unwrap();
+        write_mock_index(
+            dir.path(),
+            &[
+                ("a", &[("1.0.0", &["b"])], true),
+                ("b", &[("0.1.0", &[])], false),
+            ],
+        );
+        let port = serve_mock_index(dir.path().to_path_buf(), None);
+
+        let resolution = resolver(&format!("http://127.0.0.1:{port}/simple"))
+            .resolve(&dependencies(&["a"]))
+            .unwrap();
+
+        assert_eq!(
+            resolution
+                .packages()
+                .map(|it| (it.name.as_str(), it.version.to_string()))
+                .collect::<Vec<_>>(),
+            vec![("a", "1.0.0".to_string()), ("b", "0.1.0".to_string())]
+        );
+        assert_eq!(
+            resolution.get("b").unwrap().distribution.url.as_str(),
+            format!("http://127.0.0.1:{port}/files/b-0.1.0-py3-none-any.whl")
+        );
+    }
+
+    #[test]
+    fn test_resolve_http_index_with_credentials() {
+        let dir = tempdir().unwrap();
+        write_mock_index(dir.path(), &[("a", &[("1.0.0", &[])], false)]);
+        let port = serve_mock_index(dir.path().to_path_buf(), Some("user:mock-password"));
+        let url = format!("http://127.0.0.1:{port}/simple");
+        let home = dir.path().join("home");
+        std::fs::create_dir_all(&home).unwrap();
+        let mut file = std::fs::OpenOptions::new();
+        file.write(true).create(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut file, 0o600);
+        file.open(home.join("credentials.toml"))
+            .unwrap()
+            .write_all(b"[private]\nusername = \"user\"\npassword = \"mock-password\"\n")
+            .unwrap();
+        let indexes = std::collections::BTreeMap::from([("private".to_string(), url.clone())]);
+        let options = |credentials| ResolverOptions {
+            index_urls: vec![url.clone()],
+            find_links: Vec::new(),
+            package_indexes: HashMap::new(),
+            credentials,
+            marker_environment: mock_marker_environment(),
+            prereleases: false,
+            offline: false,
+        };
+
+        assert!(Resolver::new(options(CredentialProvider::default()))
+            .unwrap()
+            .resolve(&dependencies(&["a"]))
+            .is_err());
+
+        let credentials = CredentialProvider::new(Some(&home), &indexes).unwrap();
+        let resolution = Resolver::new(options(credentials))
+            .unwrap()
+            .resolve(&dependencies(&["a"]))
+            .unwrap();
+
+        assert_eq!(resolution.get("a").unwrap().version.to_string(), "1.0.0");
+    }
+}
@@ -0,0 +1,611 @@
+use crate::{
+    lock::{normalized_name, InstallationReport, DEFAULT_PACKAGE_INDEX_URL},
+    Config, CredentialProvider, Error, HuakResult, PackageIndex, ResolverOptions,
+};
+use pep508_rs::MarkerEnvironment;
+use std::collections::BTreeMap;
+use toml_edit::{Item, TableLike, Value};
+
+/// The name of the default package index in `[tool.huak.repositories]`.
+const PYPI_INDEX_NAME: &str = "pypi";
+
+/// The package indexes and `find-links` locations packages are resolved from.
+///
+/// `find-links` locations are local directories of distributions or URLs of HTML pages
+/// linking to them. They're configured in `[tool.huak]`. Relative paths are relative to
+/// the project's root.
+///
+/// ```toml
+/// [tool.huak]
+/// find-links = ["wheels", "https://example.com/wheels.html"]
+/// ```
+///
+/// Package indexes are configured in `[tool.huak.repositories]`. Indexes are named in
+/// `indexes` and referenced by name (or URL) as the `default` index, `extra` indexes and
+/// the index each package in `packages` is pinned to. Pinned packages are only resolved
+/// from their index.
+///
+/// ```toml
+/// [tool.huak.repositories]
+/// default = "internal"
+/// extra = ["pypi"]
+///
+/// [tool.huak.repositories.indexes]
+/// internal = "https://pypi.example.com/simple"
+///
+/// [tool.huak.repositories.packages]
+/// internal-package = "internal"
+/// ```
+///
+/// In offline mode package indexes and remote `find-links` locations aren't used. Packages
+/// are resolved from the local `find-links` directories and the wheels in Huak's cache.
+/// Pinned packages are still only resolved from their index, so they can't be resolved
+/// offline unless their index is a local directory.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PackageSources {
+    /// The URLs of the package indexes. The first is the default index and earlier indexes
+    /// are preferred.
+    pub index_urls: Vec<String>,
+    /// The `find-links` locations.
+    pub find_links: Vec<String>,
+    /// The URLs of the indexes packages are pinned to by normalized package name.
+    pub package_indexes: BTreeMap<String, String>,
+    /// The URLs of the named indexes by name.
+    pub named_indexes: BTreeMap<String, String>,
+    /// The credentials for the indexes.
+    pub credentials: CredentialProvider,
+    /// Whether network access is disabled.
+    pub offline: bool,
+}
+
+impl PackageSources {
+    /// Get the `PackageSources` for a `Config`. An `index_url` (like one passed with
+    /// --index-url) replaces the default package index.
+    ///
+    /// Returns `Error::OfflineNetworkRequest` in offline mode if there are no local
+    /// locations to resolve packages from.
+    pub fn from_config(config: &Config, index_url: Option<&str>) -> HuakResult<PackageSources> {
+        let mut sources = PackageSources {
+            index_urls: vec![DEFAULT_PACKAGE_INDEX_URL.to_string()],
+            offline: config.offline,
+            ..Default::default()
+        };
+
+        if let Ok(manifest) = config.workspace().current_local_manifest() {
+            let root = manifest
+                .path()
+                .parent()
+                .unwrap_or(&config.cwd)
+                .to_path_buf();
+            let huak_table = manifest.manifest_data().huak_table();
+            let locations = huak_table
+                .and_then(|it| it.get("find-links"))
+                .and_then(Item::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str);
+
+            for location in locations {
+                if is_url(location) {
+                    sources.find_links.push(location.to_string());
+                } else {
+                    sources
+                        .find_links
+                        .push(root.join(location).display().to_string());
+                }
+            }
+
+            if let Some(table) = huak_table
+                .and_then(|it| it.get("repositories"))
+                .and_then(Item::as_table_like)
+            {
+                sources.add_repositories(table)?;
+            }
+        }
+
+        if let Some(url) = index_url {
+            sources.index_urls[0] = url.to_string();
+        }
+
+        sources.credentials =
+            CredentialProvider::new(config.home.as_deref(), &sources.named_indexes)?;
+
+        if !sources.offline {
+            return Ok(sources);
+        }
+
+        sources.index_urls.clear();
+        sources
+            .find_links
+            .retain(|it| !is_url(it) || it.starts_with("file:"));
+        if let Some(wheelhouse) = config.cache().map(|it| it.wheelhouse()) {
+            if wheelhouse.exists() {
+                sources.find_links.push(wheelhouse.display().to_string());
+            }
+        }
+
+        if sources.find_links.is_empty() {
+            return Err(Error::OfflineNetworkRequest(
+                "no find-links directories or cached wheels are available".to_string(),
+            ));
+        }
+
+        Ok(sources)
+    }
+
+    /// Get the URL of the index a package is resolved from. This is the index it's pinned
+    /// to or the default index.
+    #[must_use]
+    pub fn index_url(&self, name: &str) -> Option<&str> {
+        self.package_indexes
+            .get(&normalized_name(name))
+            .or(self.index_urls.first())
+            .map(String::as_str)
+    }
+
+    /// Get the arguments passing the `PackageSources` to pip.
+    ///
+    /// The default index is only passed if it isn't `pypi` so that pip's own configuration
+    /// is respected. Indexes packages are pinned to are passed as extra indexes. Indexes
+    /// are disabled with --no-index in offline mode.
+    #[must_use]
+    pub fn pip_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+
+        if self.offline {
+            args.push("--no-index".to_string());
+        }
+
+        let mut extra_urls = Vec::new();
+        for (i, url) in self.index_urls.iter().enumerate() {
+            if i == 0 {
+                if url != DEFAULT_PACKAGE_INDEX_URL {
+                    args.push("--index-url".to_string());
+                    args.push(url.clone());
+                }
+            } else if !extra_urls.contains(&url) {
+                extra_urls.push(url);
+            }
+        }
+        for url in self.package_indexes.values() {
+            if !self.index_urls.contains(url) && !extra_urls.contains(&url) {
+                extra_urls.push(url);
+            }
+        }
+        for url in extra_urls {
+            args.push("--extra-index-url".to_string());
+            args.push(url.clone());
+        }
+
+        for it in &self.find_links {
+            args.push("--find-links".to_string());
+            args.push(it.clone());
+        }
+
+        args
+    }
+
+    /// Get `ResolverOptions` for resolving from the `PackageSources`.
+    #[must_use]
+    pub fn resolver_options(&self, marker_environment: MarkerEnvironment) -> ResolverOptions {
+        ResolverOptions {
+            index_urls: self.index_urls.clone(),
+            find_links: self.find_links.clone(),
+            package_indexes: self.package_indexes.clone().into_iter().collect(),
+            credentials: self.credentials.clone(),
+            marker_environment,
+            prereleases: false,
+            offline: self.offline,
+        }
+    }
+
+    /// Check that the packages in an `InstallationReport` that are pinned to an index are
+    /// downloaded from it. pip doesn't support pinning packages to indexes, so a package
+    /// with the same name on another index could be selected instead (dependency
+    /// confusion).
+    ///
+    /// Returns `Error::PackageIndexMismatch` if a pinned package's distribution isn't the
+    /// file its index serves at the same URL with the same sha256 hash. Distributions
+    /// without a sha256 hash can't be verified and are rejected too. Direct references and
+    /// local files aren't checked unless Huak is offline, when every distribution is a
+    /// local file.
+    ///
+    /// Returns `Error::OfflineNetworkRequest` in offline mode if a package is pinned to a
+    /// remote index.
+    pub fn verify_report(&self, report: &InstallationReport) -> HuakResult<()> {
+        for item in report.items() {
+            let Some(url) = self.package_indexes.get(&normalized_name(item.name())) else {
+                continue;
+            };
+            let Some(file) = item.distribution_file() else {
+                continue;
+            };
+            if item.is_direct() || (file.url.scheme() == "file" && !self.offline) {
+                continue;
+            }
+            if self.offline && !url.starts_with("file:") {
+                return Err(Error::OfflineNetworkRequest(format!(
+                    "{} is pinned to {url}",
+                    item.name()
+                )));
+            }
+
+            let Some(digest) = file.hashes.get("sha256") else {
+                return Err(Error::PackageIndexMismatch(format!(
+                    "{} is pinned to {url} but {} has no sha256 hash to verify it with",
+                    item.name(),
+                    file.filename
+                )));
+            };
+
+            let index = self.package_index(url)?;
+            let found = index.project(item.name())?.is_some_and(|page| {
+                page.files.iter().any(|it| {
+                    it.filename == file.filename
+                        && it.url == file.url
+                        && it
+                            .hashes
+                            .get("sha256")
+                            .is_some_and(|it| it.eq_ignore_ascii_case(digest))
+                })
+            });
+
+            if !found {
+                return Err(Error::PackageIndexMismatch(format!(
+                    "{} is pinned to {url} but {} isn't available from it",
+                    item.name(),
+                    file.url
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the `PackageIndex` for a URL with its credentials.
+    pub fn package_index(&self, url: &str) -> HuakResult<PackageIndex> {
+        let index = PackageIndex::new(url)?;
+        let credentials = self.credentials.credentials(index.url());
+
+        Ok(index.with_credentials(credentials))
+    }
+
+    fn add_repositories(&mut self, table: &dyn TableLike) -> HuakResult<()> {
+        self.named_indexes.insert(
+            PYPI_INDEX_NAME.to_string(),
+            DEFAULT_PACKAGE_INDEX_URL.to_string(),
+        );
+        for (name, value) in table
+            .get("indexes")
+            .and_then(Item::as_table_like)
+            .into_iter()
+            .flat_map(TableLike::iter)
+        {
+            let Some(url) = value.as_str() else {
+                return Err(invalid_repositories(&format!(
+                    "the url of index {name} must be a string"
+                )));
+            };
+            self.named_indexes.insert(name.to_string(), url.to_string());
+        }
+
+        if let Some(value) = table.get("default") {
+            let Some(it) = value.as_str() else {
+                return Err(invalid_repositories("default must be a string"));
+            };
+            self.index_urls[0] = self.repository_url(it)?;
+        }
+
+        if let Some(value) = table.get("extra") {
+            let Some(extra) = value.as_array() else {
+                return Err(invalid_repositories("extra must be an array"));
+            };
+            for it in extra {
+                let Some(it) = it.as_str() else {
+                    return Err(invalid_repositories("extra must be an array of strings"));
+                };
+                let url = self.repository_url(it)?;
+                if !self.index_urls.contains(&url) {
+                    self.index_urls.push(url);
+                }
+            }
+        }
+
+        for (name, value) in table
+            .get("packages")
+            .and_then(Item::as_table_like)
+            .into_iter()
+            .flat_map(TableLike::iter)
+        {
+            let Some(it) = value.as_str() else {
+                return Err(invalid_repositories(&format!(
+                    "the index of package {name} must be a string"
+                )));
+            };
+            let url = self.repository_url(it)?;
+            self.package_indexes.insert(normalized_name(name), url);
+        }
+
+        Ok(())
+    }
+
+    /// Get the URL of a repository from its name or URL.
+    fn repository_url(&self, repository: &str) -> HuakResult<String> {
+        if is_url(repository) {
+            return Ok(repository.to_string());
+        }
+
+        self.named_indexes
+            .get(repository)
+            .cloned()
+            .ok_or_else(|| invalid_repositories(&format!("unknown index {repository}")))
+    }
+}
+
+fn invalid_repositories(msg: &str) -> Error {
+    Error::HuakConfigurationError(format!("[tool.huak.repositories] {msg}"))
+}
+
+fn is_url(location: &str) -> bool {
+    location.contains("://")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use tempfile::tempdir;
+    use url::Url;
+
+    #[test]
+    fn test_package_sources_from_config() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("pyproject.toml"),
+            "[project]\nname = \"mock-project\"\n\n[tool.huak]\nfind-links = [\"wheels\", \"https://example.com/wheels.html\"]\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(dir.path().join("home").join("cache").join("wheelhouse")).unwrap();
+        let mut config = Config {
+            workspace_root: dir.path().to_path_buf(),
+            cwd: dir.path().to_path_buf(),
+            home: Some(dir.path().join("home")),
+            ..Default::default()
+        };
+
+        let sources =
+            PackageSources::from_config(&config, Some("https://example.com/simple")).unwrap();
+
+        assert_eq!(sources.index_urls, vec!["https://example.com/simple"]);
+        assert_eq!(
+            sources.find_links,
+            vec![
+                dir.path().join("wheels").display().to_string(),
+                "https://example.com/wheels.html".to_string()
+            ]
+        );
+
+        config.offline = true;
+        let sources = PackageSources::from_config(&config, None).unwrap();
+
+        assert!(sources.index_urls.is_empty());
+        assert_eq!(
+            sources.pip_args(),
+            vec![
+                "--no-index".to_string(),
+                "--find-links".to_string(),
+                dir.path().join("wheels").display().to_string(),
+                "--find-links".to_string(),
+                dir.path()
+                    .join("home")
+                    .join("cache")
+                    .join("wheelhouse")
+                    .display()
+                    .to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_package_sources_repositories() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("pyproject.toml"),
+            r#"[project]
+name = "mock-project"
+
+[tool.huak.repositories]
+default = "internal"
+extra = ["pypi", "https://example.com/extra"]
+
+[tool.huak.repositories.indexes]
+internal = "https://pypi.example.com/simple"
+torch = "https://download.pytorch.org/whl/cpu"
+
+[tool.huak.repositories.packages]
+Internal_Package = "internal"
+torch = "torch"
+"#,
+        )
+        .unwrap();
+        let config = Config {
+            workspace_root: dir.path().to_path_buf(),
+            cwd: dir.path().to_path_buf(),
+            ..Default::default()
+        };
+
+        let sources = PackageSources::from_config(&config, None).unwrap();
+
+        assert_eq!(
+            sources.index_urls,
+            vec![
+                "https://pypi.example.com/simple",
+                DEFAULT_PACKAGE_INDEX_URL,
+                "https://example.com/extra"
+            ]
+        );
+        assert_eq!(
+            sources.index_url("internal-package"),
+            Some("https://pypi.example.com/simple")
+        );
+        assert_eq!(
+            sources.index_url("torch"),
+            Some("https://download.pytorch.org/whl/cpu")
+        );
+        assert_eq!(
+            sources.index_url("click"),
+            Some("https://pypi.example.com/simple")
+        );
+        assert_eq!(
+            sources.pip_args(),
+            vec![
+                "--index-url",
+                "https://pypi.example.com/simple",
+                "--extra-index-url",
+                DEFAULT_PACKAGE_INDEX_URL,
+                "--extra-index-url",
+                "https://example.com/extra",
+                "--extra-index-url",
+                "https://download.pytorch.org/whl/cpu",
+            ]
+        );
+
+        // An index passed with --index-url replaces the default index.
+        let sources =
+            PackageSources::from_config(&config, Some("https://example.com/simple")).unwrap();
+        assert_eq!(sources.index_urls[0], "https://example.com/simple");
+
+        std::fs::write(
+            dir.path().join("pyproject.toml"),
+            "[project]\nname = \"mock-project\"\n\n[tool.huak.repositories]\ndefault = \"missing\"\n",
+        )
+        .unwrap();
+        assert!(matches!(
+            PackageSources::from_config(&config, None),
+            Err(Error::HuakConfigurationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_offline_package_sources_without_local_locations() {
+        let dir = tempdir().unwrap();
+        let config = Config {
+            workspace_root: dir.path().to_path_buf(),
+            cwd: dir.path().to_path_buf(),
+            home: Some(dir.path().join("home")),
+            offline: true,
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            PackageSources::from_config(&config, None),
+            Err(Error::OfflineNetworkRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_report() {
+        let dir = tempdir().unwrap();
+        let filename = "internal_package-1.0.0.tar.gz";
+        let internal_file = format!("https://pypi.example.com/files/{filename}");
+        let pypi_file = format!("https://files.pythonhosted.org/packages/{filename}");
+        for (index, href) in [
+            ("internal", format!("{internal_file}#sha256=abc")),
+            ("pypi", format!("{pypi_file}#sha256=def")),
+        ] {
+            let project = dir.path().join(index).join("internal-package");
+            std::fs::create_dir_all(&project).unwrap();
+            std::fs::write(
+                project.join("index.html"),
+                format!("<html><body><a href=\"{href}\">{filename}</a></body></html>"),
+            )
+            .unwrap();
+        }
+        let sources = PackageSources {
+            package_indexes: BTreeMap::from([(
+                "internal-package".to_string(),
+                Url::from_directory_path(dir.path().join("internal"))
+                    .unwrap()
+                    .to_string(),
+            )]),
+            ..Default::default()
+        };
+        let report = |url: &str, archive_info: &str| {
+            InstallationReport::from_str(&format!(
+                r#"{{"install": [{{
+                    "download_info": {{"url": "{url}", "archive_info": {archive_info}}},
+                    "metadata": {{"name": "internal-package", "version": "1.0.0"}}
+                }}]}}"#
+            ))
+            .unwrap()
+        };
+
+        assert!(sources
+            .verify_report(&report(&internal_file, r#"{"hashes": {"sha256": "abc"}}"#))
+            .is_ok());
+        // pypi serves a file with the same name.
+        assert!(matches!(
+            sources.verify_report(&report(&pypi_file, r#"{"hashes": {"sha256": "def"}}"#)),
+            Err(Error::PackageIndexMismatch(_))
+        ));
+        assert!(matches!(
+            sources.verify_report(&report(&pypi_file, r#"{"hashes": {"sha256": "abc"}}"#)),
+            Err(Error::PackageIndexMismatch(_))
+        ));
+        assert!(matches!(
+            sources.verify_report(&report(&internal_file, r#"{"hashes": {"sha256": "def"}}"#)),
+            Err(Error::PackageIndexMismatch(_))
+        ));
+        assert!(matches!(
+            sources.verify_report(&report(&internal_file, "{}")),
+            Err(Error::PackageIndexMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_report_offline() {
+        let dir = tempdir().unwrap();
+        let filename = "internal_package-1.0.0.tar.gz";
+        let index_file = Url::from_file_path(dir.path().join("files").join(filename)).unwrap();
+        let find_links_file =
+            Url::from_file_path(dir.path().join("wheels").join(filename)).unwrap();
+        let project = dir.path().join("internal").join("internal-package");
+        std::fs::create_dir_all(&project).unwrap();
+        std::fs::write(
+            project.join("index.html"),
+            format!("<html><body><a href=\"{index_file}#sha256=abc\">{filename}</a></body></html>"),
+        )
+        .unwrap();
+        let sources = |url: String| PackageSources {
+            package_indexes: BTreeMap::from([("internal-package".to_string(), url)]),
+            offline: true,
+            ..Default::default()
+        };
+        let report = |url: &Url| {
+            InstallationReport::from_str(&format!(
+                r#"{{"install": [{{
+                    "download_info": {{"url": "{url}", "archive_info": {{"hashes": {{"sha256": "abc"}}}}}},
+                    "metadata": {{"name": "internal-package", "version": "1.0.0"}}
+                }}]}}"#
+            ))
+            .unwrap()
+        };
+        let local = sources(
+            Url::from_directory_path(dir.path().join("internal"))
+                .unwrap()
+                .to_string(),
+        );
+
+        assert!(local.verify_report(&report(&index_file)).is_ok());
+        // A local file that isn't served by the package's index.
+        assert!(matches!(
+            local.verify_report(&report(&find_links_file)),
+            Err(Error::PackageIndexMismatch(_))
+        ));
+        assert!(matches!(
+            sources("https://pypi.example.com/simple".to_string())
+                .verify_report(&report(&find_links_file)),
+            Err(Error::OfflineNetworkRequest(_))
+        ));
+    }
+}
@@ -1,5 +1,5 @@
 use crate::error::HuakResult;
-use crate::Error;
+use crate::{redact, Error};
 #[cfg(unix)]
 use std::os::unix::fs::symlink;
 #[cfg(windows)]
@@ -9,6 +9,7 @@ use std::{
     io::Write,
     path::Path,
     process::{Command, ExitStatus},
+    str::FromStr,
 };
 use tempfile::TempDir;
 use termcolor::{self, Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
@@ -44,6 +45,35 @@ pub enum Verbosity {
     Quiet,
 }
 
+/// The format of a report printed to stdout.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "table" => Ok(OutputFormat::Table),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(Error::InvalidOutputFormat(s.to_string())),
+        }
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputFormat::Table => write!(f, "table"),
+            OutputFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
 /// An abstraction around terminal output that remembers preferences for output
 /// verbosity and color (inspired by cargo's `Shell`).
 pub struct Terminal {
@@ -83,8 +113,12 @@ impl Terminal {
 
     /// Print an error message.
     pub fn print_error<T: Display>(&mut self, message: T) -> HuakResult<()> {
-        self.output
-            .message_stderr_with_status(&"error", Some(&message), Color::Red, false)
+        self.output.message_stderr_with_status(
+            &"error",
+            Some(&redact(&message.to_string())),
+            Color::Red,
+            false,
+        )
     }
 
     /// Prints a warning message.
@@ -117,12 +151,27 @@ impl Terminal {
     {
         match self.options.verbosity {
             Verbosity::Quiet => Ok(()),
-            _ => self.output.message_stderr(Some(&message), color),
+            _ => self
+                .output
+                .message_stderr(Some(&redact(&message.to_string())), color),
         }
     }
 
+    /// Prints a message to stdout.
+    ///
+    /// Output meant to be consumed by other programs, like a report or a tree, is printed
+    /// to stdout regardless of the verbosity.
+    #[allow(clippy::unused_self)]
+    pub fn print_stdout<T>(&mut self, message: T) -> HuakResult<()>
+    where
+        T: Display,
+    {
+        writeln!(std::io::stdout(), "{}", redact(&message.to_string()))?;
+        Ok(())
+    }
+
     /// Prints a message, where the status will have `color` color, and can be justified.
-    /// The messages follows without color.
+    /// The messages follows without color. Credentials are redacted from messages.
     ///
     /// NOTE: Messages are printed to stderr. This is behavior cargo implements as well to
     /// avoid polluting stdout for end users. See <https://github.com/rust-lang/cargo/issues/1473>.
@@ -135,9 +184,15 @@ impl Terminal {
     ) -> HuakResult<()> {
         match self.options.verbosity {
             Verbosity::Quiet => Ok(()),
-            _ => self
-                .output
-                .message_stderr_with_status(status, message, color, justified),
+            _ => self.output.message_stderr_with_status(
+                status,
+                message
+                    .map(|it| redact(&it.to_string()))
+                    .as_ref()
+                    .map(|it| it as &dyn Display),
+                color,
+                justified,
+            ),
         }
     }
 
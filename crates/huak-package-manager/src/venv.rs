@@ -0,0 +1,551 @@
+use crate::{
+    fs::last_path_component,
+    python_environment::{site_packages_dir_path, venv_config_file_name, VenvConfig},
+    sys, venv_executables_dir_path, Config, Error, HuakResult, PythonEnvironment,
+};
+use huak_python_manager::Version;
+use serde::Deserialize;
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+    str::FromStr,
+};
+
+/// Get the Python interpreter a virtual environment is created from. If the interpreter is
+/// a virtual environment's then its base interpreter is used.
+const BASE_INTERPRETER_SCRIPT: &str = r#"import json, sys
+
+print(json.dumps({
+    "executable": getattr(sys, "_base_executable", None) or sys.executable,
+    "version": "%d.%d.%d" % sys.version_info[:3],
+}))
+"#;
+
+/// The activation script for bash and zsh (`source .venv/bin/activate`).
+const ACTIVATE_SH: &str = r#"# This file must be used with "source bin/activate" *from bash or zsh*.
+# You cannot run it directly.
+
+deactivate () {
+    # Reset old environment variables.
+    if [ -n "${_OLD_VIRTUAL_PATH:-}" ] ; then
+        PATH="${_OLD_VIRTUAL_PATH:-}"
+        export PATH
+        unset _OLD_VIRTUAL_PATH
+    fi
+    if [ -n "${_OLD_VIRTUAL_PYTHONHOME:-}" ] ; then
+        PYTHONHOME="${_OLD_VIRTUAL_PYTHONHOME:-}"
+        export PYTHONHOME
+        unset _OLD_VIRTUAL_PYTHONHOME
+    fi
+
+    # Forget past commands so that the PATH changes are respected.
+    hash -r 2> /dev/null
+
+    if [ -n "${_OLD_VIRTUAL_PS1:-}" ] ; then
+        PS1="${_OLD_VIRTUAL_PS1:-}"
+        export PS1
+        unset _OLD_VIRTUAL_PS1
+    fi
+
+    unset VIRTUAL_ENV
+    unset VIRTUAL_ENV_PROMPT
+    if [ ! "${1:-}" = "nondestructive" ] ; then
+        unset -f deactivate
+    fi
+}
+
+# Unset irrelevant variables.
+deactivate nondestructive
+
+VIRTUAL_ENV='__VENV_DIR__'
+export VIRTUAL_ENV
+
+_OLD_VIRTUAL_PATH="$PATH"
+PATH="$VIRTUAL_ENV/__VENV_BIN_NAME__:$PATH"
+export PATH
+
+if [ -n "${PYTHONHOME:-}" ] ; then
+    _OLD_VIRTUAL_PYTHONHOME="${PYTHONHOME:-}"
+    unset PYTHONHOME
+fi
+
+if [ -z "${VIRTUAL_ENV_DISABLE_PROMPT:-}" ] ; then
+    _OLD_VIRTUAL_PS1="${PS1:-}"
+    PS1='(__VENV_PROMPT__) '"${PS1:-}"
+    export PS1
+fi
+VIRTUAL_ENV_PROMPT='__VENV_PROMPT__'
+export VIRTUAL_ENV_PROMPT
+
+hash -r 2> /dev/null
+"#;
+
+/// The activation script for fish (`source .venv/bin/activate.fish`).
+const ACTIVATE_FISH: &str = r#"# This file must be used with "source bin/activate.fish" *from fish*.
+# You cannot run it directly.
+
+function deactivate -d "Exit the virtual environment and return to the normal shell environment"
+    # Reset old environment variables.
+    if test -n "$_OLD_VIRTUAL_PATH"
+        set -gx PATH $_OLD_VIRTUAL_PATH
+        set -e _OLD_VIRTUAL_PATH
+    end
+    if test -n "$_OLD_VIRTUAL_PYTHONHOME"
+        set -gx PYTHONHOME $_OLD_VIRTUAL_PYTHONHOME
+        set -e _OLD_VIRTUAL_PYTHONHOME
+    end
+
+    if test -n "$_OLD_FISH_PROMPT_OVERRIDE"
+        set -e _OLD_FISH_PROMPT_OVERRIDE
+        if functions -q _old_fish_prompt
+            functions -e fish_prompt
+            functions -c _old_fish_prompt fish_prompt
+            functions -e _old_fish_prompt
+        end
+    end
+
+    set -e VIRTUAL_ENV
+    set -e VIRTUAL_ENV_PROMPT
+    if test "$argv[1]" != "nondestructive"
+        functions -e deactivate
+    end
+end
+
+# Unset irrelevant variables.
+deactivate nondestructive
+
+set -gx VIRTUAL_ENV '__VENV_DIR__'
+
+set -gx _OLD_VIRTUAL_PATH $PATH
+set -gx PATH "$VIRTUAL_ENV/__VENV_BIN_NAME__" $PATH
+
+if set -q PYTHONHOME
+    set -gx _OLD_VIRTUAL_PYTHONHOME $PYTHONHOME
+    set -e PYTHONHOME
+end
+
+if test -z "$VIRTUAL_ENV_DISABLE_PROMPT"
+    functions -c fish_prompt _old_fish_prompt
+
+    function fish_prompt
+        set -l old_status $status
+        printf "%s%s%s" (set_color 4B8BBE) '(__VENV_PROMPT__) ' (set_color normal)
+
+        # Restore the status of the previous command for the old prompt.
+        echo "exit $old_status" | .
+        _old_fish_prompt
+    end
+
+    set -gx _OLD_FISH_PROMPT_OVERRIDE "$VIRTUAL_ENV"
+end
+set -gx VIRTUAL_ENV_PROMPT '__VENV_PROMPT__'
+"#;
+
+/// The activation script for nushell (`overlay use .venv/bin/activate.nu`). Hiding the
+/// overlay (`deactivate`) restores the environment.
+const ACTIVATE_NU: &str = r#"# This file must be used with "overlay use bin/activate.nu" *from nushell*.
+# Use "deactivate" to leave the virtual environment.
+
+export-env {
+    let venv_dir = r#'__VENV_DIR__'#
+    let venv_prompt = r#'__VENV_PROMPT__'#
+    let path_name = if ('Path' in $env) { 'Path' } else { 'PATH' }
+    let old_path = ($env | get $path_name)
+    let old_path = if (($old_path | describe) == 'string') {
+        $old_path | split row (char esep)
+    } else {
+        $old_path
+    }
+    let new_path = ($old_path | prepend ($venv_dir | path join '__VENV_BIN_NAME__'))
+
+    load-env {
+        $path_name: $new_path
+        VIRTUAL_ENV: $venv_dir
+        VIRTUAL_ENV_PROMPT: $venv_prompt
+    }
+
+    if ($env.VIRTUAL_ENV_DISABLE_PROMPT? | is-empty) {
+        let old_prompt = ($env.PROMPT_COMMAND? | default '')
+        let new_prompt = if (($old_prompt | describe) == 'closure') {
+            {|| '(' + $venv_prompt + ') ' + (do $old_prompt) }
+        } else {
+            {|| '(' + $venv_prompt + ') ' + $old_prompt }
+        }
+        load-env { PROMPT_COMMAND: $new_prompt }
+    }
+}
+
+export alias deactivate = overlay hide activate
+"#;
+
+/// The activation script for `PowerShell` (`.venv\Scripts\activate.ps1`).
+const ACTIVATE_PS1: &str = r#"# This file must be dot sourced from PowerShell (". .venv\Scripts\activate.ps1").
+
+function global:deactivate([switch] $NonDestructive) {
+    if (Test-Path -Path Function:_OLD_VIRTUAL_PROMPT) {
+        Copy-Item -Path Function:_OLD_VIRTUAL_PROMPT -Destination Function:prompt
+        Remove-Item -Path Function:_OLD_VIRTUAL_PROMPT
+    }
+    if (Test-Path -Path Env:_OLD_VIRTUAL_PATH) {
+        Copy-Item -Path Env:_OLD_VIRTUAL_PATH -Destination Env:PATH
+        Remove-Item -Path Env:_OLD_VIRTUAL_PATH
+    }
+    if (Test-Path -Path Env:VIRTUAL_ENV) {
+        Remove-Item -Path Env:VIRTUAL_ENV
+    }
+    if (Test-Path -Path Env:VIRTUAL_ENV_PROMPT) {
+        Remove-Item -Path Env:VIRTUAL_ENV_PROMPT
+    }
+    if (-not $NonDestructive) {
+        Remove-Item -Path Function:deactivate
+    }
+}
+
+# Unset irrelevant variables.
+deactivate -NonDestructive
+
+$env:VIRTUAL_ENV = '__VENV_DIR__'
+$env:VIRTUAL_ENV_PROMPT = '__VENV_PROMPT__'
+
+if (-not $env:VIRTUAL_ENV_DISABLE_PROMPT) {
+    function global:_OLD_VIRTUAL_PROMPT { "" }
+    Copy-Item -Path Function:prompt -Destination Function:_OLD_VIRTUAL_PROMPT
+    function global:prompt {
+        Write-Host -NoNewline -ForegroundColor Green "($env:VIRTUAL_ENV_PROMPT) "
+        _OLD_VIRTUAL_PROMPT
+    }
+}
+
+Copy-Item -Path Env:PATH -Destination Env:_OLD_VIRTUAL_PATH
+$env:PATH = (Join-Path $env:VIRTUAL_ENV '__VENV_BIN_NAME__') + [System.IO.Path]::PathSeparator + $env:PATH
+"#;
+
+/// A struct used to configure the creation of virtual environments.
+#[derive(Clone, Debug)]
+pub struct VenvOptions {
+    /// Symlink the Python interpreter instead of copying it.
+    pub symlinks: bool,
+    /// Install pip to the virtual environment.
+    pub seed_pip: bool,
+    /// The prompt activation scripts display. Defaults to the name of the virtual
+    /// environment's directory.
+    pub prompt: Option<String>,
+}
+
+impl Default for VenvOptions {
+    fn default() -> Self {
+        Self {
+            symlinks: sys::symlink_supported(),
+            seed_pip: false,
+            prompt: None,
+        }
+    }
+}
+
+/// Create a virtual environment at `path` for the Python interpreter at `python_path`.
+///
+/// The virtual environment is created without Python's `venv` module (which some minimal
+/// Python distributions don't include). `create_venv` writes the pyvenv.cfg, links or copies
+/// the interpreter, creates the site-packages directory and writes activation scripts for
+/// bash, zsh, fish, nushell and `PowerShell`. If `python_path` is a virtual environment's
+/// interpreter then the new virtual environment uses its base interpreter.
+///
+/// See <https://peps.python.org/pep-0405/>.
+pub fn create_venv<T: Into<PathBuf>>(
+    path: T,
+    python_path: &Path,
+    options: &VenvOptions,
+    config: &Config,
+) -> HuakResult<PythonEnvironment> {
+    let path = path.into();
+    let root = if path.is_absolute() {
+        path
+    } else {
+        std::env::current_dir()?.join(path)
+    };
+    let base = BaseInterpreter::new(python_path)?;
+    let version = Version::from_str(&base.version)?;
+
+    let executables_dir = venv_executables_dir_path(&root);
+    std::fs::create_dir_all(&executables_dir)?;
+    std::fs::create_dir_all(site_packages_dir_path(&root, version))?;
+    #[cfg(all(unix, not(target_os = "macos"), target_pointer_width = "64"))]
+    if !root.join("lib64").exists() {
+        std::os::unix::fs::symlink("lib", root.join("lib64"))?;
+    }
+
+    VenvConfig {
+        home: base.executable.parent().map(Path::to_path_buf),
+        include_system_site_packages: false,
+        version,
+        executable: Some(base.executable.clone()),
+        prompt: options.prompt.clone(),
+    }
+    .write(root.join(venv_config_file_name()))?;
+
+    link_interpreter(
+        &base.executable,
+        &executables_dir,
+        version,
+        options.symlinks,
+    )?;
+
+    let prompt = match options.prompt.as_ref() {
+        Some(it) => it.clone(),
+        None => last_path_component(&root)?,
+    };
+    write_activation_scripts(&root, &executables_dir, &prompt)?;
+
+    let python_env = PythonEnvironment::new(&root)?;
+
+    if options.seed_pip {
+        let mut cmd = Command::new(python_env.python_path());
+        cmd.args(["-Im", "ensurepip", "--upgrade", "--default-pip"]);
+        config.terminal().run_command(&mut cmd)?;
+    }
+
+    Ok(python_env)
+}
+
+/// The Python interpreter a virtual environment is created from.
+#[derive(Deserialize)]
+struct BaseInterpreter {
+    executable: PathBuf,
+    version: String,
+}
+
+impl BaseInterpreter {
+    fn new(python_path: &Path) -> HuakResult<Self> {
+        let mut cmd = Command::new(python_path);
+        cmd.args(["-c", BASE_INTERPRETER_SCRIPT]);
+
+        let output = cmd.output()?;
+        if !output.status.success() {
+            return Err(Error::SubprocessFailure(sys::SubprocessError::new(
+                output.status,
+            )));
+        }
+
+        Ok(serde_json::from_slice(&output.stdout)?)
+    }
+}
+
+/// Link or copy the base interpreter to the virtual environment's executables directory as
+/// python, python3 and python3.Y.
+#[cfg(unix)]
+fn link_interpreter(
+    executable: &Path,
+    executables_dir: &Path,
+    version: Version,
+    symlinks: bool,
+) -> HuakResult<()> {
+    let python = executables_dir.join("python");
+    link_or_copy(executable, &python, symlinks)?;
+
+    for name in [
+        format!("python{}", version.major),
+        format!("python{}.{}", version.major, version.minor),
+    ] {
+        let path = executables_dir.join(name);
+        if symlinks {
+            replace_file(&path)?;
+            std::os::unix::fs::symlink("python", path)?;
+        } else {
+            link_or_copy(executable, &path, false)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Link or copy the base interpreter, pythonw.exe and the DLLs next to them to the virtual
+/// environment's executables directory.
+#[cfg(windows)]
+fn link_interpreter(
+    executable: &Path,
+    executables_dir: &Path,
+    _version: Version,
+    symlinks: bool,
+) -> HuakResult<()> {
+    let Some(home) = executable.parent() else {
+        return Err(Error::PythonNotFound);
+    };
+
+    link_or_copy(executable, &executables_dir.join("python.exe"), symlinks)?;
+    for entry in std::fs::read_dir(home)? {
+        let path = entry?.path();
+        let Some(name) = path.file_name() else {
+            continue;
+        };
+        let is_dll = path
+            .extension()
+            .is_some_and(|it| it.eq_ignore_ascii_case("dll"));
+        if is_dll || name.eq_ignore_ascii_case("pythonw.exe") {
+            link_or_copy(&path, &executables_dir.join(name), symlinks)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn link_or_copy(from: &Path, to: &Path, symlinks: bool) -> HuakResult<()> {
+    replace_file(to)?;
+
+    if symlinks {
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(from, to)?;
+        #[cfg(windows)]
+        std::os::windows::fs::symlink_file(from, to)?;
+    } else {
+        std::fs::copy(from, to)?;
+    }
+
+    Ok(())
+}
+
+/// Remove a file (or a link) so that it can be replaced.
+fn replace_file(path: &Path) -> HuakResult<()> {
+    if path.symlink_metadata().is_ok() {
+        std::fs::remove_file(path)?;
+    }
+
+    Ok(())
+}
+
+/// Write the activation scripts to the virtual environment's executables directory.
+fn write_activation_scripts(root: &Path, executables_dir: &Path, prompt: &str) -> HuakResult<()> {
+    let dir = root.display().to_string();
+    let bin_name = last_path_component(executables_dir)?;
+
+    for (name, template, quote) in [
+        ("activate", ACTIVATE_SH, quote_sh as fn(&str) -> String),
+        ("activate.fish", ACTIVATE_FISH, quote_fish),
+        ("activate.nu", ACTIVATE_NU, ToString::to_string),
+        ("activate.ps1", ACTIVATE_PS1, quote_ps1),
+    ] {
+        let script = template
+            .replace("__VENV_DIR__", &quote(&dir))
+            .replace("__VENV_BIN_NAME__", &quote(&bin_name))
+            .replace("__VENV_PROMPT__", &quote(prompt));
+        std::fs::write(executables_dir.join(name), script)?;
+    }
+
+    Ok(())
+}
+
+/// Escape a value for a single-quoted sh string.
+fn quote_sh(s: &str) -> String {
+    s.replace('\'', r"'\''")
+}
+
+/// Escape a value for a single-quoted fish string.
+fn quote_fish(s: &str) -> String {
+    s.replace('\\', r"\\").replace('\'', r"\'")
+}
+
+/// Escape a value for a single-quoted `PowerShell` string.
+fn quote_ps1(s: &str) -> String {
+    s.replace('\'', "''")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TerminalOptions, Verbosity};
+    use tempfile::tempdir;
+
+    /// Get the path to a Python interpreter that runs (pyenv shims might not).
+    fn python_path() -> Option<PathBuf> {
+        crate::Environment::new()
+            .python_paths()
+            .find(|it| BaseInterpreter::new(it).is_ok())
+            .map(PathBuf::from)
+    }
+
+    #[test]
+    fn test_venv_config() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("pyvenv.cfg");
+        let cfg = VenvConfig {
+            home: Some(PathBuf::from("/usr/bin")),
+            include_system_site_packages: false,
+            version: Version::new(3, 11, 7),
+            executable: Some(PathBuf::from("/usr/bin/python3.11")),
+            prompt: Some("it's".to_string()),
+        };
+
+        cfg.write(&path).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            r"home = /usr/bin
+include-system-site-packages = false
+version = 3.11.7
+executable = /usr/bin/python3.11
+prompt = 'it's'
+"
+        );
+
+        let cfg = VenvConfig::new(&path).unwrap();
+
+        assert_eq!(cfg.home, Some(PathBuf::from("/usr/bin")));
+        assert_eq!(cfg.version, Version::new(3, 11, 7));
+        assert_eq!(cfg.prompt.as_deref(), Some("it's"));
+    }
+
+    #[test]
+    fn test_activation_scripts() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("it's a venv");
+        let executables_dir = venv_executables_dir_path(&root);
+        std::fs::create_dir_all(&executables_dir).unwrap();
+
+        write_activation_scripts(&root, &executables_dir, "it's").unwrap();
+
+        let sh = std::fs::read_to_string(executables_dir.join("activate")).unwrap();
+        let fish = std::fs::read_to_string(executables_dir.join("activate.fish")).unwrap();
+        let nu = std::fs::read_to_string(executables_dir.join("activate.nu")).unwrap();
+
+        assert!(sh.contains(&format!(
+            "VIRTUAL_ENV='{}'\n",
+            root.display().to_string().replace('\'', r"'\''")
+        )));
+        assert!(sh.contains(r"VIRTUAL_ENV_PROMPT='it'\''s'"));
+        assert!(fish.contains(r"set -gx VIRTUAL_ENV_PROMPT 'it\'s'"));
+        assert!(nu.contains("let venv_prompt = r#'it's'#"));
+        for it in [sh, fish, nu] {
+            assert!(!it.contains("__VENV_"));
+        }
+    }
+
+    #[test]
+    fn test_create_venv() {
+        let Some(python_path) = python_path() else {
+            return;
+        };
+        let dir = tempdir().unwrap();
+        let config = Config {
+            workspace_root: dir.path().to_path_buf(),
+            cwd: dir.path().to_path_buf(),
+            terminal_options: TerminalOptions {
+                verbosity: Verbosity::Quiet,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let path = dir.path().join(".venv");
+
+        let venv = create_venv(&path, &python_path, &VenvOptions::default(), &config).unwrap();
+        let output = Command::new(venv.python_path())
+            .args(["-c", "import sys; print(sys.prefix)"])
+            .output()
+            .unwrap();
+
+        assert!(venv.site_packages_dir_path().exists());
+        assert!(venv.executables_dir_path().join("activate").exists());
+        assert_eq!(
+            PathBuf::from(sys::parse_command_output(&output).unwrap().trim()),
+            path
+        );
+    }
+}
@@ -0,0 +1,843 @@
+use crate::{Error, HuakResult, InstalledDistribution, WheelFilename};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    ffi::OsStr,
+    fs::File,
+    io::{Read, Seek},
+    path::{Component, Path, PathBuf},
+    str::FromStr,
+};
+use zip::ZipArchive;
+
+/// The name written to the INSTALLER file of distributions installed by huak.
+const INSTALLER_NAME: &str = "huak";
+
+/// The directories a `Wheel`'s files are installed to.
+///
+/// See <https://docs.python.org/3/library/sysconfig.html#installation-paths>.
+#[derive(Clone, Debug)]
+pub struct InstallScheme {
+    /// The directory for pure Python modules (site-packages).
+    pub purelib: PathBuf,
+    /// The directory for platform-specific modules.
+    pub platlib: PathBuf,
+    /// The directory for scripts and console-script launchers.
+    pub scripts: PathBuf,
+    /// The directory for C headers. Each distribution's headers are installed to a
+    /// directory named after it.
+    pub headers: PathBuf,
+    /// The directory for data files.
+    pub data: PathBuf,
+    /// The Python interpreter used to run scripts.
+    pub python_path: PathBuf,
+}
+
+/// A built distribution (wheel) file.
+///
+/// See <https://packaging.python.org/en/latest/specifications/binary-distribution-format/>.
+///
+/// ```no_run
+/// use huak_package_manager::{PythonEnvironment, Wheel};
+///
+/// let python_env = PythonEnvironment::new(".venv").unwrap();
+/// let wheel = Wheel::new("click-8.1.7-py3-none-any.whl").unwrap();
+/// let distribution = wheel.install(&python_env.install_scheme()).unwrap();
+/// ```
+pub struct Wheel {
+    path: PathBuf,
+    filename: WheelFilename,
+}
+
+impl Wheel {
+    /// Initialize a `Wheel` from the path to its file.
+    pub fn new<T: Into<PathBuf>>(path: T) -> HuakResult<Wheel> {
+        let path = path.into();
+        let filename = path
+            .file_name()
+            .and_then(OsStr::to_str)
+            .ok_or_else(|| Error::InvalidWheel(path.display().to_string()))?;
+
+        Ok(Wheel {
+            filename: WheelFilename::from_str(filename)?,
+            path,
+        })
+    }
+
+    /// Get a reference to the path to the `Wheel`'s file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Get a reference to the `Wheel`'s parsed `WheelFilename`.
+    #[must_use]
+    pub fn filename(&self) -> &WheelFilename {
+        &self.filename
+    }
+
+    /// Install the `Wheel` to the directories of an `InstallScheme`.
+    ///
+    /// The wheel is unpacked to a temporary directory and its files are copied from there.
+    pub fn install(&self, scheme: &InstallScheme) -> HuakResult<InstalledDistribution> {
+        let dir = tempfile::tempdir()?;
+
+        self.unpack(dir.path())?.install(scheme)
+    }
+
+    /// Unpack the `Wheel`'s files to a directory. Files are verified against the wheel's
+    /// RECORD as they're unpacked.
+    pub fn unpack<T: Into<PathBuf>>(&self, dir: T) -> HuakResult<UnpackedWheel> {
+        let dir = dir.into();
+        let mut archive = ZipArchive::new(File::open(&self.path)?).map_err(|e| self.invalid(e))?;
+        let record_path = archive
+            .file_names()
+            .find(|it| {
+                it.strip_suffix("/RECORD")
+                    .is_some_and(|dir| dir.ends_with(".dist-info") && !dir.contains('/'))
+            })
+            .map(ToString::to_string)
+            .unwrap_or_default();
+        let expected_hashes = record_digests(&String::from_utf8_lossy(
+            &read_entry(&mut archive, &record_path)?.unwrap_or_default(),
+        ));
+
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i).map_err(|e| self.invalid(e))?;
+            let name = file.name().to_string();
+
+            if file.is_dir() {
+                continue;
+            }
+
+            if file.enclosed_name().is_none() {
+                return Err(self.invalid(format!("{name} is outside of the wheel")));
+            }
+
+            let mut contents = Vec::new();
+            file.read_to_end(&mut contents)?;
+
+            if let Some(expected) = expected_hashes.get(&name) {
+                if expected.split(',').next() != Some(&format!("sha256={}", record_hash(&contents)))
+                {
+                    return Err(Error::InvalidChecksum(name));
+                }
+            }
+
+            let executable = file.unix_mode().is_some_and(|it| it & 0o111 != 0);
+            write_file(&dir.join(&name), &contents, executable)?;
+        }
+
+        UnpackedWheel::new(dir)
+            .map_err(|_| self.invalid("the wheel is missing a *.dist-info/WHEEL file"))
+    }
+
+    fn invalid<T: std::fmt::Display>(&self, reason: T) -> Error {
+        Error::InvalidWheel(format!("{}: {reason}", self.path.display()))
+    }
+}
+
+/// A wheel unpacked to a directory.
+///
+/// Unpacked wheels are installed by copying or hard linking their files to the
+/// directories of an `InstallScheme`.
+pub struct UnpackedWheel {
+    path: PathBuf,
+    dist_info: String,
+}
+
+impl UnpackedWheel {
+    /// Initialize an `UnpackedWheel` from the directory it was unpacked to.
+    pub fn new<T: Into<PathBuf>>(path: T) -> HuakResult<UnpackedWheel> {
+        let path = path.into();
+        let dist_info = std::fs::read_dir(&path)?
+            .filter_map(Result::ok)
+            .filter_map(|it| it.file_name().into_string().ok())
+            .find(|it| it.ends_with(".dist-info") && path.join(it).join("WHEEL").is_file())
+            .ok_or_else(|| {
+                Error::InvalidWheel(format!(
+                    "{}: the wheel is missing a *.dist-info/WHEEL file",
+                    path.display()
+                ))
+            })?;
+
+        Ok(UnpackedWheel { path, dist_info })
+    }
+
+    /// Get a reference to the path to the directory the wheel was unpacked to.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Get the name of the distribution from its *.dist-info directory.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        self.dist_info.split('-').next().unwrap_or(&self.dist_info)
+    }
+
+    /// Install the wheel to the directories of an `InstallScheme` by copying its files.
+    ///
+    /// Files in the wheel's .data directory are installed to the scheme directory they're
+    /// listed under and console-script launchers are generated from its `entry_points.txt`.
+    /// The installed files are written to the distribution's RECORD.
+    pub fn install(&self, scheme: &InstallScheme) -> HuakResult<InstalledDistribution> {
+        self.install_files(scheme, false)
+    }
+
+    /// Install the wheel to the directories of an `InstallScheme` by hard linking its files.
+    ///
+    /// Files are copied if they can't be linked (for example across file systems). Scripts
+    /// are always copied since their shebangs are rewritten.
+    pub fn link(&self, scheme: &InstallScheme) -> HuakResult<InstalledDistribution> {
+        self.install_files(scheme, true)
+    }
+
+    fn install_files(
+        &self,
+        scheme: &InstallScheme,
+        link: bool,
+    ) -> HuakResult<InstalledDistribution> {
+        let dist_info = &self.dist_info;
+        let stem = dist_info.trim_end_matches(".dist-info");
+        let data_dir = format!("{stem}.data");
+
+        let wheel_metadata = std::fs::read_to_string(self.path.join(dist_info).join("WHEEL"))?;
+        let wheel_version = metadata_value(&wheel_metadata, "Wheel-Version").unwrap_or_default();
+        if wheel_version.split('.').next() != Some("1") {
+            return Err(self.invalid(format!("Wheel-Version {wheel_version} isn't supported")));
+        }
+        let root = if metadata_value(&wheel_metadata, "Root-Is-Purelib")
+            .is_some_and(|it| it.eq_ignore_ascii_case("true"))
+        {
+            &scheme.purelib
+        } else {
+            &scheme.platlib
+        };
+
+        let record_path = format!("{dist_info}/RECORD");
+        let digests = record_digests(
+            &std::fs::read_to_string(self.path.join(&record_path)).unwrap_or_default(),
+        );
+        let mut records = Vec::new();
+
+        for name_in_wheel in relative_files(&self.path)? {
+            if name_in_wheel == record_path {
+                continue;
+            }
+
+            let (path, is_script) = match name_in_wheel.strip_prefix(&format!("{data_dir}/")) {
+                Some(rest) => {
+                    let (key, rest) = rest.split_once('/').ok_or_else(|| {
+                        self.invalid(format!("{name_in_wheel} isn't in a scheme directory"))
+                    })?;
+                    let dir = match key {
+                        "purelib" => scheme.purelib.clone(),
+                        "platlib" => scheme.platlib.clone(),
+                        "scripts" => scheme.scripts.clone(),
+                        "headers" => scheme.headers.join(self.name()),
+                        "data" => scheme.data.clone(),
+                        _ => return Err(self.invalid(format!("{key} isn't a scheme directory"))),
+                    };
+                    (dir.join(rest), key == "scripts")
+                }
+                None => (root.join(&name_in_wheel), false),
+            };
+            let source = self.path.join(&name_in_wheel);
+
+            if is_script {
+                let mut contents = std::fs::read(&source)?;
+                if contents.starts_with(b"#!python") {
+                    let end = contents
+                        .iter()
+                        .position(|it| *it == b'\n')
+                        .unwrap_or(contents.len());
+                    let mut script = shebang(&scheme.python_path).into_bytes();
+                    script.extend_from_slice(&contents[end..]);
+                    contents = script;
+                }
+                write_file(&path, &contents, true)?;
+                records.push((path, Some(digest(&contents))));
+                continue;
+            }
+
+            place_file(&source, &path, link)?;
+            let digest = match digests.get(&name_in_wheel) {
+                Some(it) => it.clone(),
+                None => digest(&std::fs::read(&source)?),
+            };
+            records.push((path, Some(digest)));
+        }
+
+        let dist_info_path = root.join(dist_info);
+        if let Ok(entry_points) = std::fs::read_to_string(dist_info_path.join("entry_points.txt")) {
+            for (name, module, attr) in console_scripts(&entry_points) {
+                let launcher = launcher(&scheme.python_path, &module, &attr);
+                #[cfg(unix)]
+                let path = scheme.scripts.join(&name);
+                // Windows launchers are run with the interpreter by a batch file.
+                #[cfg(windows)]
+                let path = {
+                    let script = scheme.scripts.join(format!("{name}-script.py"));
+                    let batch = format!(
+                        "@\"{}\" \"%~dp0{name}-script.py\" %*\r\n",
+                        scheme.python_path.display()
+                    );
+                    let batch_path = scheme.scripts.join(format!("{name}.cmd"));
+                    write_file(&batch_path, batch.as_bytes(), true)?;
+                    records.push((batch_path, Some(digest(batch.as_bytes()))));
+                    script
+                };
+                write_file(&path, launcher.as_bytes(), true)?;
+                records.push((path, Some(digest(launcher.as_bytes()))));
+            }
+        }
+
+        let installer = format!("{INSTALLER_NAME}\n");
+        let installer_path = dist_info_path.join("INSTALLER");
+        write_file(&installer_path, installer.as_bytes(), false)?;
+        records.push((installer_path, Some(digest(installer.as_bytes()))));
+        records.push((dist_info_path.join("RECORD"), None));
+
+        write_record(root, &dist_info_path, &records)?;
+
+        InstalledDistribution::from_path(dist_info_path)
+    }
+
+    fn invalid<T: std::fmt::Display>(&self, reason: T) -> Error {
+        Error::InvalidWheel(format!("{}: {reason}", self.path.display()))
+    }
+}
+
+/// Add a file to an `InstalledDistribution`'s metadata directory and its RECORD.
+pub(crate) fn add_dist_info_file(
+    distribution: &InstalledDistribution,
+    name: &str,
+    contents: &[u8],
+) -> HuakResult<()> {
+    let path = distribution.path().join(name);
+    write_file(&path, contents, false)?;
+
+    let root = distribution
+        .path()
+        .parent()
+        .expect("dist-info directory should have a parent directory");
+    let record = distribution.path().join("RECORD");
+    let mut rows = std::fs::read_to_string(&record)?;
+    rows.push_str(&record_row(root, &path, Some(&digest(contents))));
+
+    Ok(std::fs::write(record, rows)?)
+}
+
+/// Remove a file from an installed distribution's .dist-info directory and its RECORD row.
+pub(crate) fn remove_dist_info_file(
+    distribution: &InstalledDistribution,
+    name: &str,
+) -> HuakResult<()> {
+    let path = distribution.path().join(name);
+    if !path.exists() {
+        return Ok(());
+    }
+    std::fs::remove_file(&path)?;
+
+    let root = distribution
+        .path()
+        .parent()
+        .expect("dist-info directory should have a parent directory");
+    let relative = relative_path(root, &path);
+    let record = distribution.path().join("RECORD");
+    let mut rows = String::new();
+    for line in std::fs::read_to_string(&record)?.lines() {
+        let is_removed = parse_record(line)
+            .first()
+            .and_then(|row| row.first())
+            .is_some_and(|it| *it == relative);
+        if !is_removed {
+            rows.push_str(line);
+            rows.push('\n');
+        }
+    }
+
+    Ok(std::fs::write(record, rows)?)
+}
+
+/// Parse the rows of a RECORD file (see <https://packaging.python.org/en/latest/specifications/recording-installed-packages/#the-record-file>).
+///
+/// RECORD files are CSV files with a path, a hash and a size on each row. Fields can be
+/// quoted.
+pub(crate) fn parse_record(contents: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+
+    for line in contents.lines().filter(|it| !it.trim().is_empty()) {
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+        let mut chars = line.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if quoted && chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => quoted = !quoted,
+                ',' if !quoted => fields.push(std::mem::take(&mut field)),
+                _ => field.push(c),
+            }
+        }
+        fields.push(field);
+        rows.push(fields);
+    }
+
+    rows
+}
+
+/// Normalize a path by resolving its `.` and `..` components without touching the file system.
+pub(crate) fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            it => normalized.push(it),
+        }
+    }
+
+    normalized
+}
+
+fn read_entry<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    name: &str,
+) -> HuakResult<Option<Vec<u8>>> {
+    let Ok(mut file) = archive.by_name(name) else {
+        return Ok(None);
+    };
+    let mut contents = Vec::new();
+    file.read_to_end(&mut contents)?;
+
+    Ok(Some(contents))
+}
+
+/// Get the value of a field in an email header formatted file like WHEEL.
+fn metadata_value<'a>(contents: &'a str, key: &str) -> Option<&'a str> {
+    contents.lines().find_map(|line| {
+        let (k, v) = line.split_once(':')?;
+        k.trim().eq_ignore_ascii_case(key).then(|| v.trim())
+    })
+}
+
+/// Get the (name, module, attribute) of each console and GUI script in an `entry_points.txt`.
+///
+/// See <https://packaging.python.org/en/latest/specifications/entry-points/>.
+fn console_scripts(contents: &str) -> Vec<(String, String, String)> {
+    let mut scripts = Vec::new();
+    let mut section = "";
+
+    for line in contents.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with(['#', ';']) {
+            continue;
+        }
+
+        if let Some(it) = line.strip_prefix('[').and_then(|it| it.strip_suffix(']')) {
+            section = it.trim();
+            continue;
+        }
+
+        if section != "console_scripts" && section != "gui_scripts" {
+            continue;
+        }
+
+        let Some((name, value)) = line.split_once('=') else {
+            continue;
+        };
+        // Extras (`module:attr [extra]`) don't change the launcher.
+        let value = value.split('[').next().unwrap_or_default().trim();
+        if let Some((module, attr)) = value.split_once(':') {
+            scripts.push((
+                name.trim().to_string(),
+                module.trim().to_string(),
+                attr.trim().to_string(),
+            ));
+        }
+    }
+
+    scripts
+}
+
+/// Build the launcher script for a console script.
+fn launcher(python_path: &Path, module: &str, attr: &str) -> String {
+    let import = attr.split('.').next().unwrap_or(attr);
+
+    format!(
+        r#"{}
+# -*- coding: utf-8 -*-
+import re
+import sys
+from {module} import {import}
+if __name__ == "__main__":
+    sys.argv[0] = re.sub(r"(-script\.pyw|\.exe)?$", "", sys.argv[0])
+    sys.exit({attr}())
+"#,
+        shebang(python_path)
+    )
+}
+
+/// Build the shebang used to run a script with a Python interpreter.
+fn shebang(python_path: &Path) -> String {
+    let python = python_path.display().to_string();
+
+    // Shebangs can't contain spaces and are limited to 127 bytes on Linux. Those
+    // interpreters are run with a /bin/sh trampoline instead.
+    if python.contains(' ') || python.len() > 127 {
+        format!("#!/bin/sh\n'''exec' \"{python}\" \"$0\" \"$@\"\n' '''")
+    } else {
+        format!("#!{python}")
+    }
+}
+
+fn write_file(path: &Path, contents: &[u8], executable: bool) -> HuakResult<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    // Replace rather than truncate existing files since they might be hard linked.
+    if path.is_file() || path.is_symlink() {
+        std::fs::remove_file(path)?;
+    }
+    std::fs::write(path, contents)?;
+
+    #[cfg(unix)]
+    if executable {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))?;
+    }
+    #[cfg(not(unix))]
+    let _ = executable;
+
+    Ok(())
+}
+
+/// Place a file by hard linking it, or by copying it if it can't be linked or `link` is false.
+fn place_file(source: &Path, path: &Path, link: bool) -> HuakResult<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if path.is_file() || path.is_symlink() {
+        std::fs::remove_file(path)?;
+    }
+
+    if link && std::fs::hard_link(source, path).is_ok() {
+        return Ok(());
+    }
+    std::fs::copy(source, path)?;
+
+    Ok(())
+}
+
+/// Get the paths of the files in a directory relative to it with `/` separators.
+fn relative_files(dir: &Path) -> HuakResult<Vec<String>> {
+    let mut files = Vec::new();
+    let mut dirs = vec![(dir.to_path_buf(), String::new())];
+
+    while let Some((dir, prefix)) = dirs.pop() {
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let name = format!("{prefix}{}", entry.file_name().to_string_lossy());
+            if entry.file_type()?.is_dir() {
+                dirs.push((entry.path(), format!("{name}/")));
+            } else {
+                files.push(name);
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+/// Get the "{hash},{size}" digests of a RECORD's rows by path. Only sha256 hashes are used.
+fn record_digests(contents: &str) -> HashMap<String, String> {
+    parse_record(contents)
+        .into_iter()
+        .filter_map(|row| {
+            let [path, hash, size] = <[String; 3]>::try_from(row).ok()?;
+            hash.starts_with("sha256=")
+                .then(|| (path, format!("{hash},{size}")))
+        })
+        .collect()
+}
+
+fn write_record(
+    root: &Path,
+    dist_info: &Path,
+    files: &[(PathBuf, Option<String>)],
+) -> HuakResult<()> {
+    let rows = files
+        .iter()
+        .map(|(path, digest)| record_row(root, path, digest.as_deref()))
+        .collect::<String>();
+
+    Ok(std::fs::write(dist_info.join("RECORD"), rows)?)
+}
+
+/// Build a RECORD row for a file. Paths are relative to the directory containing the
+/// distribution's *.dist-info directory.
+fn record_row(root: &Path, path: &Path, digest: Option<&str>) -> String {
+    let path = relative_path(root, path);
+    let path = if path.contains([',', '"']) {
+        format!("\"{}\"", path.replace('"', "\"\""))
+    } else {
+        path
+    };
+
+    match digest {
+        Some(it) => format!("{path},{it}\n"),
+        None => format!("{path},,\n"),
+    }
+}
+
+/// Get the "{hash},{size}" digest of a file's contents used in RECORD files.
+fn digest(contents: &[u8]) -> String {
+    format!("sha256={},{}", record_hash(contents), contents.len())
+}
+
+/// Get the urlsafe-base64-nopad encoded sha256 digest used in RECORD files.
+fn record_hash(contents: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(contents))
+}
+
+/// Get the path to `path` relative to `base` with `/` separators.
+fn relative_path(base: &Path, path: &Path) -> String {
+    let base = normalize_path(base);
+    let path = normalize_path(path);
+    let base = base.components().collect::<Vec<_>>();
+    let path = path.components().collect::<Vec<_>>();
+    let common = base.iter().zip(&path).take_while(|(a, b)| a == b).count();
+
+    std::iter::repeat("..".to_string())
+        .take(base.len() - common)
+        .chain(
+            path[common..]
+                .iter()
+                .map(|it| it.as_os_str().to_string_lossy().to_string()),
+        )
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Write a wheel with a module, a script, a data file and a console script to a directory.
+#[cfg(test)]
+pub(crate) fn write_mock_wheel(dir: &Path, name: &str, version: &str) -> PathBuf {
+    use std::{fmt::Write as _, io::Write};
+
+    let dist_info = format!("{name}-{version}.dist-info");
+    let data = format!("{name}-{version}.data");
+    let files = [
+        (
+            format!("{name}/__init__.py"),
+            "def main():\n    print('hello')\n".to_string(),
+        ),
+        (
+            format!("{data}/scripts/{name}-script"),
+            "#!python\nprint('script')\n".to_string(),
+        ),
+        (
+            format!("{data}/data/share/{name}.txt"),
+            "data\n".to_string(),
+        ),
+        (
+            format!("{dist_info}/METADATA"),
+            format!("Metadata-Version: 2.1\nName: {name}\nVersion: {version}\n"),
+        ),
+        (
+            format!("{dist_info}/WHEEL"),
+            "Wheel-Version: 1.0\nGenerator: huak\nRoot-Is-Purelib: true\nTag: py3-none-any\n"
+                .to_string(),
+        ),
+        (
+            format!("{dist_info}/entry_points.txt"),
+            format!("[console_scripts]\n{name} = {name}:main\n"),
+        ),
+    ];
+    let mut record = String::new();
+    for (path, contents) in &files {
+        writeln!(
+            record,
+            "{path},sha256={},{}",
+            record_hash(contents.as_bytes()),
+            contents.len()
+        )
+        .unwrap();
+    }
+    writeln!(record, "{dist_info}/RECORD,,").unwrap();
+
+    let path = dir.join(format!("{name}-{version}-py3-none-any.whl"));
+    let mut zip = zip::ZipWriter::new(File::create(&path).unwrap());
+    for (name, contents) in files
+        .iter()
+        .chain([(format!("{dist_info}/RECORD"), record)].iter())
+    {
+        zip.start_file(name, zip::write::FileOptions::default())
+            .unwrap();
+        zip.write_all(contents.as_bytes()).unwrap();
+    }
+    zip.finish().unwrap();
+
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    fn mock_scheme(root: &Path) -> InstallScheme {
+        InstallScheme {
+            purelib: root.join("lib").join("site-packages"),
+            platlib: root.join("lib").join("site-packages"),
+            scripts: root.join("bin"),
+            headers: root.join("include"),
+            data: root.to_path_buf(),
+            python_path: root.join("bin").join("python"),
+        }
+    }
+
+    #[test]
+    fn test_install_wheel() {
+        let dir = tempdir().unwrap();
+        let scheme = mock_scheme(&dir.path().join("venv"));
+        let wheel = Wheel::new(write_mock_wheel(dir.path(), "mock_package", "0.1.0")).unwrap();
+
+        let distribution = wheel.install(&scheme).unwrap();
+
+        assert_eq!(distribution.name(), "mock_package");
+        assert_eq!(distribution.version(), "0.1.0");
+        assert!(scheme
+            .purelib
+            .join("mock_package")
+            .join("__init__.py")
+            .exists());
+        assert!(dir.path().join("venv/share/mock_package.txt").exists());
+        assert_eq!(
+            std::fs::read_to_string(distribution.path().join("INSTALLER")).unwrap(),
+            "huak\n"
+        );
+
+        let python = scheme.python_path.display();
+        assert_eq!(
+            std::fs::read_to_string(scheme.scripts.join("mock_package-script")).unwrap(),
+            format!("#!{python}\nprint('script')\n")
+        );
+        #[cfg(unix)]
+        assert!(std::fs::read_to_string(scheme.scripts.join("mock_package"))
+            .unwrap()
+            .starts_with(&format!(
+                "#!{python}\n# -*- coding: utf-8 -*-\nimport re\nimport sys\nfrom mock_package import main\n"
+            )));
+
+        let record = std::fs::read_to_string(distribution.path().join("RECORD")).unwrap();
+        let paths = parse_record(&record)
+            .into_iter()
+            .map(|it| it[0].clone())
+            .collect::<Vec<_>>();
+        assert!(paths.contains(&"mock_package/__init__.py".to_string()));
+        assert!(paths.contains(&"../../bin/mock_package-script".to_string()));
+        assert!(paths.contains(&"../../share/mock_package.txt".to_string()));
+        assert!(paths.contains(&"mock_package-0.1.0.dist-info/RECORD".to_string()));
+    }
+
+    #[test]
+    fn test_dist_info_files() {
+        let dir = tempdir().unwrap();
+        let scheme = mock_scheme(&dir.path().join("venv"));
+        let wheel = Wheel::new(write_mock_wheel(dir.path(), "mock_package", "0.1.0")).unwrap();
+        let distribution = wheel.install(&scheme).unwrap();
+        let record_paths = || {
+            parse_record(&std::fs::read_to_string(distribution.path().join("RECORD")).unwrap())
+                .into_iter()
+                .map(|it| it[0].clone())
+                .collect::<Vec<_>>()
+        };
+        let direct_url = "mock_package-0.1.0.dist-info/direct_url.json".to_string();
+
+        add_dist_info_file(&distribution, "direct_url.json", b"{}").unwrap();
+
+        assert!(distribution.path().join("direct_url.json").exists());
+        assert!(record_paths().contains(&direct_url));
+
+        remove_dist_info_file(&distribution, "direct_url.json").unwrap();
+
+        assert!(!distribution.path().join("direct_url.json").exists());
+        assert!(!record_paths().contains(&direct_url));
+        assert!(record_paths().contains(&"mock_package/__init__.py".to_string()));
+    }
+
+    #[test]
+    fn test_uninstall_distribution() {
+        let dir = tempdir().unwrap();
+        let scheme = mock_scheme(&dir.path().join("venv"));
+        let wheel = Wheel::new(write_mock_wheel(dir.path(), "mock_package", "0.1.0")).unwrap();
+        let distribution = wheel.install(&scheme).unwrap();
+        let cache = scheme.purelib.join("mock_package").join("__pycache__");
+        std::fs::create_dir_all(&cache).unwrap();
+        std::fs::write(cache.join("__init__.cpython-311.pyc"), "").unwrap();
+        std::fs::write(scheme.purelib.join("other.py"), "").unwrap();
+
+        distribution.uninstall().unwrap();
+
+        assert!(!scheme.purelib.join("mock_package").exists());
+        assert!(!distribution.path().exists());
+        assert!(!scheme.scripts.join("mock_package-script").exists());
+        assert!(!dir.path().join("venv/share/mock_package.txt").exists());
+        assert!(scheme.purelib.join("other.py").exists());
+        assert!(scheme.scripts.exists());
+    }
+
+    #[test]
+    fn test_install_invalid_wheel() {
+        let dir = tempdir().unwrap();
+        let scheme = mock_scheme(&dir.path().join("venv"));
+        let path = dir.path().join("mock_package-0.1.0-py3-none-any.whl");
+        let mut zip = zip::ZipWriter::new(File::create(&path).unwrap());
+        for (name, contents) in [
+            (
+                "mock_package-0.1.0.dist-info/WHEEL",
+                "Wheel-Version: 1.0\nRoot-Is-Purelib: true\n",
+            ),
+            (
+                "mock_package-0.1.0.dist-info/RECORD",
+                "mock_package/__init__.py,sha256=invalid,0\n",
+            ),
+            ("mock_package/__init__.py", "print('tampered')\n"),
+        ] {
+            zip.start_file(name, zip::write::FileOptions::default())
+                .unwrap();
+            zip.write_all(contents.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+
+        assert!(matches!(
+            Wheel::new(path).unwrap().install(&scheme),
+            Err(Error::InvalidChecksum(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_record() {
+        assert_eq!(
+            parse_record("a.py,sha256=abc,3\n\"b,\"\"c\"\".py\",,\n"),
+            vec![
+                vec![
+                    "a.py".to_string(),
+                    "sha256=abc".to_string(),
+                    "3".to_string()
+                ],
+                vec!["b,\"c\".py".to_string(), String::new(), String::new()],
+            ]
+        );
+    }
+}
@@ -1,15 +1,19 @@
 use crate::package::Package;
 use crate::{
+    create_venv,
     environment::Environment,
     fs,
     manifest::LocalManifest,
-    python_environment::{default_venv_name, venv_config_file_name},
-    Config, Error, HuakResult, PythonEnvironment,
+    python_environment::{
+        default_venv_name, venv_config_file_name, CONDA_DEFAULT_ENV_ENV_VAR, CONDA_ENV_ENV_VAR,
+        VIRTUAL_ENV_ENV_VAR,
+    },
+    Config, Error, HuakResult, PythonEnvironment, VenvOptions,
 };
 use huak_toolchain::{Channel, LocalToolchain, LocalToolchainResolver, SettingsDb};
 use huak_workspace::{resolve_first, PathMarker};
+use std::path::PathBuf;
 use std::str::FromStr;
-use std::{path::PathBuf, process::Command};
 use toml_edit::Item;
 
 /// The `Workspace` is a struct for resolving things like the current `Package`
@@ -93,12 +97,35 @@ impl Workspace {
     /// Get the current `PythonEnvironment`. The current `PythonEnvironment` is one
     /// found by its configuration file or `Interpreter` nearest baseed on `Config` data.
     pub fn current_python_environment(&self) -> HuakResult<PythonEnvironment> {
+        if self.config.system {
+            return self.system_python_environment();
+        }
+
         let path = find_venv_root(&self.config.cwd, &self.root)?;
         let py_env = PythonEnvironment::new(path)?;
 
         Ok(py_env)
     }
 
+    /// Get the `PythonEnvironment` of the first python or python3 interpreter found from
+    /// the environment. Interpreters that belong to a virtual environment are skipped.
+    fn system_python_environment(&self) -> HuakResult<PythonEnvironment> {
+        let env = self.environment();
+        let (preferred, others): (Vec<_>, Vec<_>) = env.python_paths().partition(|it| {
+            it.file_stem()
+                .is_some_and(|name| name == "python" || name == "python3")
+        });
+
+        for python_path in preferred.into_iter().chain(others) {
+            let result = PythonEnvironment::system(python_path);
+            if !matches!(result, Err(Error::UnsupportedPythonEnvironment(_))) {
+                return result;
+            }
+        }
+
+        Err(Error::PythonNotFound)
+    }
+
     /// Create a `PythonEnvironment` for the `Workspace`.
     fn new_python_environment(&self) -> HuakResult<PythonEnvironment> {
         // Get a snapshot of the environment.
@@ -126,14 +153,8 @@ impl Workspace {
         let name = default_venv_name();
         let path = self.root.join(name);
 
-        // Create the `PythonEnvironment`. This uses the `venv` module distributed with Python.
-        // Note that this will fail on systems with minimal Python distributions.
-        let args = ["-m", "venv", name];
-        let mut cmd = Command::new(python_path);
-        cmd.args(args).current_dir(&self.root);
-        self.config.terminal().run_command(&mut cmd)?;
-
-        let python_env = PythonEnvironment::new(path)?;
+        // Create the `PythonEnvironment`. pip is only installed if it's needed.
+        let python_env = create_venv(path, &python_path, &VenvOptions::default(), &self.config)?;
 
         Ok(python_env)
     }
@@ -161,16 +182,32 @@ pub struct WorkspaceOptions {
 
 /// Search for a Python virtual environment.
 /// 1. If `VIRTUAL_ENV` exists then a venv is active; use it.
-/// 2. Walk from the `from` dir upwards, searching for dir containing the pyvenv.cfg file.
-/// 3. Stop after searching the `stop_after` dir.
+/// 2. If `CONDA_PREFIX` exists then a conda environment is active; use it unless it's
+///    conda's base environment.
+/// 3. Walk from the `from` dir upwards, searching for dir containing the pyvenv.cfg file.
+/// 4. Stop after searching the `stop_after` dir.
 pub fn find_venv_root<T: Into<PathBuf>>(from: T, stop_after: T) -> HuakResult<PathBuf> {
-    let from = from.into();
-    let stop_after = stop_after.into();
+    search_venv_root(from.into(), stop_after.into(), |key| {
+        std::env::var(key).ok()
+    })
+}
 
-    if let Ok(path) = std::env::var("VIRTUAL_ENV") {
+/// Search for a Python virtual environment reading environment variables with `var`.
+fn search_venv_root(
+    from: PathBuf,
+    stop_after: PathBuf,
+    var: impl Fn(&str) -> Option<String>,
+) -> HuakResult<PathBuf> {
+    if let Some(path) = var(VIRTUAL_ENV_ENV_VAR) {
         return Ok(PathBuf::from(path));
     }
 
+    if let Some(path) = var(CONDA_ENV_ENV_VAR) {
+        if var(CONDA_DEFAULT_ENV_ENV_VAR).as_deref() != Some("base") {
+            return Ok(PathBuf::from(path));
+        }
+    }
+
     if !from.is_dir() || !stop_after.is_dir() {
         return Err(Error::InternalError(
             "`from` and `stop_after` must be directories".to_string(),
@@ -247,3 +284,56 @@ fn resolve_local_toolchain(
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_find_venv_root() {
+        let dir = tempdir().unwrap();
+        let project = dir.path().join("project");
+        std::fs::create_dir_all(project.join(".venv")).unwrap();
+        std::fs::write(project.join(".venv").join(venv_config_file_name()), "").unwrap();
+        let conda_env = dir.path().join("conda").join("envs").join("my-env");
+        let search = |vars: &[(&str, &str)]| {
+            let vars = vars.iter().copied().collect::<HashMap<_, _>>();
+            search_venv_root(project.join(".venv"), project.clone(), |key| {
+                vars.get(key).map(ToString::to_string)
+            })
+            .unwrap()
+        };
+
+        assert_eq!(search(&[]), project.join(".venv"));
+        assert_eq!(
+            search(&[(CONDA_ENV_ENV_VAR, conda_env.to_str().unwrap())]),
+            conda_env
+        );
+        assert_eq!(
+            search(&[
+                (CONDA_ENV_ENV_VAR, conda_env.to_str().unwrap()),
+                (CONDA_DEFAULT_ENV_ENV_VAR, "my-env"),
+            ]),
+            conda_env
+        );
+        assert_eq!(
+            search(&[
+                (
+                    CONDA_ENV_ENV_VAR,
+                    dir.path().join("conda").to_str().unwrap()
+                ),
+                (CONDA_DEFAULT_ENV_ENV_VAR, "base"),
+            ]),
+            project.join(".venv")
+        );
+        assert_eq!(
+            search(&[
+                (VIRTUAL_ENV_ENV_VAR, project.to_str().unwrap()),
+                (CONDA_ENV_ENV_VAR, conda_env.to_str().unwrap()),
+            ]),
+            project
+        );
+    }
+}
@@ -3,6 +3,12 @@ use thiserror::Error as ThisError;
 #[allow(clippy::enum_variant_names)]
 #[derive(ThisError, Debug)]
 pub enum Error {
+    #[error("dependency group '{0}' includes itself")]
+    DependencyGroupCycle(String),
+    #[error("dependency group '{0}' not found")]
+    DependencyGroupNotFound(String),
+    #[error("dependency group '{0}' contains an invalid entry")]
+    InvalidDependencyGroup(String),
     #[error("{0}")]
     IOError(#[from] std::io::Error),
     #[error("{0}")]
@@ -53,7 +53,11 @@
 //! ```toml
 //! [tool.huak]
 //! toolchain = "3.11.6"
-//! repositories = { package = "url to repo" }  # TODO
+//!
+//! [tool.huak.repositories]
+//! default = "internal"
+//! indexes = { internal = "https://pypi.example.com/simple" }
+//! packages = { internal-package = "internal" }
 //!
 //! [tool.huak.run]  # TODO: Compare with new project.run table.
 //! hello-world = "python -c 'print('hello, world.')'"
@@ -66,8 +70,8 @@ pub use error::Error;
 use pep508_rs::Requirement;
 use std::{collections::HashMap, fmt::Display, path::Path, str::FromStr};
 use toml_edit::{Array, Document, Formatted, Item, Table, Value};
-use utils::{format_array, format_table};
-pub use utils::{sanitize_str, value_to_sanitized_string};
+use utils::{format_array, format_table, value_to_dependency_string};
+pub use utils::{normalize_name, sanitize_str, value_to_sanitized_string};
 
 mod error;
 mod utils;
@@ -141,6 +145,14 @@ impl PyProjectToml {
         self.get_mut("tool").and_then(Item::as_table_mut)
     }
 
+    /// Get Huak's `[tool.huak]` table.
+    #[must_use]
+    pub fn huak_table(&self) -> Option<&Table> {
+        self.tool_table()
+            .and_then(|it| it.get("huak"))
+            .and_then(Item::as_table)
+    }
+
     #[must_use]
     pub fn project_name(&self) -> Option<String> {
         self.project_table()
@@ -177,7 +189,7 @@ impl PyProjectToml {
     }
 
     pub fn set_project_description(&mut self, description: &str) -> &mut Self {
-        self.doc["project"]["version"] =
+        self.doc["project"]["description"] =
             Item::Value(Value::String(Formatted::new(description.to_string())));
         self
     }
@@ -192,7 +204,7 @@ impl PyProjectToml {
         Some(
             array
                 .into_iter()
-                .map(value_to_sanitized_string)
+                .map(value_to_dependency_string)
                 .collect::<Vec<_>>(),
         )
     }
@@ -216,6 +228,7 @@ impl PyProjectToml {
         self.project_dependencies().map_or(false, |it| {
             it.iter().any(|v| matches_dependency(v, dependency))
         }) || self.contains_project_optional_dependency_any(dependency)
+            || self.contains_dependency_group_dependency_any(dependency)
     }
 
     #[must_use]
@@ -256,7 +269,7 @@ impl PyProjectToml {
                     sanitize_str(it),
                     array
                         .iter()
-                        .map(value_to_sanitized_string)
+                        .map(value_to_dependency_string)
                         .collect::<Vec<_>>(),
                 );
             }
@@ -278,7 +291,12 @@ impl PyProjectToml {
             *item = Item::Table(Table::new());
         }
 
-        add_array_str(&mut item[group], dependency);
+        let key = item
+            .as_table()
+            .and_then(|it| group_key(it, group))
+            .unwrap_or_else(|| group.to_string());
+
+        add_array_str(&mut item[&key], dependency);
 
         self
     }
@@ -288,9 +306,13 @@ impl PyProjectToml {
         dependency: &str,
         group: &str,
     ) -> &mut Self {
-        let item = &mut self.doc["project"]["optional-dependencies"][group];
+        let Some(table) = self.project_optional_dependencies_mut() else {
+            return self;
+        };
 
-        remove_array_dependency(item, dependency);
+        if let Some(key) = group_key(table, group) {
+            remove_array_dependency(&mut table[&key], dependency);
+        }
 
         self
     }
@@ -312,13 +334,132 @@ impl PyProjectToml {
 
     #[must_use]
     pub fn contains_project_optional_dependency(&self, dependency: &str, group: &str) -> bool {
+        let Some(key) = self
+            .project_table()
+            .and_then(|it| it.get("optional-dependencies"))
+            .and_then(Item::as_table)
+            .and_then(|it| group_key(it, group))
+        else {
+            return false;
+        };
+
         // TODO(cnpryer): Perf
         self.project_optional_dependencies().map_or(false, |it| {
-            it.get(group).map_or(false, |g| {
+            it.get(&key).map_or(false, |g| {
                 g.iter().any(|s| matches_dependency(s, dependency))
             })
         })
     }
+
+    #[must_use]
+    pub fn dependency_groups_table(&self) -> Option<&Table> {
+        self.get("dependency-groups").and_then(Item::as_table)
+    }
+
+    pub fn dependency_groups_table_mut(&mut self) -> Option<&mut Table> {
+        self.get_mut("dependency-groups")
+            .and_then(Item::as_table_mut)
+    }
+
+    #[must_use]
+    pub fn dependency_group_names(&self) -> Option<Vec<String>> {
+        self.dependency_groups_table()
+            .map(|it| it.iter().map(|(k, _)| k.to_string()).collect())
+    }
+
+    /// Get the dependencies listed directly in each `[dependency-groups]` group (see
+    /// [PEP 735](https://peps.python.org/pep-0735/)). `include-group` entries aren't expanded.
+    #[must_use]
+    pub fn dependency_groups(&self) -> Option<HashMap<String, Vec<String>>> {
+        let table = self.dependency_groups_table()?;
+
+        Some(
+            table
+                .iter()
+                .filter_map(|(k, v)| {
+                    v.as_array().map(|array| {
+                        (
+                            k.to_string(),
+                            array
+                                .iter()
+                                .filter(|it| it.is_str())
+                                .map(value_to_dependency_string)
+                                .collect(),
+                        )
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    /// Get the dependencies of a `[dependency-groups]` group with any `include-group` entries
+    /// expanded. Group names are compared after normalization.
+    pub fn dependency_group(&self, group: &str) -> Result<Vec<String>, Error> {
+        let Some(table) = self.dependency_groups_table() else {
+            return Err(Error::DependencyGroupNotFound(group.to_string()));
+        };
+        let mut dependencies = Vec::new();
+        expand_dependency_group(table, group, &mut Vec::new(), &mut dependencies)?;
+
+        Ok(dependencies)
+    }
+
+    pub fn add_dependency_group_dependency(&mut self, dependency: &str, group: &str) -> &mut Self {
+        let item: &mut Item = &mut self.doc["dependency-groups"];
+
+        if item.is_none() {
+            *item = Item::Table(Table::new());
+        }
+
+        let key = item
+            .as_table()
+            .and_then(|it| group_key(it, group))
+            .unwrap_or_else(|| group.to_string());
+
+        add_array_str(&mut item[&key], dependency);
+
+        self
+    }
+
+    pub fn remove_dependency_group_dependency(
+        &mut self,
+        dependency: &str,
+        group: &str,
+    ) -> &mut Self {
+        let Some(table) = self.dependency_groups_table_mut() else {
+            return self;
+        };
+
+        if let Some(key) = group_key(table, group) {
+            remove_array_dependency(&mut table[&key], dependency);
+        }
+
+        self
+    }
+
+    #[must_use]
+    pub fn contains_dependency_group_dependency_any(&self, dependency: &str) -> bool {
+        self.dependency_groups().is_some_and(|it| {
+            it.values()
+                .any(|g| g.iter().any(|s| matches_dependency(s, dependency)))
+        })
+    }
+
+    /// Check if a dependency is listed directly in a `[dependency-groups]` group.
+    #[must_use]
+    pub fn contains_dependency_group_dependency(&self, dependency: &str, group: &str) -> bool {
+        let Some(key) = self
+            .dependency_groups_table()
+            .and_then(|it| group_key(it, group))
+        else {
+            return false;
+        };
+
+        self.dependency_groups().is_some_and(|it| {
+            it.get(&key)
+                .is_some_and(|g| g.iter().any(|s| matches_dependency(s, dependency)))
+        })
+    }
 }
 
 /// Read and return a `PyProjectToml` from a pyproject.toml file.
@@ -333,6 +474,9 @@ fn format_pyproject_toml(pyproject_toml: &mut PyProjectToml) -> &mut PyProjectTo
     pyproject_toml
         .project_optional_dependencies_mut()
         .map(format_table);
+    pyproject_toml
+        .dependency_groups_table_mut()
+        .map(format_table);
 
     pyproject_toml
 }
@@ -342,7 +486,8 @@ fn write_pyproject_toml<T: AsRef<Path>>(toml: &PyProjectToml, path: T) -> Result
     Ok(std::fs::write(path, toml.to_string())?)
 }
 
-// TODO(cnpryer): If contains requirement
+/// Add a dependency string to an array. An existing entry for the same package is replaced,
+/// keeping its extras and markers if `s` doesn't have any.
 fn add_array_str(item: &mut Item, s: &str) {
     if item.is_none() {
         *item = Item::Value(Value::Array(Array::new()));
@@ -351,21 +496,47 @@ fn add_array_str(item: &mut Item, s: &str) {
     // Replace the entry if it exists
     if let Some(index) = item.as_array().and_then(|it| {
         it.iter()
-            .position(|v| v.as_str().map_or(false, |x| matches_dependency(x, s)))
+            .position(|v| v.as_str().is_some_and(|x| matches_dependency(x, s)))
     }) {
         if let Some(array) = item.as_array_mut() {
-            array.replace(index, s);
+            let existing = array.get(index).and_then(Value::as_str).unwrap_or_default();
+            array.replace(index, replacement_dependency(existing, s));
         }
     } else {
         item.as_array_mut().get_or_insert(&mut Array::new()).push(s);
     }
 }
 
+/// Get the dependency string replacing `existing`. The extras and markers of `existing` are
+/// kept if `new` doesn't have any.
+fn replacement_dependency(existing: &str, new: &str) -> String {
+    let (Ok(existing), Ok(mut req)) = (Requirement::from_str(existing), Requirement::from_str(new))
+    else {
+        return new.to_string();
+    };
+    let mut changed = false;
+
+    if req.extras.as_ref().map_or(true, Vec::is_empty) && existing.extras.is_some() {
+        req.extras = existing.extras;
+        changed = true;
+    }
+    if req.marker.is_none() && existing.marker.is_some() {
+        req.marker = existing.marker;
+        changed = true;
+    }
+
+    if changed {
+        req.to_string()
+    } else {
+        new.to_string()
+    }
+}
+
 fn remove_array_dependency(item: &mut Item, dependency: &str) {
     if let Some(array) = item.as_array_mut() {
         array.retain(|it| {
-            it.as_str()
-                .map_or(false, |s| !matches_dependency(s, dependency))
+            !it.as_str()
+                .is_some_and(|s| matches_dependency(s, dependency))
         });
 
         if let Some(it) = array.get_mut(0) {
@@ -377,12 +548,79 @@ fn remove_array_dependency(item: &mut Item, dependency: &str) {
     }
 }
 
-fn matches_dependency(s: &str, dependency: &str) -> bool {
-    let Ok(req) = Requirement::from_str(dependency) else {
-        return false;
+/// Get the key of a group in a table matching a group name after normalization.
+fn group_key(table: &Table, group: &str) -> Option<String> {
+    let group = normalize_name(group);
+
+    table
+        .iter()
+        .find(|(k, _)| normalize_name(k) == group)
+        .map(|(k, _)| k.to_string())
+}
+
+/// Collect the dependencies of a `[dependency-groups]` group into `dependencies`, following
+/// `{include-group = "..."}` entries. `stack` holds the groups being expanded.
+fn expand_dependency_group(
+    table: &Table,
+    group: &str,
+    stack: &mut Vec<String>,
+    dependencies: &mut Vec<String>,
+) -> Result<(), Error> {
+    let Some(key) = group_key(table, group) else {
+        return Err(Error::DependencyGroupNotFound(group.to_string()));
+    };
+    if stack.contains(&key) {
+        return Err(Error::DependencyGroupCycle(key));
+    }
+    let Some(array) = table.get(&key).and_then(Item::as_array) else {
+        return Err(Error::InvalidDependencyGroup(key));
     };
 
-    Requirement::from_str(s).map_or(false, |it| it.name == req.name)
+    stack.push(key.clone());
+    for value in array {
+        if value.is_str() {
+            let dependency = value_to_dependency_string(value);
+            if !dependencies.contains(&dependency) {
+                dependencies.push(dependency);
+            }
+        } else if let Some(include) = value
+            .as_inline_table()
+            .and_then(|it| it.get("include-group"))
+            .and_then(Value::as_str)
+        {
+            expand_dependency_group(table, include, stack, dependencies)?;
+        } else {
+            return Err(Error::InvalidDependencyGroup(key));
+        }
+    }
+    stack.pop();
+
+    Ok(())
+}
+
+/// Check if two dependency strings are for the same package. Names are compared after
+/// normalization (see `normalize_name`).
+fn matches_dependency(s: &str, dependency: &str) -> bool {
+    match (dependency_name(s), dependency_name(dependency)) {
+        (Some(a), Some(b)) => normalize_name(&a) == normalize_name(&b),
+        _ => false,
+    }
+}
+
+/// Get the name of a dependency string. Requirements that aren't valid PEP 508 until they're
+/// expanded (like `my-lib @ {root:uri}/libs/my-lib`) use the name they start with.
+fn dependency_name(s: &str) -> Option<String> {
+    if let Ok(it) = Requirement::from_str(s) {
+        return Some(it.name);
+    }
+
+    let name = s
+        .trim_start()
+        .split(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .next()
+        .unwrap_or_default();
+
+    (!name.is_empty() && s.contains('@')).then(|| name.to_string())
 }
 
 impl FromStr for PyProjectToml {
@@ -433,6 +671,152 @@ mod tests {
         assert!(!pyproject_toml.contains_project_optional_dependency("test", "test"));
     }
 
+    #[test]
+    fn test_get_dependencies_with_markers() {
+        let pyproject_toml = PyProjectToml::from_str(
+            r#"[project]
+dependencies = ["click; python_version >= '3.8'"]
+
+[project.optional-dependencies]
+dev = ["pytest>=7 ; sys_platform == 'linux'"]
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            pyproject_toml.project_dependencies().unwrap(),
+            vec!["click; python_version >= '3.8'".to_string()]
+        );
+        assert_eq!(
+            pyproject_toml.project_optional_dependencies().unwrap()["dev"],
+            vec!["pytest>=7 ; sys_platform == 'linux'".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_dependency_groups() {
+        let mut pyproject_toml = PyProjectToml::from_str(
+            r#"[project]
+name = "mock-project"
+
+[dependency-groups]
+test = ["pytest>=7", {include-group = "Type_Check"}]
+type-check = ["mypy", {include-group = "typing"}]
+typing = ["types-requests", "pytest>=7"]
+cycle = [{include-group = "cycle"}]
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            pyproject_toml.dependency_group("test").unwrap(),
+            vec!["pytest>=7", "mypy", "types-requests"]
+        );
+        assert_eq!(
+            pyproject_toml.dependency_groups().unwrap()["test"],
+            vec!["pytest>=7".to_string()]
+        );
+        assert!(matches!(
+            pyproject_toml.dependency_group("cycle"),
+            Err(Error::DependencyGroupCycle(_))
+        ));
+        assert!(matches!(
+            pyproject_toml.dependency_group("docs"),
+            Err(Error::DependencyGroupNotFound(_))
+        ));
+        assert!(pyproject_toml.contains_dependency_group_dependency("mypy", "TYPE.CHECK"));
+        assert!(!pyproject_toml.contains_dependency_group_dependency("mypy", "test"));
+        assert!(pyproject_toml.contains_project_dependency_any("types-requests"));
+        assert!(pyproject_toml.project_optional_dependencies().is_none());
+
+        pyproject_toml
+            .add_dependency_group_dependency("ruff", "type_check")
+            .add_dependency_group_dependency("sphinx", "docs")
+            .remove_dependency_group_dependency("pytest", "test")
+            .formatted();
+
+        assert_eq!(
+            pyproject_toml.to_string(),
+            r#"[project]
+name = "mock-project"
+
+[dependency-groups]
+test = [
+    {include-group = "Type_Check"},
+]
+type-check = [
+    "mypy",
+    {include-group = "typing"},
+    "ruff",
+]
+typing = [
+    "types-requests",
+    "pytest>=7",
+]
+cycle = [
+    {include-group = "cycle"},
+]
+docs = [
+    "sphinx",
+]
+"#
+        );
+    }
+
+    #[test]
+    fn test_normalized_dependency_names() {
+        let mut pyproject_toml = PyProjectToml::from_str(
+            r#"[project]
+name = "mock-project"
+dependencies = [
+    "Foo_Bar[socks]>=1.0 ; python_version >= '3.8'",  # comment
+    "zope.interface",
+]
+
+[project.optional-dependencies]
+Dev_Tools = ["Py.Test"]
+"#,
+        )
+        .unwrap();
+
+        assert!(pyproject_toml.contains_project_dependency("foo-bar"));
+        assert!(pyproject_toml.contains_project_dependency("FOO.BAR==2"));
+        assert!(pyproject_toml.contains_project_optional_dependency("py_test", "dev-tools"));
+
+        pyproject_toml
+            .add_project_dependency("foo.bar==2.0")
+            .add_project_dependency("zope-interface[docs]==6.0 ; sys_platform == 'linux'")
+            .add_project_optional_dependency("py-test>=7", "dev.tools")
+            .remove_project_optional_dependency("PY_TEST", "dev-tools")
+            .add_project_optional_dependency("ruff", "DEV_TOOLS");
+
+        assert_eq!(
+            pyproject_toml.to_string(),
+            r#"[project]
+name = "mock-project"
+dependencies = [
+    "foo.bar[socks] ==2.0 ; python_version >= '3.8'",  # comment
+    "zope-interface[docs]==6.0 ; sys_platform == 'linux'",
+]
+
+[project.optional-dependencies]
+Dev_Tools = ["ruff"]
+"#
+        );
+
+        pyproject_toml.remove_project_dependency("Foo-Bar");
+
+        assert!(!pyproject_toml.contains_project_dependency("foo_bar"));
+
+        pyproject_toml.add_project_dependency("My_Lib @ {root:uri}/libs/my-lib");
+
+        assert!(pyproject_toml.contains_project_dependency("my-lib"));
+
+        pyproject_toml.remove_project_dependency("my.lib");
+
+        assert!(!pyproject_toml.contains_project_dependency("my-lib"));
+    }
+
     #[test]
     fn test_get_tool() {
         let pyproject_toml = PyProjectToml::from_str(mock_pyproject_toml_content()).unwrap();
@@ -467,6 +851,13 @@ strip = true
             .unwrap();
 
         assert_eq!(toolchain, " \"default\"".to_string());
+        assert_eq!(
+            pyproject_toml
+                .huak_table()
+                .and_then(|it| it.get("toolchain"))
+                .and_then(Item::as_str),
+            Some("default")
+        );
     }
 
     #[test]
@@ -600,6 +991,25 @@ members = ["projects/*"]
         );
     }
 
+    #[test]
+    fn test_set_project_description() {
+        let mut pyproject_toml = PyProjectToml::from_str(
+            r#"[project]
+name = "mock-project"
+version = "0.0.1"
+"#,
+        )
+        .unwrap();
+
+        pyproject_toml.set_project_description("A mock project.");
+
+        assert_eq!(
+            pyproject_toml.project_description().as_deref(),
+            Some("A mock project.")
+        );
+        assert_eq!(pyproject_toml.project_version().as_deref(), Some("0.0.1"));
+    }
+
     #[test]
     fn test_update_tool_section() {
         let dir = TempDir::new().unwrap();
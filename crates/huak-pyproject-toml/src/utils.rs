@@ -8,6 +8,40 @@ pub fn value_to_sanitized_string(value: &Value) -> String {
     }
 }
 
+/// Get a dependency string from an array `Value`. Unlike `value_to_sanitized_string`
+/// quotes are kept since PEP 508 markers can end with one.
+pub(crate) fn value_to_dependency_string(value: &Value) -> String {
+    match value {
+        Value::String(string) => string.value().trim().to_string(),
+        _ => value.to_string(),
+    }
+}
+
+/// Normalize a name as described in [PEP 503](https://peps.python.org/pep-0503/#normalized-names).
+/// Runs of `-`, `_` and `.` are replaced with a single `-` and the name is lowercased.
+///
+/// ```
+/// use huak_pyproject_toml::normalize_name;
+///
+/// assert_eq!(normalize_name("Foo__Bar.baz"), "foo-bar-baz");
+/// ```
+#[must_use]
+pub fn normalize_name(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+
+    for c in name.trim().chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !normalized.ends_with('-') {
+                normalized.push('-');
+            }
+        } else {
+            normalized.push(c.to_ascii_lowercase());
+        }
+    }
+
+    normalized
+}
+
 #[must_use]
 pub fn sanitize_str(s: &str) -> String {
     s.trim_matches('\n')